@@ -2,8 +2,12 @@
 
 use clap::{Parser, ValueEnum};
 use nostrss_grpc::grpc::{
-    nostrss_grpc_client::NostrssGrpcClient, AddFeedRequest, DeleteFeedRequest, FeedInfoRequest,
-    FeedItem, FeedsListRequest,
+    nostrss_grpc_client::NostrssGrpcClient, AddFeedRequest, CatchUpItem, DeleteFeedRequest,
+    DiscoverFeedsRequest, DiscoveredFeedItem, FailedEntryItem, FeedErrorItem, FeedErrorsRequest,
+    FeedHealthItem, FeedInfoRequest, FeedItem, FeedProblemItem, FeedStatusRequest,
+    FeedsListRequest, ListOutboxRequest, ListPublicationsRequest, OutboxItem, PreviewFeedRequest,
+    PublicationItem, RetractEntryRequest, StartJobRequest, StopJobRequest, TickReportItem,
+    UpdateFeedRequest, ValidateFeedRequest,
 };
 use tabled::Tabled;
 use tonic::{async_trait, transport::Channel};
@@ -18,9 +22,21 @@ use super::CommandsHandler;
 #[derive(Clone, PartialEq, Parser, Debug, ValueEnum)]
 pub enum FeedActions {
     Add,
+    Edit,
     Delete,
     List,
     Info,
+    Pause,
+    Resume,
+    Retract,
+    History,
+    Outbox,
+    Errors,
+    Status,
+    Check,
+    Preview,
+    Shadow,
+    Live,
 }
 
 pub struct FeedCommandsHandler {
@@ -43,6 +59,17 @@ pub struct FullFeedTemplate {
     pub template: String,
     pub cache_size: Option<String>,
     pub pow_level: String,
+    pub paused: String,
+    pub mirror_deletions: String,
+    pub auth: String,
+    pub headers: String,
+    pub proxy: String,
+    pub shadow: String,
+    pub shadow_file: String,
+    pub kind: String,
+    pub on_update: String,
+    pub catch_up: String,
+    pub watch: String,
 }
 
 impl From<FeedItem> for FullFeedTemplate {
@@ -54,6 +81,8 @@ impl From<FeedItem> for FullFeedTemplate {
             None => None,
         };
         let pow_level = value.pow_level.to_string();
+        let paused = value.paused.unwrap_or(false).to_string();
+        let mirror_deletions = value.mirror_deletions.unwrap_or(false).to_string();
         Self {
             id: value.id,
             name: value.name,
@@ -64,11 +93,34 @@ impl From<FeedItem> for FullFeedTemplate {
             template: value.template.unwrap_or("".to_string()),
             cache_size,
             pow_level,
+            paused,
+            mirror_deletions,
+            auth: value.auth.unwrap_or_default(),
+            headers: value.headers.join(", "),
+            proxy: value.proxy.unwrap_or_default(),
+            shadow: value.shadow.unwrap_or(false).to_string(),
+            shadow_file: value.shadow_file.unwrap_or_default(),
+            kind: value.kind.unwrap_or_default(),
+            on_update: value.on_update.unwrap_or_default(),
+            catch_up: value
+                .catch_up
+                .map(Self::catch_up_to_string)
+                .unwrap_or_default(),
+            watch: value.watch.unwrap_or(false).to_string(),
         }
     }
 }
 
 impl FullFeedTemplate {
+    // Formats a catch-up policy along its limit, e.g: `max_entries (5)`
+    fn catch_up_to_string(catch_up: CatchUpItem) -> String {
+        match (catch_up.count, catch_up.seconds) {
+            (Some(count), _) => format!("{} ({})", catch_up.policy, count),
+            (_, Some(seconds)) => format!("{} ({}s)", catch_up.policy, seconds),
+            _ => catch_up.policy,
+        }
+    }
+
     fn properties_to_vec(&self) -> Vec<FeedDetailsTemplate> {
         let cache_binding = self.cache_size.clone().unwrap_or_else(|| "".to_string());
         let properties: Vec<(String, &String)> = [
@@ -81,6 +133,17 @@ impl FullFeedTemplate {
             ("template".to_string(), &self.template),
             ("cache_size".to_string(), &cache_binding),
             ("pow_level".to_string(), &self.pow_level),
+            ("paused".to_string(), &self.paused),
+            ("mirror_deletions".to_string(), &self.mirror_deletions),
+            ("auth".to_string(), &self.auth),
+            ("headers".to_string(), &self.headers),
+            ("proxy".to_string(), &self.proxy),
+            ("shadow".to_string(), &self.shadow),
+            ("shadow_file".to_string(), &self.shadow_file),
+            ("kind".to_string(), &self.kind),
+            ("on_update".to_string(), &self.on_update),
+            ("catch_up".to_string(), &self.catch_up),
+            ("watch".to_string(), &self.watch),
         ]
         .to_vec();
 
@@ -111,16 +174,217 @@ impl FeedsTemplate {
     }
 }
 
+#[derive(Tabled)]
+struct DiscoveredFeedsTemplate {
+    number: usize,
+    title: String,
+    url: String,
+    content_type: String,
+}
+
+impl DiscoveredFeedsTemplate {
+    fn new(number: usize, feed: DiscoveredFeedItem) -> Self {
+        Self {
+            number,
+            title: feed.title.unwrap_or_default(),
+            url: feed.url,
+            content_type: feed.content_type.unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(Tabled)]
+struct PublicationsTemplate {
+    entry_id: String,
+    profile_id: String,
+    event_id: String,
+    kind: u64,
+    relays: String,
+    status: String,
+    timestamp: i64,
+}
+
+impl From<PublicationItem> for PublicationsTemplate {
+    fn from(value: PublicationItem) -> Self {
+        Self {
+            entry_id: value.entry_id,
+            profile_id: value.profile_id,
+            event_id: value.event_id,
+            kind: value.kind,
+            relays: format!("{}/{}", value.relays.len(), value.targets.len()),
+            status: value.status,
+            timestamp: value.timestamp,
+        }
+    }
+}
+
+#[derive(Tabled)]
+struct OutboxTemplate {
+    entry_id: String,
+    event_id: String,
+    status: String,
+    acks: String,
+    attempts: u32,
+    next_attempt: String,
+    last_error: String,
+}
+
+impl From<OutboxItem> for OutboxTemplate {
+    fn from(value: OutboxItem) -> Self {
+        let acks = value.deliveries.iter().filter(|d| d.acked).count();
+        let pending: Vec<_> = value.deliveries.iter().filter(|d| !d.acked).collect();
+
+        Self {
+            entry_id: value.entry_id,
+            event_id: value.event_id,
+            status: value.status,
+            acks: format!("{}/{}", acks, value.required_acks),
+            attempts: pending.iter().map(|d| d.attempts).max().unwrap_or(0),
+            next_attempt: pending
+                .iter()
+                .map(|d| d.next_attempt)
+                .min()
+                .map(|n| n.to_string())
+                .unwrap_or_default(),
+            last_error: pending
+                .iter()
+                .find_map(|d| d.last_error.clone())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(Tabled)]
+struct FeedErrorsTemplate {
+    timestamp: i64,
+    kind: String,
+    profile_id: String,
+    entry_id: String,
+    message: String,
+}
+
+impl From<FeedErrorItem> for FeedErrorsTemplate {
+    fn from(value: FeedErrorItem) -> Self {
+        Self {
+            timestamp: value.timestamp,
+            kind: value.kind,
+            profile_id: value.profile_id.unwrap_or_default(),
+            entry_id: value.entry_id.unwrap_or_default(),
+            message: value.message,
+        }
+    }
+}
+
+#[derive(Tabled)]
+struct FeedProblemTemplate {
+    field: String,
+    message: String,
+}
+
+impl From<FeedProblemItem> for FeedProblemTemplate {
+    fn from(value: FeedProblemItem) -> Self {
+        Self {
+            field: value.field,
+            message: value.message,
+        }
+    }
+}
+
+#[derive(Tabled)]
+struct FailedEntryTemplate {
+    entry_id: String,
+    reason: String,
+}
+
+impl From<FailedEntryItem> for FailedEntryTemplate {
+    fn from(value: FailedEntryItem) -> Self {
+        Self {
+            entry_id: value.entry_id,
+            reason: value.reason,
+        }
+    }
+}
+
+// Builds the rows describing the last processing of a feed
+fn tick_report_to_vec(report: &TickReportItem) -> Vec<FeedDetailsTemplate> {
+    let row = |key: &str, value: String| FeedDetailsTemplate {
+        key: key.to_string(),
+        value,
+    };
+
+    [
+        row("timestamp", report.timestamp.to_string()),
+        row("published", report.published.join(", ")),
+        row("updated", report.updated.join(", ")),
+        row("deleted", report.deleted.join(", ")),
+        row("skipped", report.skipped.to_string()),
+        row("failed", report.failed.len().to_string()),
+        row("error", report.error.clone().unwrap_or_default()),
+    ]
+    .into()
+}
+
+// Builds the rows describing the health of a feed
+fn health_to_vec(health: &FeedHealthItem) -> Vec<FeedDetailsTemplate> {
+    let row = |key: &str, value: String| FeedDetailsTemplate {
+        key: key.to_string(),
+        value,
+    };
+    let optional = |value: Option<i64>| value.map(|v| v.to_string()).unwrap_or_default();
+
+    [
+        row("last_fetch", optional(health.last_fetch)),
+        row("last_success", optional(health.last_success)),
+        row(
+            "consecutive_failures",
+            health.consecutive_failures.to_string(),
+        ),
+        row("last_error", health.last_error.clone().unwrap_or_default()),
+        row(
+            "http_status",
+            health
+                .http_status
+                .map(|s| s.to_string())
+                .unwrap_or_default(),
+        ),
+        row("entries_seen", health.entries_seen.to_string()),
+        row("entries_published", health.entries_published.to_string()),
+        row("next_run", optional(health.next_run)),
+    ]
+    .into()
+}
+
 #[async_trait]
 impl CommandsHandler for FeedCommandsHandler {}
 
 impl FeedCommandsHandler {
-    pub async fn handle(&mut self, action: FeedActions, opts: CliOptions) {
+    pub async fn handle(&mut self, action: FeedActions, id: Option<String>, opts: CliOptions) {
         match action {
             FeedActions::Add => self.add(opts).await,
-            FeedActions::Delete => self.delete(opts).await,
+            FeedActions::Edit => self.edit(id, opts).await,
+            FeedActions::Delete => self.delete(id, opts).await,
             FeedActions::List => self.list().await,
-            FeedActions::Info => self.info().await,
+            FeedActions::Info => self.info(id).await,
+            FeedActions::Pause => self.pause(id, opts).await,
+            FeedActions::Resume => self.resume(id, opts).await,
+            FeedActions::Retract => self.retract(id).await,
+            FeedActions::History => self.history(id).await,
+            FeedActions::Outbox => self.outbox(id).await,
+            FeedActions::Errors => self.errors(id).await,
+            FeedActions::Status => self.status(id).await,
+            FeedActions::Check => self.check(id).await,
+            FeedActions::Preview => self.preview(id, opts).await,
+            FeedActions::Shadow => self.set_shadow(id, true, opts).await,
+            FeedActions::Live => self.set_shadow(id, false, opts).await,
+        }
+    }
+
+    // Retrieves the id of the targeted feed from the command arguments,
+    // or prompts for it if none was provided
+    fn get_feed_id(&self, id: Option<String>, prompt: &str) -> String {
+        match id {
+            Some(id) => id,
+            None => self.get_input(prompt, Some(InputValidators::required_input_validator)),
         }
     }
 
@@ -149,7 +413,14 @@ impl FeedCommandsHandler {
         println!("=== Add a new feed ===");
         let id = self.get_input("Id: ", Some(InputValidators::required_input_validator));
         let name = self.get_input("Name: ", Some(InputValidators::required_input_validator));
-        let url = self.get_input("Url: ", Some(InputValidators::url_validator));
+        let url = self.get_input(
+            "Url (feed or website): ",
+            Some(InputValidators::url_validator),
+        );
+        let url = match self.discover(url).await {
+            Some(url) => url,
+            None => return,
+        };
         let schedule = self.get_input(
             "scheduler pattern: ",
             Some(InputValidators::cron_pattern_validator),
@@ -167,6 +438,10 @@ impl FeedCommandsHandler {
             Some(cache_size_input.parse().unwrap_or(1000))
         };
         let pow_level = self.get_input("Pow Level: ", None).parse().unwrap_or(0);
+        let proxy: Option<String> = InputFormatter::string_nullifier(self.get_input(
+            "(optional) Proxy url (socks5h://, http:// or ip:port): ",
+            None,
+        ));
 
         let request = tonic::Request::new(AddFeedRequest {
             feed: FeedItem {
@@ -179,6 +454,17 @@ impl FeedCommandsHandler {
                 tags,
                 cache_size,
                 pow_level,
+                paused: None,
+                mirror_deletions: None,
+                auth: None,
+                headers: Vec::new(),
+                proxy,
+                shadow: None,
+                shadow_file: None,
+                kind: None,
+                on_update: None,
+                catch_up: None,
+                watch: None,
             },
             save: Some(opts.save),
         });
@@ -195,8 +481,200 @@ impl FeedCommandsHandler {
         }
     }
 
-    async fn delete(&mut self, opts: CliOptions) {
-        let id = self.get_input("Id: ", None);
+    // Finds the feed to add from the provided url, which might be the url of a website.
+    // Prompts to choose a feed if the website provides several ones.
+    async fn discover(&mut self, url: String) -> Option<String> {
+        let request = tonic::Request::new(DiscoverFeedsRequest { url: url.clone() });
+
+        let mut feeds = match self.client.discover_feeds(request).await {
+            Ok(response) => response.into_inner().feeds,
+            Err(e) => {
+                println!("Error {}: {}", e.code(), e.message());
+                return None;
+            }
+        };
+
+        if feeds.len() == 1 {
+            let feed = feeds.remove(0);
+            if feed.url != url {
+                println!("Using feed {} found at {}", feed.url, url);
+            }
+            return Some(feed.url);
+        }
+
+        println!("Several feeds found at {}", url);
+        let rows = feeds
+            .iter()
+            .enumerate()
+            .map(|(i, feed)| DiscoveredFeedsTemplate::new(i + 1, feed.clone()))
+            .collect();
+        self.print(rows);
+
+        loop {
+            let number = self.get_input("Feed number: ", None);
+            match number.parse::<usize>() {
+                Ok(number) if number >= 1 && number <= feeds.len() => {
+                    return Some(feeds.remove(number - 1).url);
+                }
+                _ => println!("Invalid value provided."),
+            }
+        }
+    }
+
+    async fn edit(&mut self, id: Option<String>, opts: CliOptions) {
+        println!("=== Edit a feed ===");
+        let id = self.get_feed_id(id, "Id: ");
+
+        let request = tonic::Request::new(FeedInfoRequest {
+            id: id.trim().to_string(),
+        });
+
+        let feed = match self.client.feed_info(request).await {
+            Ok(response) => response.into_inner().feed,
+            Err(e) => {
+                println!("Error {}: {}", e.code(), e.message());
+                return;
+            }
+        };
+
+        println!("Leave a value empty to keep the current one");
+        let name = InputFormatter::string_nullifier(
+            self.get_input(&format!("Name [{}]: ", feed.name), None),
+        );
+        let url = InputFormatter::string_nullifier(self.get_input(
+            &format!("Url [{}]: ", feed.url),
+            Some(InputValidators::optional_url_validator),
+        ));
+        let schedule = InputFormatter::string_nullifier(self.get_input(
+            &format!("scheduler pattern [{}]: ", feed.schedule),
+            Some(InputValidators::optional_cron_pattern_validator),
+        ));
+        // Fields set to "none" are removed from the feed
+        let mut clear = Vec::new();
+        let profiles = self.get_input(
+            &format!(
+                "profiles ids (separated with coma, \"none\" to remove) [{}]: ",
+                feed.profiles.join(",")
+            ),
+            None,
+        );
+        let profiles = match profiles.trim() {
+            "none" => {
+                clear.push("profiles".to_string());
+                Vec::new()
+            }
+            _ => InputFormatter::optional_input_to_vec(profiles),
+        };
+        let tags = self.get_input(
+            &format!(
+                "Tags (separated with coma, \"none\" to remove) [{}]: ",
+                feed.tags.join(",")
+            ),
+            None,
+        );
+        let tags = match tags.trim() {
+            "none" => {
+                clear.push("tags".to_string());
+                Vec::new()
+            }
+            _ => InputFormatter::optional_input_to_vec(tags),
+        };
+        let template = InputFormatter::string_nullifier(self.get_input(
+            &format!(
+                "Template path (\"none\" to remove) [{}]: ",
+                feed.template.unwrap_or_default()
+            ),
+            None,
+        ))
+        .filter(|t| match t.as_str() {
+            "none" => {
+                clear.push("template".to_string());
+                false
+            }
+            _ => true,
+        });
+        let cache_size = InputFormatter::string_nullifier(self.get_input(
+            &format!(
+                "Cache size [{}]: ",
+                feed.cache_size.map(|c| c.to_string()).unwrap_or_default()
+            ),
+            None,
+        ))
+        .and_then(|c| c.parse().ok());
+        let pow_level = InputFormatter::string_nullifier(
+            self.get_input(&format!("Pow Level [{}]: ", feed.pow_level), None),
+        )
+        .and_then(|p| p.parse().ok());
+        let proxy = InputFormatter::string_nullifier(self.get_input(
+            &format!(
+                "Proxy url (\"none\" to remove) [{}]: ",
+                feed.proxy.unwrap_or_default()
+            ),
+            None,
+        ))
+        .map(|p| match p.trim() {
+            "none" => String::new(),
+            p => p.to_string(),
+        });
+        let shadow_file = InputFormatter::string_nullifier(self.get_input(
+            &format!(
+                "Shadow file (\"none\" to remove) [{}]: ",
+                feed.shadow_file.unwrap_or_default()
+            ),
+            None,
+        ))
+        .map(|p| match p.trim() {
+            "none" => String::new(),
+            p => p.to_string(),
+        });
+        let kind = InputFormatter::string_nullifier(self.get_input(
+            &format!("Kind (note, article) [{}]: ", feed.kind.unwrap_or_default()),
+            None,
+        ));
+        let on_update = InputFormatter::string_nullifier(self.get_input(
+            &format!(
+                "Edited entries policy (ignore, correction, replace, delete_and_repost) [{}]: ",
+                feed.on_update.unwrap_or_default()
+            ),
+            None,
+        ));
+
+        let request = tonic::Request::new(UpdateFeedRequest {
+            id: feed.id,
+            name,
+            url,
+            schedule,
+            profiles,
+            tags,
+            template,
+            cache_size,
+            pow_level,
+            save: Some(opts.save),
+            proxy,
+            shadow: None,
+            shadow_file,
+            clear,
+            kind,
+            on_update,
+            ..Default::default()
+        });
+
+        let response = self.client.update_feed(request).await;
+
+        match response {
+            Ok(response) => {
+                println!("Feed successfully updated");
+                let feed = FullFeedTemplate::from(response.into_inner().feed);
+                self.print(feed.properties_to_vec());
+            }
+            Err(e) => {
+                println!("Error {}: {}", e.code(), e.message());
+            }
+        }
+    }
+
+    async fn delete(&mut self, id: Option<String>, opts: CliOptions) {
+        let id = self.get_feed_id(id, "Id: ");
 
         let request = tonic::Request::new(DeleteFeedRequest {
             id,
@@ -215,8 +693,288 @@ impl FeedCommandsHandler {
         }
     }
 
-    async fn info(&mut self) {
-        let id = self.get_input("Id: ", None);
+    // Switches a feed to shadow mode, or back to live publishing
+    async fn set_shadow(&mut self, id: Option<String>, shadow: bool, opts: CliOptions) {
+        let id = self.get_feed_id(id, "Id: ");
+
+        let request = tonic::Request::new(UpdateFeedRequest {
+            id: id.trim().to_string(),
+            shadow: Some(shadow),
+            save: Some(opts.save),
+            ..Default::default()
+        });
+
+        let response = self.client.update_feed(request).await;
+
+        match response {
+            Ok(_) => match shadow {
+                true => println!("Feed switched to shadow mode"),
+                false => println!("Feed switched to live publishing"),
+            },
+            Err(e) => {
+                println!("Error {}: {}", e.code(), e.message());
+            }
+        }
+    }
+
+    async fn pause(&mut self, id: Option<String>, opts: CliOptions) {
+        let id = self.get_feed_id(id, "Id: ");
+
+        let request = tonic::Request::new(StopJobRequest {
+            feed_id: id.trim().to_string(),
+            save: Some(opts.save),
+        });
+
+        let response = self.client.stop_job(request).await;
+
+        match response {
+            Ok(_) => {
+                println!("Feed successfully paused");
+            }
+            Err(e) => {
+                println!("Error {}: {}", e.code(), e.message());
+            }
+        }
+    }
+
+    async fn resume(&mut self, id: Option<String>, opts: CliOptions) {
+        let id = self.get_feed_id(id, "Id: ");
+
+        let request = tonic::Request::new(StartJobRequest {
+            feed_id: id.trim().to_string(),
+            save: Some(opts.save),
+        });
+
+        let response = self.client.start_job(request).await;
+
+        match response {
+            Ok(_) => {
+                println!("Feed successfully resumed");
+            }
+            Err(e) => {
+                println!("Error {}: {}", e.code(), e.message());
+            }
+        }
+    }
+
+    async fn retract(&mut self, id: Option<String>) {
+        let feed_id = self.get_feed_id(id, "Feed id: ");
+        let entry_id = self.get_input(
+            "Entry id (guid): ",
+            Some(InputValidators::required_input_validator),
+        );
+
+        let request = tonic::Request::new(RetractEntryRequest {
+            feed_id: feed_id.trim().to_string(),
+            entry_id: entry_id.trim().to_string(),
+        });
+
+        let response = self.client.retract_entry(request).await;
+
+        match response {
+            Ok(response) => {
+                println!(
+                    "Entry retracted. {} deletion request(s) sent",
+                    response.into_inner().deletions
+                );
+            }
+            Err(e) => {
+                println!("Error {}: {}", e.code(), e.message());
+            }
+        }
+    }
+
+    async fn history(&mut self, id: Option<String>) {
+        let feed_id = self.get_feed_id(id, "Id: ");
+
+        let request = tonic::Request::new(ListPublicationsRequest {
+            feed_id: Some(feed_id.trim().to_string()),
+            limit: None,
+        });
+        let response = self.client.list_publications(request).await;
+
+        match response {
+            Ok(response) => {
+                let raws: Vec<PublicationsTemplate> = response
+                    .into_inner()
+                    .publications
+                    .into_iter()
+                    .map(PublicationsTemplate::from)
+                    .collect();
+
+                println!("=== Publications of feed {} ===", feed_id.trim());
+                self.print(raws);
+            }
+            Err(e) => {
+                println!("Error {}: {}", e.code(), e.message());
+            }
+        }
+    }
+
+    async fn outbox(&mut self, id: Option<String>) {
+        let feed_id = self.get_feed_id(id, "Id: ");
+
+        let request = tonic::Request::new(ListOutboxRequest {
+            feed_id: Some(feed_id.trim().to_string()),
+            status: None,
+        });
+        let response = self.client.list_outbox(request).await;
+
+        match response {
+            Ok(response) => {
+                let raws: Vec<OutboxTemplate> = response
+                    .into_inner()
+                    .events
+                    .into_iter()
+                    .map(OutboxTemplate::from)
+                    .collect();
+
+                println!("=== Outbox of feed {} ===", feed_id.trim());
+                self.print(raws);
+            }
+            Err(e) => {
+                println!("Error {}: {}", e.code(), e.message());
+            }
+        }
+    }
+
+    async fn errors(&mut self, id: Option<String>) {
+        let feed_id = self.get_feed_id(id, "Id: ");
+
+        let request = tonic::Request::new(FeedErrorsRequest {
+            feed_id: feed_id.trim().to_string(),
+        });
+        let response = self.client.feed_errors(request).await;
+
+        match response {
+            Ok(response) => {
+                let response = response.into_inner();
+                let raws: Vec<FeedErrorsTemplate> = response
+                    .errors
+                    .into_iter()
+                    .map(FeedErrorsTemplate::from)
+                    .collect();
+
+                println!(
+                    "=== {} error(s) on feed {} ===",
+                    response.count,
+                    feed_id.trim()
+                );
+                self.print(raws);
+            }
+            Err(e) => {
+                println!("Error {}: {}", e.code(), e.message());
+            }
+        }
+    }
+
+    async fn check(&mut self, id: Option<String>) {
+        let feed_id = self.get_feed_id(id, "Id: ");
+
+        let request = tonic::Request::new(ValidateFeedRequest {
+            feed_id: Some(feed_id.trim().to_string()),
+            feed: None,
+        });
+        let response = self.client.validate_feed(request).await;
+
+        match response {
+            Ok(response) => {
+                let problems = response.into_inner().problems;
+
+                if problems.is_empty() {
+                    println!("Feed {} is valid", feed_id.trim());
+                    return;
+                }
+
+                let raws: Vec<FeedProblemTemplate> = problems
+                    .into_iter()
+                    .map(FeedProblemTemplate::from)
+                    .collect();
+
+                println!(
+                    "=== {} problem(s) on feed {} ===",
+                    raws.len(),
+                    feed_id.trim()
+                );
+                self.print(raws);
+            }
+            Err(e) => {
+                println!("Error {}: {}", e.code(), e.message());
+            }
+        }
+    }
+
+    async fn preview(&mut self, id: Option<String>, opts: CliOptions) {
+        let feed_id = self.get_feed_id(id, "Id: ");
+
+        let request = tonic::Request::new(PreviewFeedRequest {
+            feed_id: feed_id.trim().to_string(),
+            entries: opts.entries,
+        });
+        let response = self.client.preview_feed(request).await;
+
+        match response {
+            Ok(response) => {
+                let events = response.into_inner().events;
+
+                if events.is_empty() {
+                    println!("Feed {} has no entry to preview", feed_id.trim());
+                    return;
+                }
+
+                for event in events {
+                    println!(
+                        "=== Entry {} for profile {} (pow level {}) ===",
+                        event.entry_id, event.profile_id, event.pow_level
+                    );
+
+                    match (event.event, event.error) {
+                        (Some(event), _) => println!("{}", event),
+                        (None, error) => println!("Error: {}", error.unwrap_or_default()),
+                    }
+                }
+            }
+            Err(e) => {
+                println!("Error {}: {}", e.code(), e.message());
+            }
+        }
+    }
+
+    async fn status(&mut self, id: Option<String>) {
+        let feed_id = self.get_feed_id(id, "Id: ");
+
+        let request = tonic::Request::new(FeedStatusRequest {
+            feed_id: feed_id.trim().to_string(),
+        });
+        let response = self.client.feed_status(request).await;
+
+        match response {
+            Ok(response) => {
+                let Some(report) = response.into_inner().last_report else {
+                    println!("Feed {} has not been processed yet", feed_id.trim());
+                    return;
+                };
+
+                println!("=== Last processing of feed {} ===", feed_id.trim());
+                self.print(tick_report_to_vec(&report));
+
+                if !report.failed.is_empty() {
+                    let raws: Vec<FailedEntryTemplate> = report
+                        .failed
+                        .into_iter()
+                        .map(FailedEntryTemplate::from)
+                        .collect();
+                    self.print(raws);
+                }
+            }
+            Err(e) => {
+                println!("Error {}: {}", e.code(), e.message());
+            }
+        }
+    }
+
+    async fn info(&mut self, id: Option<String>) {
+        let id = self.get_feed_id(id, "Id: ");
 
         let request = tonic::Request::new(FeedInfoRequest {
             id: id.trim().to_string(),
@@ -228,8 +986,17 @@ impl FeedCommandsHandler {
                 let feed = response.into_inner().feed;
 
                 let feed = FullFeedTemplate::from(feed);
+                let mut rows = feed.properties_to_vec();
 
-                self.print(feed.properties_to_vec());
+                let request = tonic::Request::new(FeedStatusRequest {
+                    feed_id: id.trim().to_string(),
+                });
+
+                if let Ok(response) = self.client.feed_status(request).await {
+                    rows.append(&mut health_to_vec(&response.into_inner().health));
+                }
+
+                self.print(rows);
             }
             Err(e) => {
                 println!("Error {}: {}", e.code(), e.message());
@@ -2,41 +2,217 @@
 
 use super::CommandsHandler;
 use clap::{Parser, ValueEnum};
-use nostrss_grpc::grpc::nostrss_grpc_client::NostrssGrpcClient;
+use nostrss_grpc::grpc::{
+    nostrss_grpc_client::NostrssGrpcClient, AddRelayRequest, DeleteRelayRequest, RelayInfoRequest,
+    RelayItem, RelaysListRequest,
+};
+use tabled::{Table, Tabled};
 use tonic::async_trait;
 use tonic::transport::Channel;
 
+use crate::{
+    input::{formatter::InputFormatter, input::InputValidators},
+    CliOptions,
+};
+
 #[derive(Clone, PartialEq, Parser, Debug, ValueEnum)]
 pub enum RelayActions {
     Add,
     Delete,
     List,
+    Info,
 }
 
 pub struct RelayCommandsHandler {
     pub client: NostrssGrpcClient<Channel>,
 }
 
+#[derive(Tabled)]
+pub struct RelayDetailsTemplate {
+    pub key: String,
+    pub value: String,
+}
+
+#[derive(Tabled)]
+pub struct ListRelayTemplate {
+    pub name: String,
+    pub target: String,
+    pub active: bool,
+    pub status: String,
+}
+
+impl From<RelayItem> for ListRelayTemplate {
+    fn from(data: RelayItem) -> Self {
+        Self {
+            name: data.name,
+            target: data.target,
+            active: data.active,
+            status: data.status.unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(Tabled)]
+pub struct FullRelayTemplate {
+    pub name: String,
+    pub target: String,
+    pub active: bool,
+    pub proxy: String,
+    pub pow_level: u64,
+    pub status: String,
+}
+
+impl FullRelayTemplate {
+    // Builds a table row from each property of relay struct
+    pub fn properties_to_vec(&self) -> Vec<RelayDetailsTemplate> {
+        let active = &self.active.to_string();
+        let pow_level = &self.pow_level.to_string();
+
+        let properties: Vec<(String, &String)> = [
+            ("name".to_string(), &self.name),
+            ("target".to_string(), &self.target),
+            ("active".to_string(), active),
+            ("proxy".to_string(), &self.proxy),
+            ("pow_level".to_string(), pow_level),
+            ("status".to_string(), &self.status),
+        ]
+        .to_vec();
+
+        properties
+            .into_iter()
+            .map(|p| RelayDetailsTemplate {
+                key: p.0,
+                value: p.1.to_string(),
+            })
+            .collect()
+    }
+}
+
+impl From<RelayItem> for FullRelayTemplate {
+    fn from(data: RelayItem) -> Self {
+        Self {
+            name: data.name,
+            target: data.target,
+            active: data.active,
+            proxy: data.proxy.unwrap_or_default(),
+            pow_level: data.pow_level,
+            status: data.status.unwrap_or_default(),
+        }
+    }
+}
+
 #[async_trait]
 impl CommandsHandler for RelayCommandsHandler {}
 
 impl RelayCommandsHandler {
-    pub async fn handle(&mut self, action: RelayActions) {
+    pub async fn handle(&mut self, action: RelayActions, opts: CliOptions) {
         match action {
-            RelayActions::Add => self.add(),
-            RelayActions::Delete => self.delete(),
-            RelayActions::List => self.list(),
+            RelayActions::Add => self.add(opts).await,
+            RelayActions::Delete => self.delete(opts).await,
+            RelayActions::List => self.list().await,
+            RelayActions::Info => self.info().await,
         }
     }
-    fn list(&self) {
-        println!("List relays");
+
+    async fn list(&mut self) {
+        let request = tonic::Request::new(RelaysListRequest {});
+        let response = self.client.relays_list(request).await;
+        match response {
+            Ok(response) => {
+                let raws: Vec<ListRelayTemplate> = response
+                    .into_inner()
+                    .relays
+                    .into_iter()
+                    .map(ListRelayTemplate::from)
+                    .collect();
+
+                let table = Table::new(raws);
+                println!("=== Relays list ===");
+                println!("{}", table);
+            }
+            Err(e) => {
+                println!("Error {}: {}", e.code(), e.message());
+            }
+        }
     }
 
-    fn add(&self) {
-        println!("add relay");
+    async fn add(&mut self, opts: CliOptions) {
+        println!("=== Add a relay ===");
+        let name = self.get_input("Name: ", Some(InputValidators::required_input_validator));
+        let target = self.get_input("Url: ", Some(InputValidators::url_validator));
+        let active = self.get_input("(optional) Active [Y/n]: ", None);
+        let active = !matches!(active.trim().to_lowercase().as_str(), "n" | "no");
+        let proxy: Option<String> = InputFormatter::string_nullifier(
+            self.get_input("(optional) Proxy address (ip:port): ", None),
+        );
+        let pow_level: String = self.get_input("(optional) Publishing PoW level: ", None);
+        let pow_level = pow_level.trim().parse().unwrap_or(0);
+
+        let request = tonic::Request::new(AddRelayRequest {
+            relay: RelayItem {
+                name,
+                target,
+                active,
+                proxy,
+                pow_level,
+                status: None,
+            },
+            save: Some(opts.save),
+        });
+
+        let response = self.client.add_relay(request).await;
+
+        match response {
+            Ok(_) => {
+                println!("Relay successfully added");
+            }
+            Err(e) => {
+                println!("Error: {}: {}", e.code(), e.message());
+            }
+        }
+    }
+
+    async fn delete(&mut self, opts: CliOptions) {
+        let name = self.get_input("Name: ", Some(InputValidators::required_input_validator));
+        let request = tonic::Request::new(DeleteRelayRequest {
+            name,
+            save: Some(opts.save),
+        });
+        let response = self.client.delete_relay(request).await;
+
+        match response {
+            Ok(_) => {
+                println!("Relay successfully deleted");
+            }
+            Err(e) => {
+                println!(
+                    "An error happened with code {} : {} ",
+                    e.code(),
+                    e.message()
+                );
+            }
+        }
     }
 
-    fn delete(&mut self) {
-        println!("add relay");
+    async fn info(&mut self) {
+        let name = self.get_input("Name: ", None);
+
+        let request = tonic::Request::new(RelayInfoRequest { name });
+        let response = self.client.relay_info(request).await;
+
+        match response {
+            Ok(response) => {
+                let relay = FullRelayTemplate::from(response.into_inner().relay);
+                let table = Table::new(relay.properties_to_vec());
+                println!("{}", table);
+            }
+            Err(e) => {
+                println!(
+                    "An error happened with code {} : {} ",
+                    e.code(),
+                    e.message()
+                );
+            }
+        }
     }
 }
@@ -30,18 +30,22 @@ impl CliHandler {
                     }
                 }
             }
-            Subcommands::Feed { action } => {
+            Subcommands::Feed {
+                action,
+                id,
+                entries,
+            } => {
                 let mut feed_handler = FeedCommandsHandler {
                     client: self.client.clone(),
                 };
-                feed_handler.handle(action, opts).await;
+                let opts = CliOptions { entries, ..opts };
+                feed_handler.handle(action, id, opts).await;
             }
             Subcommands::Relay { action } => {
                 let mut relay_handler = RelayCommandsHandler {
                     client: self.client.clone(),
                 };
-                println!("Relay commands");
-                relay_handler.handle(action).await;
+                relay_handler.handle(action, opts).await;
             }
             Subcommands::Profile { action } => {
                 let mut profile_handler = ProfileCommandsHandler {
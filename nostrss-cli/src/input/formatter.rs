@@ -5,6 +5,14 @@ impl InputFormatter {
         value.split(',').map(|e| e.trim().to_string()).collect()
     }
 
+    // Same as `input_to_vec` but an empty input gives an empty vec
+    pub fn optional_input_to_vec(value: String) -> Vec<String> {
+        match value.trim().is_empty() {
+            true => Vec::new(),
+            false => Self::input_to_vec(value),
+        }
+    }
+
     pub fn string_nullifier(value: String) -> Option<String> {
         match !value.is_empty() {
             true => Some(value.trim().to_string()),
@@ -25,4 +33,13 @@ mod tests {
         assert_eq!(result.len(), 3);
         assert_eq!(result[0], "a".to_string());
     }
+
+    #[test]
+    fn optional_input_to_vec_test() {
+        let result = InputFormatter::optional_input_to_vec("".to_string());
+        assert_eq!(result.len(), 0);
+
+        let result = InputFormatter::optional_input_to_vec("a, b".to_string());
+        assert_eq!(result, ["a".to_string(), "b".to_string()].to_vec());
+    }
 }
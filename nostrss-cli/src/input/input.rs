@@ -22,6 +22,16 @@ impl InputValidators {
         cron::Schedule::from_str(&value).is_ok()
     }
 
+    // Validates an url, if provided
+    pub fn optional_url_validator(value: String) -> bool {
+        value.is_empty() || Self::url_validator(value)
+    }
+
+    // Validates a cron pattern, if provided
+    pub fn optional_cron_pattern_validator(value: String) -> bool {
+        value.is_empty() || Self::cron_pattern_validator(value)
+    }
+
     pub fn key_validator(value: String) -> bool {
         let decoded = bech32::decode(value.trim());
 
@@ -69,12 +79,12 @@ mod tests {
         let value = "abc".to_string();
         let result = InputValidators::required_input_validator(value);
 
-        assert_eq!(result, true);
+        assert!(result);
 
         let value = "".to_string();
         let result = InputValidators::required_input_validator(value);
 
-        assert_eq!(result, false);
+        assert!(!result);
     }
 
     #[test]
@@ -82,12 +92,12 @@ mod tests {
         let value = "https://www.domain.org".to_string();
         let result = InputValidators::url_validator(value);
 
-        assert_eq!(result, true);
+        assert!(result);
 
         let value = "invalid_url".to_string();
         let result = InputValidators::url_validator(value);
 
-        assert_eq!(result, false);
+        assert!(!result);
     }
 
     #[test]
@@ -95,17 +105,31 @@ mod tests {
         let value = "1/10 * * * * *".to_string();
         let result = InputValidators::cron_pattern_validator(value);
 
-        assert_eq!(result, true);
+        assert!(result);
 
         let value = "1/10 * * *".to_string();
         let result = InputValidators::cron_pattern_validator(value);
 
-        assert_eq!(result, false);
+        assert!(!result);
 
         let value = "1/10 * * * * * * * *".to_string();
         let result = InputValidators::cron_pattern_validator(value);
 
-        assert_eq!(result, false);
+        assert!(!result);
+    }
+
+    #[test]
+    fn optional_validators_test() {
+        assert!(InputValidators::optional_url_validator("".to_string()));
+        assert!(!InputValidators::optional_url_validator(
+            "invalid_url".to_string()
+        ));
+        assert!(InputValidators::optional_cron_pattern_validator(
+            "".to_string()
+        ));
+        assert!(!InputValidators::optional_cron_pattern_validator(
+            "1/10 * * *".to_string()
+        ));
     }
 
     #[test]
@@ -114,30 +138,30 @@ mod tests {
 
         let result = InputValidators::key_validator(value);
 
-        assert_eq!(result, true);
+        assert!(result);
 
         let value = "6789abcdef0123456789abcdef0123456789abcdef0123456789abcdef".to_string();
 
         let result = InputValidators::key_validator(value);
 
-        assert_eq!(result, false);
+        assert!(!result);
 
         let value = "6789abcdef0123456789abcdef0123456789abcdef0123456789abkdef012345".to_string();
 
         let result = InputValidators::key_validator(value);
 
-        assert_eq!(result, false);
+        assert!(!result);
 
         let value = "nsec14uuscmj9ac0f3lqfq33cuq6mu8q7sscvpyyhsjn5r8q9w5pdafgq0qrj8a".to_string();
 
         let result = InputValidators::key_validator(value);
 
-        assert_eq!(result, true);
+        assert!(result);
 
         let value = "nsec14uuscmj9ac0f3lqfq33cuq6mu8q7sscvpyyhsjn5r8q9w5pdafgq0qrj8d".to_string();
 
         let result = InputValidators::key_validator(value);
 
-        assert_eq!(result, false);
+        assert!(!result);
     }
 }
@@ -30,6 +30,7 @@ struct Cli {
 #[derive(Debug, Default)]
 pub struct CliOptions {
     save: bool,
+    entries: Option<u64>,
 }
 
 #[derive(Debug, PartialEq, Parser)]
@@ -51,7 +52,14 @@ pub enum Subcommands {
     },
 
     #[clap(name = "feed", about = "Provides commands for feed mcanagement")]
-    Feed { action: FeedActions },
+    Feed {
+        action: FeedActions,
+        /// The id of the feed targeted by the action. Prompted if not provided
+        id: Option<String>,
+        #[arg(long)]
+        /// The number of entries to preview. Defaults to 1
+        entries: Option<u64>,
+    },
     /// Provides commands for Profile management
     Profile { action: ProfileActions },
     /// Checks health of core
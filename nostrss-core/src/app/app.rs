@@ -1,21 +1,58 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, sync::Arc, time::Duration};
 
 use crate::{
-    nostr::service::NostrService,
+    nostr::{relay::Relay, service::NostrService},
     profiles::{config::Profile, profiles::ProfileHandler},
     rss::{
         config::{Feed, RssConfig},
+        parser::RssParser,
         rss::RssInstance,
     },
+    scheduler::{
+        outbox::schedule_outbox,
+        scheduler::{publish_feed, schedule},
+    },
+    store::{
+        report::TickReport,
+        store::{flush_store, load_store, SharedSeenStore},
+    },
 };
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use log::info;
-use nostr_sdk::{prelude::RelayOptions, prelude::ToBech32, Client, Keys};
+use nostr_sdk::{prelude::RelayOptions, prelude::ToBech32, Client, Keys, RelayStatus};
 
-use tokio::sync::Mutex;
-use tokio_cron_scheduler::JobScheduler;
+use tokio::{io::AsyncReadExt, sync::Mutex};
+use tokio_cron_scheduler::Job;
 use uuid::Uuid;
 
+// The shared state the job of a feed is built from.
+// Building a job fetches the feed, so the context is taken from the app
+// for the job to be built without keeping the app locked.
+#[derive(Clone)]
+pub struct FeedJobContext {
+    store: SharedSeenStore,
+    client: Arc<Mutex<Client>>,
+    profiles: Arc<Mutex<HashMap<String, Profile>>>,
+    config: Arc<Mutex<AppConfig>>,
+    http_client: reqwest::Client,
+}
+
+impl FeedJobContext {
+    // Builds the job of a feed. Returns an error if the job can not be built.
+    pub async fn build(&self, feed: &Feed) -> Result<Job, String> {
+        schedule(
+            feed.schedule.as_str(),
+            feed.clone(),
+            Arc::clone(&self.store),
+            Arc::clone(&self.client),
+            Arc::clone(&self.profiles),
+            Arc::clone(&self.config),
+            self.http_client.clone(),
+        )
+        .await
+    }
+}
+
 #[derive(Parser, Debug, Clone, Default)]
 #[command(author, version, about, long_about = None)]
 pub struct AppConfig {
@@ -41,30 +78,50 @@ pub struct AppConfig {
 
     #[arg(long)]
     pub update: Option<bool>,
+
+    /// path to the file in which the seen entries of feeds are persisted.
+    /// Entries are kept in memory only if not provided
+    #[arg(long)]
+    pub store: Option<String>,
+
+    /// One-shot command to run instead of the feeds jobs
+    #[command(subcommand)]
+    pub command: Option<AppCommand>,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum AppCommand {
+    /// Publishes the new entries of a feed document and exits
+    Publish {
+        /// path to the feed document, `-` to read it from the standard input
+        #[arg(long)]
+        feed_file: String,
+
+        /// id of the configured feed whose settings are used to publish the entries.
+        /// Default feed settings are used if not provided
+        #[arg(long)]
+        feed: Option<String>,
+    },
 }
 
 pub struct App {
     pub rss: RssInstance,
-    pub scheduler: Arc<JobScheduler>,
     // pub clients: HashMap<String, NostrInstance>,
     pub feeds_jobs: HashMap<String, Uuid>,
-    pub feeds_map: HashMap<String, Vec<String>>,
+    pub seen_store: SharedSeenStore,
     pub nostr_service: NostrService,
-    pub config: AppConfig,
+    // Shared with the feeds jobs
+    pub config: Arc<Mutex<AppConfig>>,
     pub profile_handler: ProfileHandler,
 }
 
 impl App {
-    pub async fn new(config: AppConfig) -> Self {
-        let profile_handler = ProfileHandler::new(&config.profiles, &config.relays);
+    // Builds the application from its config.
+    // Returns an error if the store can not be loaded.
+    pub async fn new(config: AppConfig) -> Result<Self, String> {
+        let seen_store = load_store(&config.store)?;
 
-        let scheduler = match JobScheduler::new().await {
-            Ok(result) => Arc::new(result),
-            Err(_) => {
-                // We shall improve the scheduler creation error in a better way than just a panic
-                panic!("Scheduler init failure. Panicking !");
-            }
-        };
+        let profile_handler = ProfileHandler::new(&config.profiles, &config.relays);
 
         // RSS feed handler
         let rss = RssInstance::new(RssConfig::new(config.clone().feeds)).await;
@@ -73,12 +130,16 @@ impl App {
 
         let default_relays = profile_handler.clone().get_default_relays();
 
+        // Every relay used by a profile is registered in a single client
+        // so profiles publishing to the same relay share its connection.
+        let mut pool_relays: HashMap<String, Relay> = HashMap::new();
+
         for profile_entry in profiles {
             let profile_id = profile_entry.0.clone();
-            let mut profile = profile_entry.1.clone();
+            let profile = profile_entry.1.clone();
 
-            if profile.relays.is_empty() {
-                profile.relays = default_relays.clone();
+            for relay in profile.get_publishing_relays(&default_relays) {
+                pool_relays.entry(relay.target.clone()).or_insert(relay);
             }
 
             let keys = Keys::parse(profile.private_key.as_str()).unwrap();
@@ -97,36 +158,39 @@ impl App {
 
         let client = Client::new(&Keys::generate());
 
-        for relay in default_relays.into_iter() {
+        for relay in pool_relays.into_values() {
             let mut opts = RelayOptions::new();
 
             opts = opts.proxy(relay.proxy);
 
-            _ = &client.add_relay_with_opts(relay.target, opts).await;
-        }
+            _ = &client.add_relay_with_opts(relay.target.clone(), opts).await;
 
-        _ = &client.connect().await;
+            // Inactive relays are registered but never connected
+            match relay.active {
+                true => _ = &client.connect_relay(relay.target).await,
+                false => info!("Relay {} is inactive. Skipping connection.", relay.name),
+            }
+        }
 
         let nostr_service =
             NostrService::new(client, config.relays.clone(), config.profiles.clone()).await;
 
-        Self {
+        Ok(Self {
             rss,
-            scheduler,
             feeds_jobs: HashMap::new(),
-            feeds_map: HashMap::new(),
+            seen_store,
             nostr_service,
-            config,
+            config: Arc::new(Mutex::new(config)),
             profile_handler: ProfileHandler(HashMap::new()),
-        }
+        })
     }
 
     pub async fn get_profiles(&self) -> Arc<Mutex<HashMap<String, Profile>>> {
-        Arc::new(Mutex::new(self.nostr_service.profiles.clone()))
+        self.nostr_service.get_profiles().await
     }
 
     pub async fn get_config(&self) -> Arc<Mutex<AppConfig>> {
-        Arc::new(Mutex::new(self.config.clone()))
+        Arc::clone(&self.config)
     }
 
     pub async fn update_profile_config(&self) -> bool {
@@ -143,27 +207,169 @@ impl App {
             })
             .collect();
 
-        if self.config.profiles.is_none() {
+        let Some(path) = self.config.lock().await.profiles.clone() else {
             return false;
-        }
+        };
 
         let result = self
             .profile_handler
             .clone()
-            .save_profiles(self.config.profiles.clone().unwrap().as_str(), profiles);
+            .save_profiles(path.as_str(), profiles);
 
         result
     }
 
-    pub async fn update_feeds_config(&self, feeds: &Vec<Feed>) -> bool {
-        if self.config.feeds.is_none() {
-            return false;
+    // Builds the job of a feed and loads it in the scheduler.
+    // The job reference is kept in the jobs map of the rss instance.
+    // Returns an error if the job can not be built.
+    pub async fn schedule_feed(&mut self, feed: &Feed) -> Result<Uuid, String> {
+        let job = self.get_feed_job_context().await.build(feed).await?;
+
+        Ok(self.add_feed_job(feed, job).await)
+    }
+
+    // Gets the shared state the jobs of the feeds are built from
+    pub async fn get_feed_job_context(&self) -> FeedJobContext {
+        FeedJobContext {
+            store: Arc::clone(&self.seen_store),
+            client: self.nostr_service.get_client().await,
+            profiles: self.get_profiles().await,
+            config: self.get_config().await,
+            http_client: self.rss.http_client.clone(),
+        }
+    }
+
+    // Loads the job built for a feed in the scheduler
+    pub async fn add_feed_job(&mut self, feed: &Feed, job: Job) -> Uuid {
+        let uuid = job.guid();
+        info!("Job id for feed {:?}: {:?}", feed.name, uuid);
+
+        _ = self.rss.feeds_jobs.insert(feed.id.clone(), uuid);
+        _ = self.rss.scheduler.add(job).await;
+
+        uuid
+    }
+
+    // Builds the job retrying the events of the outbox and loads it in the scheduler
+    pub async fn schedule_outbox(&mut self) -> Option<Uuid> {
+        let job = schedule_outbox(
+            Arc::clone(&self.seen_store),
+            self.nostr_service.client.lock().await.clone(),
+        )?;
+
+        let uuid = job.guid();
+        _ = self.rss.scheduler.add(job).await;
+
+        Some(uuid)
+    }
+
+    // Publishes the new entries of a feed document, read from a file or
+    // from the standard input if the path is `-`.
+    pub async fn publish_feed_file(
+        &self,
+        path: &str,
+        feed_id: Option<String>,
+    ) -> Result<TickReport, String> {
+        let mut content = Vec::new();
+        match path {
+            "-" => tokio::io::stdin()
+                .read_to_end(&mut content)
+                .await
+                .map(|_| ())
+                .map_err(|e| format!("Error while reading feed from stdin : {}", e))?,
+            path => {
+                content = tokio::fs::read(path)
+                    .await
+                    .map_err(|e| format!("Error while reading feed file {} : {}", path, e))?
+            }
+        };
+
+        let remote = RssParser::parse(feed_id.as_deref().unwrap_or(&Feed::default().id), &content)
+            .map_err(|e| e.message)?;
+
+        let feed = match feed_id {
+            Some(id) => self
+                .rss
+                .feeds
+                .iter()
+                .find(|feed| feed.id == id)
+                .cloned()
+                .ok_or(format!("No feed found with id {}", id))?,
+            None => Feed {
+                name: remote
+                    .title
+                    .clone()
+                    .map(|title| title.content)
+                    .unwrap_or(Feed::default().name),
+                ..Default::default()
+            },
+        };
+
+        self.wait_for_relays(Duration::from_secs(10)).await;
+
+        let report = publish_feed(
+            &feed,
+            remote,
+            None,
+            &self.seen_store,
+            &self.nostr_service.get_client().await,
+            &self.get_profiles().await,
+            &self.get_config().await,
+        )
+        .await;
+        flush_store(&self.seen_store).await;
+
+        Ok(report)
+    }
+
+    // Waits for the relays being connected in background, up to the provided timeout
+    async fn wait_for_relays(&self, timeout: Duration) {
+        let started = std::time::Instant::now();
+
+        while started.elapsed() < timeout {
+            let mut connecting = false;
+            let client = self.nostr_service.client.lock().await.clone();
+            for relay in client.relays().await.values() {
+                if let RelayStatus::Pending | RelayStatus::Connecting = relay.status().await {
+                    connecting = true;
+                }
+            }
+
+            if !connecting {
+                break;
+            }
+
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    }
+
+    // Removes the job of a feed from the scheduler.
+    // Returns false if no job is associated to the feed.
+    pub async fn unschedule_feed(&mut self, feed_id: &str) -> bool {
+        match self.rss.feeds_jobs.remove(feed_id) {
+            Some(uuid) => {
+                _ = self.rss.scheduler.remove(&uuid).await;
+                true
+            }
+            None => false,
         }
-        let rss = self
-            .rss
-            .config
+    }
+
+    pub async fn update_relays_config(&self) -> bool {
+        let relays = self.nostr_service.get_relays();
+
+        let path = self.config.lock().await.relays.clone();
+
+        self.profile_handler
             .clone()
-            .save_feeds(&self.config.feeds.clone().unwrap(), feeds);
+            .save_relays(path.as_str(), relays.iter().collect())
+    }
+
+    pub async fn update_feeds_config(&self, feeds: &Vec<Feed>) -> bool {
+        let Some(path) = self.config.lock().await.feeds.clone() else {
+            return false;
+        };
+        let rss = self.rss.config.clone().save_feeds(&path, feeds);
         rss
     }
 }
@@ -232,16 +438,18 @@ mod tests {
 
         _ = &profiles.insert("test3".to_string(), profile_3);
 
-        app.nostr_service.profiles = profiles;
+        app.nostr_service.profiles = Arc::new(Mutex::new(profiles));
 
         // Point app configuration to profiles json test file
-        app.config.profiles = Some("src/fixtures/tests/profiles.test.json".to_string());
+        app.config.lock().await.profiles =
+            Some("src/fixtures/tests/profiles.test.json".to_string());
 
         let r = app.update_profile_config().await;
         assert_eq!(true, r);
 
         // Point app configuration to profiles yaml test file
-        app.config.profiles = Some("src/fixtures/tests/profiles.test.yaml".to_string());
+        app.config.lock().await.profiles =
+            Some("src/fixtures/tests/profiles.test.yaml".to_string());
 
         let r = app.update_profile_config().await;
         assert_eq!(true, r);
@@ -262,16 +470,74 @@ mod tests {
         ]
         .to_vec();
 
-        app.config.feeds = Some("src/fixtures/tests/rss.test.yaml".to_string());
+        app.config.lock().await.feeds = Some("src/fixtures/tests/rss.test.yaml".to_string());
 
         let r = app.update_feeds_config(&feeds).await;
         assert_eq!(true, r);
 
-        app.config.feeds = Some("src/fixtures/tests/rss.test.json".to_string());
+        app.config.lock().await.feeds = Some("src/fixtures/tests/rss.test.json".to_string());
 
         let r = app.update_feeds_config(&feeds).await;
         assert_eq!(true, r);
 
         clean_test_files()
     }
+
+    #[tokio::test]
+    async fn schedule_invalid_feed_test() {
+        let mut app = test_utils::mock_app().await;
+
+        let feed = Feed {
+            id: "invalid_schedule".to_string(),
+            schedule: "every minute".to_string(),
+            ..Default::default()
+        };
+
+        assert!(app.schedule_feed(&feed).await.is_err());
+        assert!(!app.rss.feeds_jobs.contains_key("invalid_schedule"));
+    }
+
+    #[tokio::test]
+    async fn relay_added_after_scheduling_test() {
+        let mut app = test_utils::mock_app().await;
+        app.config.lock().await.dry_run = true;
+
+        // The jobs scheduled by the mock share the state of the app
+        let profiles = app.get_profiles().await;
+        assert!(Arc::ptr_eq(&profiles, &app.nostr_service.profiles));
+
+        let relay = Relay {
+            name: "added".to_string(),
+            target: "ws://127.0.0.1:1".to_string(),
+            active: true,
+            proxy: None,
+            pow_level: 0,
+        };
+        assert!(app.nostr_service.add_relay(relay).await.is_ok());
+
+        let content = fs::read("src/fixtures/feed.xml").unwrap();
+        let remote = RssParser::parse("added_relay", &content).unwrap();
+        let feed = Feed {
+            id: "added_relay".to_string(),
+            ..Default::default()
+        };
+
+        let report = publish_feed(
+            &feed,
+            remote,
+            None,
+            &app.seen_store,
+            &app.nostr_service.get_client().await,
+            &profiles,
+            &app.get_config().await,
+        )
+        .await;
+        assert!(!report.published.is_empty());
+
+        let publications = app.seen_store.lock().await.get_publications("added_relay");
+        assert!(!publications.is_empty());
+        assert!(publications
+            .iter()
+            .all(|publication| publication.targets == ["ws://127.0.0.1:1".to_string()]));
+    }
 }
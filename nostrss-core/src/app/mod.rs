@@ -3,9 +3,9 @@ pub mod app;
 #[cfg(test)]
 pub mod test_utils {
     use std::{collections::HashMap, sync::Arc};
+    use tokio::sync::Mutex;
 
     use dotenv::from_filename;
-    use tokio::sync::Mutex;
 
     use crate::{
         app::app::{App, AppConfig},
@@ -13,6 +13,7 @@ pub mod test_utils {
         profiles::{config::Profile, profiles::ProfileHandler},
         rss::{config::RssConfig, rss::RssInstance},
         scheduler::scheduler::schedule,
+        store::store::load_store,
     };
 
     pub async fn mock_app() -> App {
@@ -37,19 +38,17 @@ pub mod test_utils {
         profiles.insert(test_profile.id.clone(), test_profile);
 
         let nostr_service = NostrService {
-            profiles,
+            profiles: Arc::new(Mutex::new(profiles)),
             ..Default::default()
         };
-        let scheduler = tokio_cron_scheduler::JobScheduler::new().await.unwrap();
         let mut app = App {
             rss,
-            scheduler: Arc::new(scheduler),
             feeds_jobs: HashMap::new(),
-            feeds_map: HashMap::new(),
+            seen_store: load_store(&None).unwrap(),
             nostr_service,
-            config: AppConfig {
+            config: Arc::new(Mutex::new(AppConfig {
                 ..Default::default()
-            },
+            })),
             profile_handler: ProfileHandler(HashMap::new()),
         };
 
@@ -57,12 +56,14 @@ pub mod test_utils {
             let job = schedule(
                 feed.clone().schedule.as_str(),
                 feed.clone(),
-                Arc::new(Mutex::new(app.feeds_map.clone())),
+                Arc::clone(&app.seen_store),
                 app.nostr_service.get_client().await,
                 app.get_profiles().await,
                 app.get_config().await,
+                app.rss.http_client.clone(),
             )
-            .await;
+            .await
+            .unwrap();
 
             _ = &app.rss.feeds_jobs.insert(feed.id, job.guid());
         }
@@ -1,12 +1,167 @@
+use feed_rs::model::Entry;
+use nostr_sdk::JsonUtil;
 use nostrss_grpc::grpc::{
-    self, AddFeedRequest, AddFeedResponse, DeleteFeedRequest, DeleteFeedResponse, FeedInfoRequest,
-    FeedInfoResponse, FeedItem, FeedsListRequest, FeedsListResponse,
+    self, AddFeedRequest, AddFeedResponse, CatchUpItem, DeleteFeedRequest, DeleteFeedResponse,
+    DiscoverFeedsRequest, DiscoverFeedsResponse, DiscoveredFeedItem, EventPreviewItem,
+    FailedEntryItem, FeedErrorItem, FeedErrorsRequest, FeedErrorsResponse, FeedHealthItem,
+    FeedInfoRequest, FeedInfoResponse, FeedItem, FeedProblemItem, FeedStatusRequest,
+    FeedStatusResponse, FeedsListRequest, FeedsListResponse, PreviewFeedRequest,
+    PreviewFeedResponse, RetractEntryRequest, RetractEntryResponse, StartJobRequest,
+    StartJobResponse, StopJobRequest, StopJobResponse, TickReportItem, UpdateFeedRequest,
+    UpdateFeedResponse, ValidateFeedRequest, ValidateFeedResponse,
 };
-use std::sync::Arc;
+use serde::de::DeserializeOwned;
+use std::{str::FromStr, sync::Arc};
 use tokio::sync::{Mutex, MutexGuard};
 use tonic::{Code, Request, Response, Status};
 
-use crate::{app::app::App, rss::config::Feed, scheduler::scheduler::schedule};
+use crate::{
+    app::app::App,
+    rss::{
+        config::{CatchUp, Feed},
+        discovery::{discover_feeds, DiscoveredFeed},
+        parser::RssParser,
+        validation::{self, check_schedule, FeedProblem},
+    },
+    scheduler::scheduler::{now_timestamp, EventPreview, PublishContext, RssNostrJob},
+    store::{
+        cache::HttpCache, errors::FeedError, health::FeedHealth, report::TickReport,
+        store::flush_store,
+    },
+};
+
+// The fields of a feed that can be removed through an update request
+const CLEARABLE_FIELDS: [&str; 8] = [
+    "profiles",
+    "tags",
+    "template",
+    "cache_size",
+    "proxy",
+    "shadow_file",
+    "auth",
+    "headers",
+];
+
+impl TryFrom<CatchUpItem> for CatchUp {
+    type Error = &'static str;
+
+    fn try_from(value: CatchUpItem) -> Result<Self, Self::Error> {
+        match value.policy.trim() {
+            "none" => Ok(CatchUp::None),
+            "since_last_run" => Ok(CatchUp::SinceLastRun),
+            "max_entries" => match value.count.map(usize::try_from) {
+                Some(Ok(count)) => Ok(CatchUp::MaxEntries { count }),
+                _ => Err("Invalid catch up entries count"),
+            },
+            "max_age" => match value.seconds {
+                Some(seconds) => Ok(CatchUp::MaxAge { seconds }),
+                None => Err("Invalid catch up max age"),
+            },
+            _ => Err("Invalid catch up policy"),
+        }
+    }
+}
+
+impl From<CatchUp> for CatchUpItem {
+    fn from(value: CatchUp) -> Self {
+        let (policy, count, seconds) = match value {
+            CatchUp::None => ("none", None, None),
+            CatchUp::SinceLastRun => ("since_last_run", None, None),
+            CatchUp::MaxEntries { count } => ("max_entries", Some(count as u64), None),
+            CatchUp::MaxAge { seconds } => ("max_age", None, Some(seconds)),
+        };
+
+        Self {
+            policy: policy.to_string(),
+            count,
+            seconds,
+        }
+    }
+}
+
+impl From<DiscoveredFeed> for DiscoveredFeedItem {
+    fn from(value: DiscoveredFeed) -> Self {
+        Self {
+            url: value.url,
+            title: value.title,
+            content_type: value.content_type,
+        }
+    }
+}
+
+impl From<EventPreview> for EventPreviewItem {
+    fn from(value: EventPreview) -> Self {
+        let (event, error) = match value.event {
+            Ok(event) => (Some(event.as_json()), None),
+            Err(e) => (None, Some(e.to_string())),
+        };
+
+        Self {
+            entry_id: value.entry_id,
+            profile_id: value.profile_id,
+            pow_level: value.pow_level as u64,
+            event,
+            error,
+        }
+    }
+}
+
+impl From<FeedProblem> for FeedProblemItem {
+    fn from(value: FeedProblem) -> Self {
+        Self {
+            field: value.field,
+            message: value.message,
+        }
+    }
+}
+
+impl From<FeedError> for FeedErrorItem {
+    fn from(value: FeedError) -> Self {
+        Self {
+            timestamp: value.timestamp,
+            kind: value.kind,
+            profile_id: value.profile_id,
+            entry_id: value.entry_id,
+            message: value.message,
+        }
+    }
+}
+
+impl From<TickReport> for TickReportItem {
+    fn from(value: TickReport) -> Self {
+        Self {
+            timestamp: value.timestamp,
+            published: value.published,
+            updated: value.updated,
+            deleted: value.deleted,
+            skipped: value.skipped,
+            failed: value
+                .failed
+                .into_iter()
+                .map(|failed| FailedEntryItem {
+                    entry_id: failed.entry_id,
+                    reason: failed.reason,
+                })
+                .collect(),
+            error: value.error,
+        }
+    }
+}
+
+impl From<FeedHealth> for FeedHealthItem {
+    fn from(value: FeedHealth) -> Self {
+        Self {
+            last_fetch: value.last_fetch,
+            last_success: value.last_success,
+            consecutive_failures: value.consecutive_failures,
+            last_error: value.last_error,
+            http_status: value.http_status.map(u32::from),
+            entries_seen: value.entries_seen,
+            entries_published: value.entries_published,
+            next_run: None,
+        }
+    }
+}
 
 pub struct FeedRequestHandler {}
 
@@ -38,50 +193,724 @@ impl FeedRequestHandler {
         }
     }
 
-    pub async fn add_feed(
+    // Interface to add a feed on instance.
+    // The app is not locked while the feed is checked and its job built
+    // as both fetch the feed.
+    pub async fn add_feed(
+        app: Arc<Mutex<App>>,
+        request: Request<AddFeedRequest>,
+    ) -> Result<Response<AddFeedResponse>, Status> {
+        let data = request.into_inner();
+        let save = data.save();
+
+        // Invalid feeds are rejected before being scheduled
+        let feed = match Self::check_feed_item(app.lock().await, data.feed).await {
+            Ok(feed) => feed,
+            Err(problems) => return Err(Self::invalid_feed_status(&problems)),
+        };
+
+        let mut app_lock = app.lock().await;
+
+        // Another feed with the same id may have been added while the feed was checked
+        if app_lock.rss.feeds.iter().any(|f| f.id == feed.id) {
+            return Err(Self::invalid_feed_status(&[FeedProblem::new(
+                "id",
+                "A feed with this id already exists",
+            )]));
+        }
+        app_lock.rss.feeds.push(feed.clone());
+        drop(app_lock);
+
+        if !feed.paused {
+            if let Err(e) = Self::schedule_feed(&app, &feed).await {
+                // The feed is not kept without its job
+                app.lock().await.rss.feeds.retain(|f| f.id != feed.id);
+                return Err(Status::new(Code::FailedPrecondition, e));
+            }
+        }
+
+        if save == true {
+            let app_lock = app.lock().await;
+            _ = &app_lock.update_feeds_config(&app_lock.rss.feeds).await;
+        }
+
+        Ok(Response::new(AddFeedResponse {}))
+    }
+
+    // Interface to patch a feed on instance.
+    // The app is not locked while the patched feed is checked and its job rebuilt
+    // as both fetch the feed.
+    pub async fn update_feed(
+        app: Arc<Mutex<App>>,
+        request: Request<UpdateFeedRequest>,
+    ) -> Result<Response<UpdateFeedResponse>, Status> {
+        let data = request.into_inner();
+        let save = data.save();
+        let feed_id = data.id.trim().to_string();
+
+        let app_lock = app.lock().await;
+        let current = match app_lock.rss.feeds.iter().find(|f| f.id == feed_id) {
+            Some(feed) => feed.clone(),
+            None => {
+                return Err(Status::new(
+                    Code::NotFound,
+                    "No feed found with provided id",
+                ));
+            }
+        };
+
+        let feed = match Self::patch_feed(current.clone(), data) {
+            Ok(feed) => feed,
+            Err(e) => return Err(Status::new(Code::InvalidArgument, e)),
+        };
+
+        // Invalid changes are rejected before the feed is replaced
+        let problems = Self::validate(app_lock, &feed).await;
+        if !problems.is_empty() {
+            return Err(Self::invalid_feed_status(&problems));
+        }
+
+        let mut app_lock = app.lock().await;
+
+        // The feed may have been deleted while it was checked
+        let idx = match app_lock.rss.feeds.iter().position(|f| f.id == feed_id) {
+            Some(idx) => idx,
+            None => {
+                return Err(Status::new(
+                    Code::NotFound,
+                    "No feed found with provided id",
+                ));
+            }
+        };
+        app_lock.rss.feeds[idx] = feed.clone();
+
+        // The validators and delays of the previous url do not apply to the new one
+        if feed.url != current.url {
+            app_lock
+                .seen_store
+                .lock()
+                .await
+                .set_http_cache(&feed.id, HttpCache::default());
+            flush_store(&app_lock.seen_store).await;
+        }
+
+        // The job owns a copy of the feed so it has to be rebuilt for
+        // the changes to be applied. Seen entries are kept in store
+        // as it is indexed by feed id.
+        let reschedule = app_lock.unschedule_feed(&feed.id).await;
+        drop(app_lock);
+
+        if reschedule {
+            if let Err(e) = Self::schedule_feed(&app, &feed).await {
+                return Err(Status::new(Code::FailedPrecondition, e));
+            }
+        }
+
+        if save {
+            let app_lock = app.lock().await;
+            _ = &app_lock.update_feeds_config(&app_lock.rss.feeds).await;
+        }
+
+        Ok(Response::new(UpdateFeedResponse {
+            feed: FeedItem::from(feed),
+        }))
+    }
+
+    // Applies the provided values of an update request on a feed
+    fn patch_feed(mut feed: Feed, data: UpdateFeedRequest) -> Result<Feed, &'static str> {
+        if data
+            .clear
+            .iter()
+            .any(|field| !CLEARABLE_FIELDS.contains(&field.as_str()))
+        {
+            return Err("Unknown feed field to clear");
+        }
+
+        let cleared = |field: &str| data.clear.iter().any(|f| f == field);
+
+        if (cleared("profiles") && !data.profiles.is_empty())
+            || (cleared("tags") && !data.tags.is_empty())
+            || (cleared("template") && data.template.is_some())
+            || (cleared("cache_size") && data.cache_size.is_some())
+            || (cleared("proxy") && data.proxy.is_some())
+            || (cleared("shadow_file") && data.shadow_file.is_some())
+        {
+            return Err("A cleared feed field can not be provided");
+        }
+
+        if cleared("profiles") {
+            feed.profiles = None;
+        }
+
+        if cleared("tags") {
+            feed.tags = None;
+        }
+
+        if cleared("template") {
+            feed.template = None;
+        }
+
+        if cleared("cache_size") {
+            feed.cache_size = Feed::default_cache_size();
+        }
+
+        if cleared("proxy") {
+            feed.proxy = None;
+        }
+
+        if cleared("shadow_file") {
+            feed.shadow_file = None;
+        }
+
+        if cleared("auth") {
+            feed.auth = None;
+        }
+
+        if cleared("headers") {
+            feed.headers = None;
+        }
+
+        if let Some(name) = data.name {
+            feed.name = name;
+        }
+
+        if let Some(url) = data.url {
+            feed.url = match nostr_sdk::Url::from_str(url.as_str()) {
+                Ok(url) => url,
+                Err(_) => return Err("Invalid feed url"),
+            };
+        }
+
+        if let Some(schedule) = data.schedule {
+            if check_schedule(&schedule).is_err() {
+                return Err("Invalid feed schedule");
+            }
+            feed.schedule = schedule;
+        }
+
+        if !data.profiles.is_empty() {
+            feed.profiles = Some(data.profiles);
+        }
+
+        if !data.tags.is_empty() {
+            feed.tags = Some(data.tags);
+        }
+
+        if data.template.is_some() {
+            feed.template = data.template;
+        }
+
+        if let Some(cache_size) = data.cache_size {
+            feed.cache_size = Some(cache_size as usize);
+        }
+
+        if let Some(pow_level) = data.pow_level {
+            feed.pow_level = match u8::try_from(pow_level) {
+                Ok(pow_level) => pow_level,
+                Err(_) => return Err("Invalid pow level"),
+            };
+        }
+
+        if data.proxy.is_some() {
+            feed.proxy = Self::parse_proxy(data.proxy)?;
+        }
+
+        if let Some(shadow) = data.shadow {
+            feed.shadow = shadow;
+        }
+
+        if let Some(shadow_file) = data.shadow_file {
+            feed.shadow_file = match shadow_file.trim().is_empty() {
+                true => None,
+                false => Some(shadow_file.trim().to_string()),
+            };
+        }
+
+        if let Some(kind) = data.kind {
+            feed.kind = Self::parse_value(kind, "Invalid feed kind")?;
+        }
+
+        if let Some(on_update) = data.on_update {
+            feed.on_update = Self::parse_value(on_update, "Invalid on update policy")?;
+        }
+
+        if let Some(catch_up) = data.catch_up {
+            feed.catch_up = CatchUp::try_from(catch_up)?;
+        }
+
+        if let Some(watch) = data.watch {
+            feed.watch = watch;
+        }
+
+        if let Some(mirror_deletions) = data.mirror_deletions {
+            feed.mirror_deletions = mirror_deletions;
+        }
+
+        Ok(feed)
+    }
+
+    // Parses a value of the feed config from its name, e.g: `article` for the feed kind
+    fn parse_value<T: DeserializeOwned>(
+        value: String,
+        error: &'static str,
+    ) -> Result<T, &'static str> {
+        serde_json::from_value(serde_json::Value::String(value.trim().to_string()))
+            .map_err(|_| error)
+    }
+
+    // Builds a feed from its definition and checks it before it is scheduled.
+    // Returns the problems found, if any. The app is not locked while the feed is fetched.
+    async fn check_feed_item(
+        app: MutexGuard<'_, App>,
+        item: FeedItem,
+    ) -> Result<Feed, Vec<FeedProblem>> {
+        if nostr_sdk::Url::from_str(item.url.as_str()).is_err() {
+            return Err([FeedProblem::new("url", "Invalid feed url")].to_vec());
+        }
+
+        let proxy = Self::parse_proxy(item.proxy.clone())
+            .map_err(|e| [FeedProblem::new("proxy", e)].to_vec())?;
+
+        let kind = item.kind.clone();
+        let on_update = item.on_update.clone();
+        let catch_up = item.catch_up.clone();
+
+        let mut feed = Feed {
+            proxy,
+            ..Feed::from(item)
+        };
+
+        if let Some(kind) = kind {
+            feed.kind = Self::parse_value(kind, "Invalid feed kind")
+                .map_err(|e| [FeedProblem::new("kind", e)].to_vec())?;
+        }
+
+        if let Some(on_update) = on_update {
+            feed.on_update = Self::parse_value(on_update, "Invalid on update policy")
+                .map_err(|e| [FeedProblem::new("on_update", e)].to_vec())?;
+        }
+
+        if let Some(catch_up) = catch_up {
+            feed.catch_up = CatchUp::try_from(catch_up)
+                .map_err(|e| [FeedProblem::new("catch_up", e)].to_vec())?;
+        }
+
+        let mut problems = Vec::new();
+
+        if app.rss.feeds.iter().any(|f| f.id == feed.id) {
+            problems.push(FeedProblem::new("id", "A feed with this id already exists"));
+        }
+
+        problems.extend(Self::validate(app, &feed).await);
+
+        match problems.is_empty() {
+            true => Ok(feed),
+            false => Err(problems),
+        }
+    }
+
+    // Checks a feed and returns the problems found.
+    // The app is released before the feed is fetched.
+    async fn validate(app: MutexGuard<'_, App>, feed: &Feed) -> Vec<FeedProblem> {
+        let http_client = app.rss.http_client.clone();
+        let profiles = app.nostr_service.profiles.lock().await.clone();
+        drop(app);
+
+        validation::validate_feed(feed, &http_client, &profiles).await
+    }
+
+    // Builds the job of a feed and loads it in the scheduler.
+    // The app is released while the job is built, as its catch-up fetches the feed.
+    // The job is dropped if the feed was removed, paused or scheduled meanwhile.
+    async fn schedule_feed(app: &Arc<Mutex<App>>, feed: &Feed) -> Result<(), String> {
+        let context = app.lock().await.get_feed_job_context().await;
+        let job = context.build(feed).await?;
+
+        let mut app = app.lock().await;
+        let scheduled = app.rss.feeds.iter().any(|f| f.id == feed.id && !f.paused)
+            && !app.rss.feeds_jobs.contains_key(&feed.id);
+
+        if scheduled {
+            app.add_feed_job(feed, job).await;
+        }
+
+        Ok(())
+    }
+
+    // Builds the status of a request rejected for an invalid feed
+    fn invalid_feed_status(problems: &[FeedProblem]) -> Status {
+        let problems: Vec<String> = problems.iter().map(|p| p.to_string()).collect();
+        let message = format!("Invalid feed : {}", problems.join(", "));
+        Status::new(Code::InvalidArgument, message)
+    }
+
+    // Checks the proxy url of a feed. Empty values are no proxy.
+    fn parse_proxy(proxy: Option<String>) -> Result<Option<String>, &'static str> {
+        match proxy {
+            Some(proxy) if !proxy.trim().is_empty() => match RssParser::proxy(proxy.trim()) {
+                Ok(_) => Ok(Some(proxy.trim().to_string())),
+                Err(_) => Err("Invalid feed proxy url"),
+            },
+            _ => Ok(None),
+        }
+    }
+
+    // Interface to delete a feed on instance
+    pub async fn delete_feed(
+        mut app: MutexGuard<'_, App>,
+        request: Request<DeleteFeedRequest>,
+    ) -> Result<Response<DeleteFeedResponse>, Status> {
+        let data = request.into_inner();
+
+        let save = data.save();
+        let feed_id = data.id.trim().to_string();
+
+        let idx = match app.rss.feeds.iter().position(|f| f.id == feed_id) {
+            Some(idx) => idx,
+            None => {
+                return Err(Status::new(
+                    Code::NotFound,
+                    "No feed found with provided id",
+                ));
+            }
+        };
+
+        // Paused feeds have no job associated
+        if !app.rss.feeds[idx].paused && !app.unschedule_feed(&feed_id).await {
+            return Err(Status::new(
+                Code::NotFound,
+                "Job associated to feed not found",
+            ));
+        }
+
+        _ = &app.rss.feeds.remove(idx);
+        app.seen_store.lock().await.remove(&feed_id);
+        flush_store(&app.seen_store).await;
+
+        if save == true {
+            _ = &app.update_feeds_config(&app.rss.feeds).await;
+        }
+
+        Ok(Response::new(grpc::DeleteFeedResponse {}))
+    }
+
+    // Interface to request the deletion (NIP-09) of the events
+    // published for an entry of a feed
+    pub async fn retract_entry(
+        app: MutexGuard<'_, App>,
+        request: Request<RetractEntryRequest>,
+    ) -> Result<Response<RetractEntryResponse>, Status> {
+        let data = request.into_inner();
+        let feed_id = data.feed_id.trim();
+        let entry_id = data.entry_id.trim();
+
+        let feed = match app.rss.feeds.iter().find(|f| f.id == feed_id) {
+            Some(feed) => feed.clone(),
+            None => {
+                return Err(Status::new(
+                    Code::NotFound,
+                    "No feed found with provided id",
+                ));
+            }
+        };
+
+        let record = match app
+            .seen_store
+            .lock()
+            .await
+            .get_entry_records(feed_id)
+            .remove(entry_id)
+        {
+            Some(record) if !record.events.is_empty() => record,
+            _ => {
+                return Err(Status::new(
+                    Code::NotFound,
+                    "No published event found for provided entry",
+                ));
+            }
+        };
+
+        let client = app.nostr_service.client.lock().await.clone();
+        let profiles = app.nostr_service.profiles.lock().await.clone();
+        let dry_run = app.config.lock().await.dry_run;
+
+        let mut ctx = PublishContext::new(&feed, &client, &profiles, dry_run);
+        let deleted = RssNostrJob::delete_entry_events(&mut ctx, entry_id, &record).await;
+
+        {
+            let mut store = app.seen_store.lock().await;
+
+            // The records are read again as the job of the feed may have updated them
+            // while the deletions were sent. Only the retracted events are removed, and
+            // the events of the profiles the deletion failed for are kept so the
+            // retraction can be retried.
+            let mut records = store.get_entry_records(feed_id);
+            if let Some(current) = records.get_mut(entry_id) {
+                current.events.retain(|event| {
+                    !record.events.contains(event) || deleted.is_pending(&event.profile_id)
+                });
+                store.set_entry_records(feed_id, records);
+            }
+
+            let now = now_timestamp();
+            store.add_publications(feed_id, ctx.report.ledger);
+            store.add_outbox_events(feed_id, ctx.report.outbox);
+            store.add_errors(
+                feed_id,
+                ctx.report.errors.iter().map(|e| e.to_record(now)).collect(),
+            );
+        }
+        flush_store(&app.seen_store).await;
+
+        match ctx.report.errors.first() {
+            Some(e) => Err(Status::new(Code::Internal, e.to_string())),
+            None => Ok(Response::new(grpc::RetractEntryResponse {
+                deletions: deleted.events.len() as u64,
+            })),
+        }
+    }
+
+    // Interface to retrieve the errors that occured while processing a feed
+    pub async fn feed_errors(
+        app: MutexGuard<'_, App>,
+        request: Request<FeedErrorsRequest>,
+    ) -> Result<Response<FeedErrorsResponse>, Status> {
+        let feed_id = request.into_inner().feed_id;
+        let feed_id = feed_id.trim();
+
+        if !app.rss.feeds.iter().any(|f| f.id == feed_id) {
+            return Err(Status::new(
+                Code::NotFound,
+                "No feed found with provided id",
+            ));
+        }
+
+        let store = app.seen_store.lock().await;
+
+        Ok(Response::new(grpc::FeedErrorsResponse {
+            count: store.get_error_count(feed_id),
+            errors: store
+                .get_errors(feed_id)
+                .into_iter()
+                .map(FeedErrorItem::from)
+                .collect(),
+        }))
+    }
+
+    // Interface to retrieve the health of a feed
+    // and the outcome of its last processing
+    pub async fn feed_status(
+        app: MutexGuard<'_, App>,
+        request: Request<FeedStatusRequest>,
+    ) -> Result<Response<FeedStatusResponse>, Status> {
+        let feed_id = request.into_inner().feed_id;
+        let feed_id = feed_id.trim();
+
+        if !app.rss.feeds.iter().any(|f| f.id == feed_id) {
+            return Err(Status::new(
+                Code::NotFound,
+                "No feed found with provided id",
+            ));
+        }
+
+        // Paused feeds have no job
+        let next_run = match app.rss.feeds_jobs.get(feed_id) {
+            Some(uuid) => {
+                let mut scheduler = (*app.rss.scheduler).clone();
+                match scheduler.next_tick_for_job(*uuid).await {
+                    Ok(next_tick) => next_tick.map(|tick| tick.timestamp()),
+                    Err(_) => None,
+                }
+            }
+            None => None,
+        };
+
+        let store = app.seen_store.lock().await;
+
+        Ok(Response::new(FeedStatusResponse {
+            last_report: store.get_last_report(feed_id).map(TickReportItem::from),
+            health: FeedHealthItem {
+                next_run,
+                ..FeedHealthItem::from(store.get_health(feed_id))
+            },
+        }))
+    }
+
+    // Interface to check a feed of the instance, or the definition of a feed before adding it
+    pub async fn validate_feed(
+        app: MutexGuard<'_, App>,
+        request: Request<ValidateFeedRequest>,
+    ) -> Result<Response<ValidateFeedResponse>, Status> {
+        let data = request.into_inner();
+
+        let problems = match (data.feed_id, data.feed) {
+            (Some(feed_id), _) => {
+                let feed = match app.rss.feeds.iter().find(|f| f.id == feed_id.trim()) {
+                    Some(feed) => feed.clone(),
+                    None => {
+                        return Err(Status::new(
+                            Code::NotFound,
+                            "No feed found with provided id",
+                        ))
+                    }
+                };
+                let http_client = app.rss.http_client.clone();
+                let profiles = app.nostr_service.profiles.lock().await.clone();
+
+                // The app is not locked while the feed is fetched
+                drop(app);
+
+                validation::validate_feed(&feed, &http_client, &profiles).await
+            }
+            (None, Some(feed)) => Self::check_feed_item(app, feed)
+                .await
+                .err()
+                .unwrap_or_default(),
+            (None, None) => {
+                return Err(Status::new(
+                    Code::InvalidArgument,
+                    "A feed id or a feed definition is required",
+                ))
+            }
+        };
+
+        Ok(Response::new(ValidateFeedResponse {
+            problems: problems.into_iter().map(FeedProblemItem::from).collect(),
+        }))
+    }
+
+    // Interface to build the events a feed would publish for its latest entries,
+    // without signing nor sending them
+    pub async fn preview_feed(
+        app: MutexGuard<'_, App>,
+        request: Request<PreviewFeedRequest>,
+    ) -> Result<Response<PreviewFeedResponse>, Status> {
+        let data = request.into_inner();
+        let entries = data.entries.unwrap_or(1) as usize;
+
+        let feed = match app.rss.feeds.iter().find(|f| f.id == data.feed_id.trim()) {
+            Some(feed) => feed.clone(),
+            None => {
+                return Err(Status::new(
+                    Code::NotFound,
+                    "No feed found with provided id",
+                ))
+            }
+        };
+
+        let parser = match RssParser::for_feed(&app.rss.http_client, &feed) {
+            Ok(parser) => parser,
+            Err(e) => return Err(Status::new(Code::FailedPrecondition, e.message)),
+        };
+        let profiles = app.nostr_service.profiles.lock().await.clone();
+
+        // The app is not locked while the feed is fetched
+        drop(app);
+
+        let remote = match parser.read(feed.url.to_string()).await {
+            Ok(remote) => remote,
+            Err(e) => return Err(Status::new(Code::Unavailable, e.message)),
+        };
+
+        let profile_ids = feed
+            .profiles
+            .clone()
+            .unwrap_or(["default".to_string()].to_vec());
+        let entries: Vec<Entry> = remote.entries.into_iter().take(entries).collect();
+
+        let events = RssNostrJob::preview(&feed, &entries, &profile_ids, &profiles);
+
+        Ok(Response::new(PreviewFeedResponse {
+            events: events.into_iter().map(EventPreviewItem::from).collect(),
+        }))
+    }
+
+    // Interface to find the feeds available at an url, e.g: the url of a website
+    pub async fn discover_feeds(
+        app: MutexGuard<'_, App>,
+        request: Request<DiscoverFeedsRequest>,
+    ) -> Result<Response<DiscoverFeedsResponse>, Status> {
+        let url = request.into_inner().url;
+        let url = url.trim();
+
+        if nostr_sdk::Url::from_str(url).is_err() {
+            return Err(Status::new(Code::InvalidArgument, "Invalid url"));
+        }
+
+        let parser = match RssParser::for_feed(&app.rss.http_client, &Feed::default()) {
+            Ok(parser) => parser,
+            Err(e) => return Err(Status::new(Code::Internal, e.message)),
+        };
+
+        // The app is not locked while the pages are fetched
+        drop(app);
+
+        match discover_feeds(&parser, url).await {
+            Ok(feeds) if feeds.is_empty() => {
+                Err(Status::new(Code::NotFound, "No feed found at provided url"))
+            }
+            Ok(feeds) => Ok(Response::new(DiscoverFeedsResponse {
+                feeds: feeds.into_iter().map(DiscoveredFeedItem::from).collect(),
+            })),
+            Err(e) => Err(Status::new(Code::Unavailable, e.message)),
+        }
+    }
+
+    // Interface to resume the job of a paused feed
+    pub async fn start_job(
         mut app: MutexGuard<'_, App>,
-        request: Request<AddFeedRequest>,
-    ) -> Result<Response<AddFeedResponse>, Status> {
+        request: Request<StartJobRequest>,
+    ) -> Result<Response<StartJobResponse>, Status> {
         let data = request.into_inner();
         let save = data.save();
-        let feed = Feed::from(data.feed);
-        let map = Arc::new(Mutex::new(app.feeds_map.clone()));
-        let profiles = app.get_profiles().await;
-        let client = app.nostr_service.get_client().await;
-        let config = app.get_config().await;
-        app.rss.feeds.push(feed.clone());
-
-        let job = schedule(
-            feed.schedule.clone().as_str(),
-            feed.clone(),
-            map,
-            client,
-            profiles,
-            config,
-        )
-        .await;
+        let feed_id = data.feed_id.trim().to_string();
 
-        _ = app.rss.feeds_jobs.insert(feed.id.clone(), job.guid());
-        _ = app.rss.scheduler.add(job).await;
+        let idx = match app.rss.feeds.iter().position(|f| f.id == feed_id) {
+            Some(idx) => idx,
+            None => {
+                return Err(Status::new(
+                    Code::NotFound,
+                    "No feed found with provided id",
+                ));
+            }
+        };
 
-        if save == true {
+        if app.rss.feeds_jobs.contains_key(&feed_id) {
+            return Err(Status::new(
+                Code::FailedPrecondition,
+                "Job associated to feed is already running",
+            ));
+        }
+
+        let feed = Feed {
+            paused: false,
+            ..app.rss.feeds[idx].clone()
+        };
+
+        // Feeds loaded from the configuration may have an invalid schedule
+        if let Err(e) = app.schedule_feed(&feed).await {
+            return Err(Status::new(Code::FailedPrecondition, e));
+        }
+        app.rss.feeds[idx].paused = false;
+
+        if save {
             _ = &app.update_feeds_config(&app.rss.feeds).await;
         }
 
-        Ok(Response::new(AddFeedResponse {}))
+        Ok(Response::new(grpc::StartJobResponse {}))
     }
 
-    // Interface to delete a feed on instance
-    pub async fn delete_feed(
+    // Interface to pause the job of a feed.
+    // The seen entries of the feed are kept in store.
+    pub async fn stop_job(
         mut app: MutexGuard<'_, App>,
-        request: Request<DeleteFeedRequest>,
-    ) -> Result<Response<DeleteFeedResponse>, Status> {
+        request: Request<StopJobRequest>,
+    ) -> Result<Response<StopJobResponse>, Status> {
         let data = request.into_inner();
-
         let save = data.save();
-        let feed_id = data.id;
+        let feed_id = data.feed_id.trim().to_string();
 
-        let idx = match app.rss.feeds.iter().position(|f| &f.id == &feed_id) {
+        let idx = match app.rss.feeds.iter().position(|f| f.id == feed_id) {
             Some(idx) => idx,
             None => {
                 return Err(Status::new(
@@ -91,24 +920,20 @@ impl FeedRequestHandler {
             }
         };
 
-        let app_clone = app.rss.clone();
-        let job_uuid = app_clone.feeds_jobs.get(feed_id.trim());
-
-        if job_uuid.is_none() {
+        if !app.unschedule_feed(&feed_id).await {
             return Err(Status::new(
-                Code::NotFound,
-                "Job associated to feed not found",
+                Code::FailedPrecondition,
+                "Job associated to feed is already paused",
             ));
         }
 
-        _ = &app.rss.feeds.remove(idx);
-        _ = &app.scheduler.remove(job_uuid.unwrap()).await;
+        app.rss.feeds[idx].paused = true;
 
-        if save == true {
+        if save {
             _ = &app.update_feeds_config(&app.rss.feeds).await;
         }
 
-        Ok(Response::new(grpc::DeleteFeedResponse {}))
+        Ok(Response::new(grpc::StopJobResponse {}))
     }
 }
 
@@ -118,8 +943,15 @@ mod tests {
     use super::*;
     use std::sync::Arc;
 
-    use crate::grpc::grpctest_utils::mock_app;
+    use crate::{
+        grpc::grpctest_utils::mock_app,
+        nostr::relay::Relay,
+        rss::config::{FeedKind, OnUpdate},
+        store::store::{EntryRecord, PublishedEvent},
+    };
+    use nostr_sdk::EventId;
     use nostrss_grpc::grpc::AddFeedRequest;
+    use std::collections::HashMap;
     use tokio::sync::Mutex;
     use tonic::Request;
 
@@ -148,23 +980,31 @@ mod tests {
             feed: FeedItem {
                 id: "test".to_string(),
                 name: "my test feed".to_string(),
-                url: "http://myrss.rs".to_string(),
+                url: fixture_url(),
                 schedule: "1/10 * * * * *".to_string(),
                 profiles: Vec::new(),
                 tags: Vec::new(),
                 template: None,
                 cache_size: Some(50),
                 pow_level: 50,
+                paused: None,
+                mirror_deletions: None,
+                auth: None,
+                headers: Vec::new(),
+                proxy: None,
+                shadow: None,
+                shadow_file: None,
+                kind: None,
+                on_update: None,
+                catch_up: None,
+                watch: None,
             },
             save: Some(false),
         };
 
         let request = Request::new(add_feed_request);
 
-        let add_feed_result = {
-            let app_lock = app.lock().await;
-            FeedRequestHandler::add_feed(app_lock, request).await
-        };
+        let add_feed_result = FeedRequestHandler::add_feed(Arc::clone(&app), request).await;
 
         assert_eq!(add_feed_result.is_ok(), true);
 
@@ -181,14 +1021,187 @@ mod tests {
         let response = feeds_list_request_result.unwrap().into_inner();
 
         assert_eq!(response.feeds.len(), 4);
+
+        // The job of the feed is scheduled once the app is released
+        assert!(app.lock().await.rss.feeds_jobs.contains_key("test"));
+    }
+
+    // Feeds are fetched when added, so tests use a local one
+    fn fixture_url() -> String {
+        let path = std::fs::canonicalize("./src/fixtures/feed.xml").unwrap();
+        nostr_sdk::Url::from_file_path(path).unwrap().to_string()
+    }
+
+    fn feed_item(id: &str, url: &str, schedule: &str) -> FeedItem {
+        FeedItem {
+            id: id.to_string(),
+            name: "my test feed".to_string(),
+            url: url.to_string(),
+            schedule: schedule.to_string(),
+            profiles: Vec::new(),
+            tags: Vec::new(),
+            template: None,
+            cache_size: None,
+            pow_level: 0,
+            paused: None,
+            mirror_deletions: None,
+            auth: None,
+            headers: Vec::new(),
+            proxy: None,
+            shadow: None,
+            shadow_file: None,
+            kind: None,
+            on_update: None,
+            catch_up: None,
+            watch: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn add_invalid_feed_test() {
+        let app = Arc::new(Mutex::new(mock_app().await));
+
+        let request = Request::new(AddFeedRequest {
+            feed: feed_item("test", &fixture_url(), "every minute"),
+            save: Some(false),
+        });
+
+        let result = FeedRequestHandler::add_feed(Arc::clone(&app), request).await;
+
+        let status = result.unwrap_err();
+        assert_eq!(status.code(), Code::InvalidArgument);
+        assert!(status.message().contains("schedule"));
+
+        let request = Request::new(AddFeedRequest {
+            feed: FeedItem {
+                kind: Some("video".to_string()),
+                ..feed_item("test", &fixture_url(), "0 * * * * *")
+            },
+            save: Some(false),
+        });
+
+        let result = FeedRequestHandler::add_feed(Arc::clone(&app), request).await;
+
+        let status = result.unwrap_err();
+        assert_eq!(status.code(), Code::InvalidArgument);
+        assert!(status.message().contains("Invalid feed kind"));
+
+        // Ids are unique
+        let request = Request::new(AddFeedRequest {
+            feed: feed_item("wikipedia", &fixture_url(), "0 * * * * *"),
+            save: Some(false),
+        });
+
+        let result = FeedRequestHandler::add_feed(Arc::clone(&app), request).await;
+
+        assert_eq!(result.unwrap_err().code(), Code::InvalidArgument);
+        assert_eq!(app.lock().await.rss.feeds.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn validate_feed_test() {
+        let app = Arc::new(Mutex::new(mock_app().await));
+
+        let request = Request::new(ValidateFeedRequest {
+            feed_id: None,
+            feed: Some(FeedItem {
+                profiles: ["unknown".to_string()].to_vec(),
+                ..feed_item("test", "file:///nonexistent/feed.xml", "0 * * * * *")
+            }),
+        });
+
+        let result = {
+            let app_lock = app.lock().await;
+            FeedRequestHandler::validate_feed(app_lock, request).await
+        };
+
+        let problems = result.unwrap().into_inner().problems;
+        let fields: Vec<&str> = problems.iter().map(|p| p.field.as_str()).collect();
+        assert_eq!(fields, ["profiles", "url"].to_vec());
+
+        let request = Request::new(ValidateFeedRequest {
+            feed_id: None,
+            feed: Some(feed_item("test", "not an url", "0 * * * * *")),
+        });
+
+        let result = {
+            let app_lock = app.lock().await;
+            FeedRequestHandler::validate_feed(app_lock, request).await
+        };
+
+        assert_eq!(result.unwrap().into_inner().problems[0].field, "url");
+
+        let request = Request::new(ValidateFeedRequest {
+            feed_id: Some("unknown".to_string()),
+            feed: None,
+        });
+
+        let result = {
+            let app_lock = app.lock().await;
+            FeedRequestHandler::validate_feed(app_lock, request).await
+        };
+
+        assert_eq!(result.unwrap_err().code(), Code::NotFound);
+    }
+
+    #[tokio::test]
+    async fn preview_feed_test() {
+        let app = Arc::new(Mutex::new(mock_app().await));
+
+        app.lock().await.rss.feeds.push(Feed {
+            id: "local".to_string(),
+            url: nostr_sdk::Url::parse(&fixture_url()).unwrap(),
+            profiles: Some(["default".to_string(), "test".to_string()].to_vec()),
+            ..Default::default()
+        });
+
+        let request = Request::new(PreviewFeedRequest {
+            feed_id: "local".to_string(),
+            entries: Some(2),
+        });
+
+        let result = {
+            let app_lock = app.lock().await;
+            FeedRequestHandler::preview_feed(app_lock, request).await
+        };
+
+        // An event is built for each entry and each profile
+        let events = result.unwrap().into_inner().events;
+        assert_eq!(events.len(), 4);
+        assert!(events
+            .iter()
+            .all(|e| e.event.is_some() && e.error.is_none()));
+
+        // Nothing is recorded for the feed
+        assert!(app
+            .lock()
+            .await
+            .seen_store
+            .lock()
+            .await
+            .get("local")
+            .is_none());
+
+        let request = Request::new(PreviewFeedRequest {
+            feed_id: "unknown".to_string(),
+            entries: None,
+        });
+
+        let result = {
+            let app_lock = app.lock().await;
+            FeedRequestHandler::preview_feed(app_lock, request).await
+        };
+
+        assert_eq!(result.unwrap_err().code(), Code::NotFound);
     }
 
     #[tokio::test]
     async fn delete_feed_test() {
         let app = Arc::new(Mutex::new(mock_app().await));
 
+        // Ids are trimmed
         let delete_feed_request = DeleteFeedRequest {
-            id: "stackernews".to_string(),
+            id: " stackernews ".to_string(),
             save: Some(false),
         };
 
@@ -211,6 +1224,548 @@ mod tests {
         let response = feeds_list_request_result.unwrap().into_inner();
 
         assert_eq!(response.feeds.len(), 2);
+
+        // The job and the seen entries of the feed are removed
+        let app_lock = app.lock().await;
+        assert!(!app_lock.rss.feeds_jobs.contains_key("stackernews"));
+        assert!(app_lock
+            .seen_store
+            .lock()
+            .await
+            .get("stackernews")
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn update_feed_test() {
+        let app = Arc::new(Mutex::new(mock_app().await));
+
+        let job_uuid = {
+            let app_lock = app.lock().await;
+            app_lock.seen_store.lock().await.set_http_cache(
+                "stackernews",
+                HttpCache {
+                    etag: Some("\"abc\"".to_string()),
+                    last_modified: Some("Wed, 21 Oct 2015 07:28:00 GMT".to_string()),
+                    not_before: Some(now_timestamp() + 3600),
+                },
+            );
+            app_lock.rss.feeds_jobs["stackernews"]
+        };
+
+        let update_feed_request = Request::new(UpdateFeedRequest {
+            id: "stackernews".to_string(),
+            name: Some("Updated name".to_string()),
+            url: Some(fixture_url()),
+            schedule: Some("1/20 * * * * *".to_string()),
+            tags: ["bitcoin".to_string()].to_vec(),
+            save: Some(false),
+            ..Default::default()
+        });
+
+        let update_feed_result =
+            FeedRequestHandler::update_feed(Arc::clone(&app), update_feed_request).await;
+        assert!(update_feed_result.is_ok());
+
+        let feed = update_feed_result.unwrap().into_inner().feed;
+        assert_eq!(feed.name, "Updated name");
+        assert_eq!(feed.schedule, "1/20 * * * * *");
+        assert_eq!(feed.tags, ["bitcoin".to_string()].to_vec());
+        assert_eq!(feed.url, fixture_url());
+
+        // Job has been rebuilt and seen entries are kept
+        let app_lock = app.lock().await;
+        assert_ne!(app_lock.rss.feeds_jobs["stackernews"], job_uuid);
+
+        let store = app_lock.seen_store.lock().await;
+        assert!(store.get("stackernews").is_some());
+
+        // The cache of the previous url is cleared
+        assert_eq!(store.get_http_cache("stackernews"), HttpCache::default());
+    }
+
+    #[tokio::test]
+    async fn update_invalid_feed_test() {
+        let app = Arc::new(Mutex::new(mock_app().await));
+
+        let job_uuid = {
+            let app_lock = app.lock().await;
+            app_lock.rss.feeds_jobs["stackernews"]
+        };
+
+        let update_feed_request = Request::new(UpdateFeedRequest {
+            id: "stackernews".to_string(),
+            url: Some(fixture_url()),
+            schedule: Some("every minute".to_string()),
+            save: Some(false),
+            ..Default::default()
+        });
+
+        let update_feed_result =
+            FeedRequestHandler::update_feed(Arc::clone(&app), update_feed_request).await;
+        assert_eq!(
+            update_feed_result.unwrap_err().code(),
+            Code::InvalidArgument
+        );
+
+        // The feed and its job are left untouched
+        let app_lock = app.lock().await;
+        let feed = app_lock
+            .rss
+            .feeds
+            .iter()
+            .find(|f| f.id == "stackernews")
+            .unwrap();
+        assert_eq!(feed.url.to_string(), "https://stacker.news/rss");
+        assert_eq!(app_lock.rss.feeds_jobs["stackernews"], job_uuid);
+    }
+
+    #[tokio::test]
+    async fn discover_feeds_invalid_url_test() {
+        let app = Arc::new(Mutex::new(mock_app().await));
+
+        let request = Request::new(DiscoverFeedsRequest {
+            url: "not an url".to_string(),
+        });
+
+        let result = FeedRequestHandler::discover_feeds(app.lock().await, request).await;
+        assert_eq!(result.unwrap_err().code(), Code::InvalidArgument);
+    }
+
+    #[test]
+    fn patch_feed_invalid_url_test() {
+        let data = UpdateFeedRequest {
+            id: "default".to_string(),
+            url: Some("not an url".to_string()),
+            ..Default::default()
+        };
+
+        let result = FeedRequestHandler::patch_feed(Feed::default(), data);
+
+        assert_eq!(result.unwrap_err(), "Invalid feed url");
+    }
+
+    #[test]
+    fn patch_feed_shadow_test() {
+        let data = UpdateFeedRequest {
+            id: "default".to_string(),
+            shadow: Some(true),
+            shadow_file: Some("./shadow.jsonl".to_string()),
+            ..Default::default()
+        };
+
+        let feed = FeedRequestHandler::patch_feed(Feed::default(), data).unwrap();
+        assert!(feed.shadow);
+        assert_eq!(feed.shadow_file, Some("./shadow.jsonl".to_string()));
+
+        // Going live keeps the shadow file unless it is removed
+        let data = UpdateFeedRequest {
+            id: "default".to_string(),
+            shadow: Some(false),
+            ..Default::default()
+        };
+
+        let feed = FeedRequestHandler::patch_feed(feed, data).unwrap();
+        assert!(!feed.shadow);
+        assert_eq!(feed.shadow_file, Some("./shadow.jsonl".to_string()));
+
+        let data = UpdateFeedRequest {
+            id: "default".to_string(),
+            shadow_file: Some("".to_string()),
+            ..Default::default()
+        };
+
+        let feed = FeedRequestHandler::patch_feed(feed, data).unwrap();
+        assert_eq!(feed.shadow_file, None);
+    }
+
+    #[test]
+    fn patch_feed_proxy_test() {
+        let data = UpdateFeedRequest {
+            id: "default".to_string(),
+            proxy: Some("127.0.0.1:9050".to_string()),
+            ..Default::default()
+        };
+
+        let feed = FeedRequestHandler::patch_feed(Feed::default(), data).unwrap();
+        assert_eq!(feed.proxy, Some("127.0.0.1:9050".to_string()));
+
+        let data = UpdateFeedRequest {
+            id: "default".to_string(),
+            proxy: Some("http://127.0.0.1:3128".to_string()),
+            ..Default::default()
+        };
+
+        let feed = FeedRequestHandler::patch_feed(feed, data).unwrap();
+        assert_eq!(feed.proxy, Some("http://127.0.0.1:3128".to_string()));
+
+        // An empty proxy removes the proxy of the feed
+        let data = UpdateFeedRequest {
+            id: "default".to_string(),
+            proxy: Some("".to_string()),
+            ..Default::default()
+        };
+
+        let feed = FeedRequestHandler::patch_feed(feed, data).unwrap();
+        assert_eq!(feed.proxy, None);
+
+        let data = UpdateFeedRequest {
+            id: "default".to_string(),
+            proxy: Some("not a proxy".to_string()),
+            ..Default::default()
+        };
+
+        let result = FeedRequestHandler::patch_feed(feed, data);
+        assert_eq!(result.unwrap_err(), "Invalid feed proxy url");
+    }
+
+    #[test]
+    fn patch_feed_clear_test() {
+        let feed = Feed {
+            profiles: Some(["default".to_string()].to_vec()),
+            tags: Some(["bitcoin".to_string()].to_vec()),
+            template: Some("./template.md".to_string()),
+            proxy: Some("127.0.0.1:9050".to_string()),
+            ..Default::default()
+        };
+
+        let data = UpdateFeedRequest {
+            id: "default".to_string(),
+            clear: ["profiles", "tags", "template", "proxy", "headers"]
+                .map(String::from)
+                .to_vec(),
+            ..Default::default()
+        };
+
+        let feed = FeedRequestHandler::patch_feed(feed, data).unwrap();
+        assert_eq!(feed.profiles, None);
+        assert_eq!(feed.tags, None);
+        assert_eq!(feed.template, None);
+        assert_eq!(feed.proxy, None);
+        assert_eq!(feed.headers, None);
+
+        // A field can not be both cleared and provided
+        let data = UpdateFeedRequest {
+            id: "default".to_string(),
+            tags: ["nostr".to_string()].to_vec(),
+            clear: ["tags".to_string()].to_vec(),
+            ..Default::default()
+        };
+
+        let result = FeedRequestHandler::patch_feed(feed.clone(), data);
+        assert_eq!(
+            result.unwrap_err(),
+            "A cleared feed field can not be provided"
+        );
+
+        let data = UpdateFeedRequest {
+            id: "default".to_string(),
+            clear: ["url".to_string()].to_vec(),
+            ..Default::default()
+        };
+
+        let result = FeedRequestHandler::patch_feed(feed, data);
+        assert_eq!(result.unwrap_err(), "Unknown feed field to clear");
+    }
+
+    #[test]
+    fn patch_feed_policies_test() {
+        let data = UpdateFeedRequest {
+            id: "default".to_string(),
+            kind: Some("article".to_string()),
+            on_update: Some("delete_and_repost".to_string()),
+            catch_up: Some(CatchUpItem {
+                policy: "max_entries".to_string(),
+                count: Some(5),
+                seconds: None,
+            }),
+            watch: Some(true),
+            mirror_deletions: Some(true),
+            ..Default::default()
+        };
+
+        let feed = FeedRequestHandler::patch_feed(Feed::default(), data).unwrap();
+        assert_eq!(feed.kind, FeedKind::Article);
+        assert_eq!(feed.on_update, OnUpdate::DeleteAndRepost);
+        assert_eq!(feed.catch_up, CatchUp::MaxEntries { count: 5 });
+        assert!(feed.watch);
+        assert!(feed.mirror_deletions);
+
+        let data = UpdateFeedRequest {
+            id: "default".to_string(),
+            kind: Some("video".to_string()),
+            ..Default::default()
+        };
+
+        let result = FeedRequestHandler::patch_feed(feed.clone(), data);
+        assert_eq!(result.unwrap_err(), "Invalid feed kind");
+
+        // The max age policy requires its duration
+        let data = UpdateFeedRequest {
+            id: "default".to_string(),
+            catch_up: Some(CatchUpItem {
+                policy: "max_age".to_string(),
+                count: None,
+                seconds: None,
+            }),
+            ..Default::default()
+        };
+
+        let result = FeedRequestHandler::patch_feed(feed, data);
+        assert_eq!(result.unwrap_err(), "Invalid catch up max age");
+    }
+
+    #[tokio::test]
+    async fn stop_and_start_job_test() {
+        let app = Arc::new(Mutex::new(mock_app().await));
+
+        let stop_job_request = Request::new(StopJobRequest {
+            feed_id: "stackernews".to_string(),
+            save: Some(false),
+        });
+
+        let stop_job_result =
+            FeedRequestHandler::stop_job(app.lock().await, stop_job_request).await;
+        assert!(stop_job_result.is_ok());
+
+        {
+            let app_lock = app.lock().await;
+            assert!(!app_lock.rss.feeds_jobs.contains_key("stackernews"));
+        }
+
+        let feed_info_request = Request::new(FeedInfoRequest {
+            id: "stackernews".to_string(),
+        });
+        let response = FeedRequestHandler::feed_info(app.lock().await, feed_info_request)
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(response.feed.paused, Some(true));
+
+        // Stopping an already paused feed fails
+        let stop_job_request = Request::new(StopJobRequest {
+            feed_id: "stackernews".to_string(),
+            save: Some(false),
+        });
+        let stop_job_result =
+            FeedRequestHandler::stop_job(app.lock().await, stop_job_request).await;
+        assert_eq!(
+            stop_job_result.unwrap_err().code(),
+            Code::FailedPrecondition
+        );
+
+        let start_job_request = Request::new(StartJobRequest {
+            feed_id: "stackernews".to_string(),
+            save: Some(false),
+        });
+        let start_job_result =
+            FeedRequestHandler::start_job(app.lock().await, start_job_request).await;
+        assert!(start_job_result.is_ok());
+
+        let app_lock = app.lock().await;
+        assert!(app_lock.rss.feeds_jobs.contains_key("stackernews"));
+        assert!(!app_lock.rss.feeds[1].paused);
+    }
+
+    #[tokio::test]
+    async fn start_unknown_job_test() {
+        let app = Arc::new(Mutex::new(mock_app().await));
+
+        let start_job_request = Request::new(StartJobRequest {
+            feed_id: "unknown".to_string(),
+            save: Some(false),
+        });
+        let start_job_result =
+            FeedRequestHandler::start_job(app.lock().await, start_job_request).await;
+
+        assert_eq!(start_job_result.unwrap_err().code(), Code::NotFound);
+    }
+
+    #[tokio::test]
+    async fn retract_entry_test() {
+        let app = Arc::new(Mutex::new(mock_app().await));
+
+        let retract_request = |feed_id: &str, entry_id: &str| {
+            Request::new(RetractEntryRequest {
+                feed_id: feed_id.to_string(),
+                entry_id: entry_id.to_string(),
+            })
+        };
+
+        let result =
+            FeedRequestHandler::retract_entry(app.lock().await, retract_request("unknown", "a"))
+                .await;
+        assert_eq!(result.unwrap_err().code(), Code::NotFound);
+
+        let result = FeedRequestHandler::retract_entry(
+            app.lock().await,
+            retract_request("stackernews", "a"),
+        )
+        .await;
+        assert_eq!(result.unwrap_err().code(), Code::NotFound);
+
+        {
+            let app_lock = app.lock().await;
+            let mut records = HashMap::new();
+            records.insert(
+                "a".to_string(),
+                EntryRecord {
+                    hash: "hash".to_string(),
+                    updated: None,
+                    events: [PublishedEvent {
+                        profile_id: "default".to_string(),
+                        event_id: EventId::all_zeros().to_hex(),
+                    }]
+                    .to_vec(),
+                    pending: Vec::new(),
+                },
+            );
+            app_lock
+                .seen_store
+                .lock()
+                .await
+                .set_entry_records("stackernews", records);
+        }
+
+        // Events are kept while the profile has no relay to send the deletions to
+        let result = FeedRequestHandler::retract_entry(
+            app.lock().await,
+            retract_request("stackernews", "a"),
+        )
+        .await;
+        assert_eq!(result.unwrap_err().code(), Code::Internal);
+
+        {
+            let app_lock = app.lock().await;
+            app_lock.config.lock().await.dry_run = true;
+
+            let mut profiles = app_lock.nostr_service.profiles.lock().await;
+            profiles.get_mut("default").unwrap().relays = [Relay {
+                name: "test".to_string(),
+                target: "wss://relay.invalid".to_string(),
+                active: true,
+                proxy: None,
+                pow_level: 0,
+            }]
+            .to_vec();
+        }
+
+        let result = FeedRequestHandler::retract_entry(
+            app.lock().await,
+            retract_request("stackernews", "a"),
+        )
+        .await;
+        assert!(result.is_ok());
+
+        // Events of the entry are only deleted once
+        let app_lock = app.lock().await;
+        let records = app_lock
+            .seen_store
+            .lock()
+            .await
+            .get_entry_records("stackernews");
+        assert!(records["a"].events.is_empty());
+    }
+
+    #[tokio::test]
+    async fn feed_errors_test() {
+        let app = Arc::new(Mutex::new(mock_app().await));
+
+        let error = FeedError {
+            timestamp: 1,
+            kind: "fetch".to_string(),
+            profile_id: None,
+            entry_id: None,
+            message: "timeout".to_string(),
+        };
+
+        {
+            let app_lock = app.lock().await;
+            app_lock
+                .seen_store
+                .lock()
+                .await
+                .add_errors("stackernews", [error.clone(), error].to_vec());
+        }
+
+        let errors_request = |feed_id: &str| {
+            Request::new(FeedErrorsRequest {
+                feed_id: feed_id.to_string(),
+            })
+        };
+
+        let response =
+            FeedRequestHandler::feed_errors(app.lock().await, errors_request("stackernews"))
+                .await
+                .unwrap()
+                .into_inner();
+        assert_eq!(response.count, 2);
+        assert_eq!(response.errors[0].kind, "fetch");
+
+        let result =
+            FeedRequestHandler::feed_errors(app.lock().await, errors_request("unknown")).await;
+        assert_eq!(result.unwrap_err().code(), Code::NotFound);
+    }
+
+    #[tokio::test]
+    async fn feed_status_test() {
+        let app = Arc::new(Mutex::new(mock_app().await));
+
+        let status_request = |feed_id: &str| {
+            Request::new(FeedStatusRequest {
+                feed_id: feed_id.to_string(),
+            })
+        };
+
+        let response =
+            FeedRequestHandler::feed_status(app.lock().await, status_request("stackernews"))
+                .await
+                .unwrap()
+                .into_inner();
+        assert!(response.last_report.is_none());
+        assert_eq!(response.health.last_fetch, None);
+
+        let mut report = TickReport {
+            timestamp: 1,
+            published: ["a".to_string()].to_vec(),
+            skipped: 2,
+            ..Default::default()
+        };
+        report.fail("b", "Profile unknown not found".to_string());
+
+        {
+            let app_lock = app.lock().await;
+            app_lock
+                .seen_store
+                .lock()
+                .await
+                .set_last_report("stackernews", report);
+
+            let mut health = FeedHealth::default();
+            health.record_failure(1, Some(503), "unavailable".to_string());
+            app_lock
+                .seen_store
+                .lock()
+                .await
+                .set_health("stackernews", health);
+        }
+
+        let response =
+            FeedRequestHandler::feed_status(app.lock().await, status_request("stackernews"))
+                .await
+                .unwrap()
+                .into_inner();
+        let last_report = response.last_report.unwrap();
+        assert_eq!(last_report.published, ["a".to_string()].to_vec());
+        assert_eq!(last_report.skipped, 2);
+        assert_eq!(last_report.failed[0].entry_id, "b");
+        assert_eq!(response.health.consecutive_failures, 1);
+        assert_eq!(response.health.http_status, Some(503));
+        assert_eq!(response.health.last_error, Some("unavailable".to_string()));
+
+        let result =
+            FeedRequestHandler::feed_status(app.lock().await, status_request("unknown")).await;
+        assert_eq!(result.unwrap_err().code(), Code::NotFound);
     }
 
     #[tokio::test]
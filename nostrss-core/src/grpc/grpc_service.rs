@@ -2,22 +2,33 @@ use std::str::FromStr;
 use std::sync::Arc;
 
 use nostr_sdk::{prelude::ToBech32, Keys};
+use serde::{de::DeserializeOwned, Serialize};
 
-use crate::rss::config::Feed;
+use crate::rss::config::{CatchUp, Feed};
 use nostrss_grpc::grpc::{
     self, nostrss_grpc_server::NostrssGrpc, AddFeedRequest, AddFeedResponse, AddProfileRequest,
-    AddProfileResponse, DeleteFeedRequest, DeleteFeedResponse, DeleteProfileRequest,
-    DeleteProfileResponse, FeedInfoRequest, FeedInfoResponse, FeedItem, FeedsListRequest,
-    FeedsListResponse, ProfileInfoRequest, ProfileInfoResponse, ProfileItem, ProfilesListRequest,
-    ProfilesListResponse, StartJobRequest, StartJobResponse, StateRequest, StateResponse,
-    StopJobRequest, StopJobResponse,
+    AddProfileResponse, AddRelayRequest, AddRelayResponse, CatchUpItem, DeleteFeedRequest,
+    DeleteFeedResponse, DeleteProfileRequest, DeleteProfileResponse, DeleteRelayRequest,
+    DeleteRelayResponse, DiscoverFeedsRequest, DiscoverFeedsResponse, FeedErrorsRequest,
+    FeedErrorsResponse, FeedInfoRequest, FeedInfoResponse, FeedItem, FeedStatusRequest,
+    FeedStatusResponse, FeedsListRequest, FeedsListResponse, ListOutboxRequest, ListOutboxResponse,
+    ListPublicationsRequest, ListPublicationsResponse, PreviewFeedRequest, PreviewFeedResponse,
+    ProfileInfoRequest, ProfileInfoResponse, ProfileItem, ProfilesListRequest,
+    ProfilesListResponse, PublicationInfoRequest, PublicationInfoResponse, RelayInfoRequest,
+    RelayInfoResponse, RelaysListRequest, RelaysListResponse, RetractEntryRequest,
+    RetractEntryResponse, StartJobRequest, StartJobResponse, StateRequest, StateResponse,
+    StopJobRequest, StopJobResponse, UpdateFeedRequest, UpdateFeedResponse, ValidateFeedRequest,
+    ValidateFeedResponse,
 };
 use tokio::sync::{Mutex, MutexGuard};
 use tonic::{Request, Response, Status};
 
 use crate::{app::app::App, profiles::config::Profile};
 
-use super::{feed_request::FeedRequestHandler, profile_request::ProfileRequestHandler};
+use super::{
+    feed_request::FeedRequestHandler, profile_request::ProfileRequestHandler,
+    publication_request::PublicationRequestHandler, relay_request::RelayRequestHandler,
+};
 
 /// Provides the gRPC service handling that allows
 /// remote operations.
@@ -56,10 +67,36 @@ impl From<FeedItem> for Feed {
             template: value.template,
             cache_size,
             pow_level,
+            paused: value.paused.unwrap_or(false),
+            mirror_deletions: value.mirror_deletions.unwrap_or(false),
+            shadow: value.shadow.unwrap_or(false),
+            shadow_file: value.shadow_file.filter(|path| !path.trim().is_empty()),
+            // Invalid values are rejected when the feed is checked before being added
+            kind: value.kind.and_then(parse_name).unwrap_or_default(),
+            on_update: value.on_update.and_then(parse_name).unwrap_or_default(),
+            catch_up: value
+                .catch_up
+                .and_then(|catch_up| CatchUp::try_from(catch_up).ok())
+                .unwrap_or_default(),
+            watch: value.watch.unwrap_or(false),
+            ..Default::default()
         }
     }
 }
 
+// Parses a value of the feed config from its name, e.g: `article` for the feed kind
+fn parse_name<T: DeserializeOwned>(name: String) -> Option<T> {
+    serde_json::from_value(serde_json::Value::String(name.trim().to_string())).ok()
+}
+
+// Gets the name of a value of the feed config, e.g: `article` for the feed kind
+fn value_name<T: Serialize>(value: &T) -> Option<String> {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(name)) => Some(name),
+        _ => None,
+    }
+}
+
 impl From<Profile> for ProfileItem {
     fn from(value: Profile) -> Self {
         let public_key = match Keys::parse(&value.private_key) {
@@ -71,7 +108,7 @@ impl From<Profile> for ProfileItem {
             id: value.id,
             public_key,
             name: value.name,
-            relays: Vec::new(),
+            relays: value.relays.into_iter().map(|relay| relay.name).collect(),
             display_name: value.display_name,
             description: value.description,
             picture: value.picture,
@@ -102,6 +139,15 @@ impl From<Feed> for FeedItem {
         };
         let pow_level = value.pow_level as u64;
 
+        // Secrets are never returned
+        let auth = value.auth.map(|auth| auth.masked());
+        let headers = value
+            .headers
+            .unwrap_or_default()
+            .iter()
+            .map(|(name, value)| format!("{}: {}", name, value.masked()))
+            .collect();
+
         FeedItem {
             id: value.id,
             name: value.name,
@@ -112,6 +158,17 @@ impl From<Feed> for FeedItem {
             template: value.template,
             cache_size,
             pow_level,
+            paused: Some(value.paused),
+            mirror_deletions: Some(value.mirror_deletions),
+            auth,
+            headers,
+            proxy: value.proxy,
+            shadow: Some(value.shadow),
+            shadow_file: value.shadow_file,
+            kind: value_name(&value.kind),
+            on_update: value_name(&value.on_update),
+            catch_up: Some(CatchUpItem::from(value.catch_up)),
+            watch: Some(value.watch),
         }
     }
 }
@@ -130,7 +187,7 @@ impl NostrssGrpc for NostrssServerService {
         request: Request<StateRequest>,
     ) -> Result<Response<StateResponse>, Status> {
         let app_lock = self.app.lock().await;
-        let n = app_lock.nostr_service.profiles.keys().len();
+        let n = app_lock.nostr_service.profiles.lock().await.len();
         let _ = request.into_inner();
         Ok(Response::new(grpc::StateResponse {
             state: format!("App is alive. Number of profiles : {}", n),
@@ -155,7 +212,39 @@ impl NostrssGrpc for NostrssServerService {
         &self,
         request: Request<AddFeedRequest>,
     ) -> Result<Response<AddFeedResponse>, Status> {
-        FeedRequestHandler::add_feed(self.get_app_lock().await, request).await
+        FeedRequestHandler::add_feed(Arc::clone(&self.app), request).await
+    }
+
+    // Interface to patch a feed on instance
+    async fn update_feed(
+        &self,
+        request: Request<UpdateFeedRequest>,
+    ) -> Result<Response<UpdateFeedResponse>, Status> {
+        FeedRequestHandler::update_feed(Arc::clone(&self.app), request).await
+    }
+
+    // Interface to check a feed before scheduling it
+    async fn validate_feed(
+        &self,
+        request: Request<ValidateFeedRequest>,
+    ) -> Result<Response<ValidateFeedResponse>, Status> {
+        FeedRequestHandler::validate_feed(self.get_app_lock().await, request).await
+    }
+
+    // Interface to preview the events a feed would publish
+    async fn preview_feed(
+        &self,
+        request: Request<PreviewFeedRequest>,
+    ) -> Result<Response<PreviewFeedResponse>, Status> {
+        FeedRequestHandler::preview_feed(self.get_app_lock().await, request).await
+    }
+
+    // Interface to find the feeds available at an url
+    async fn discover_feeds(
+        &self,
+        request: Request<DiscoverFeedsRequest>,
+    ) -> Result<Response<DiscoverFeedsResponse>, Status> {
+        FeedRequestHandler::discover_feeds(self.get_app_lock().await, request).await
     }
 
     // Interface to delete a feed on instance
@@ -198,28 +287,100 @@ impl NostrssGrpc for NostrssServerService {
         ProfileRequestHandler::delete_profile(self.get_app_lock().await, request).await
     }
 
-    // Interface to start a job on instance
+    // Interface to retrieve the list of relays on instance
+    async fn relays_list(
+        &self,
+        request: Request<RelaysListRequest>,
+    ) -> Result<Response<RelaysListResponse>, Status> {
+        RelayRequestHandler::relays_list(self.get_app_lock().await, request).await
+    }
+
+    // Interface to retrieve the detailed configuration of a single relay on instance
+    async fn relay_info(
+        &self,
+        request: Request<RelayInfoRequest>,
+    ) -> Result<Response<RelayInfoResponse>, Status> {
+        RelayRequestHandler::relay_info(self.get_app_lock().await, request).await
+    }
+
+    // Interface to add a relay on instance
+    async fn add_relay(
+        &self,
+        request: Request<AddRelayRequest>,
+    ) -> Result<Response<AddRelayResponse>, Status> {
+        RelayRequestHandler::add_relay(self.get_app_lock().await, request).await
+    }
+
+    // Interface to delete a relay on instance
+    async fn delete_relay(
+        &self,
+        request: Request<DeleteRelayRequest>,
+    ) -> Result<Response<DeleteRelayResponse>, Status> {
+        RelayRequestHandler::delete_relay(self.get_app_lock().await, request).await
+    }
+
+    // Interface to delete the events published for an entry
+    async fn retract_entry(
+        &self,
+        request: Request<RetractEntryRequest>,
+    ) -> Result<Response<RetractEntryResponse>, Status> {
+        FeedRequestHandler::retract_entry(self.get_app_lock().await, request).await
+    }
+
+    // Interface to retrieve the errors of a feed
+    async fn feed_errors(
+        &self,
+        request: Request<FeedErrorsRequest>,
+    ) -> Result<Response<FeedErrorsResponse>, Status> {
+        FeedRequestHandler::feed_errors(self.get_app_lock().await, request).await
+    }
+
+    // Interface to retrieve the report of the last processing of a feed
+    async fn feed_status(
+        &self,
+        request: Request<FeedStatusRequest>,
+    ) -> Result<Response<FeedStatusResponse>, Status> {
+        FeedRequestHandler::feed_status(self.get_app_lock().await, request).await
+    }
+
+    // Interface to retrieve the publication ledger
+    async fn list_publications(
+        &self,
+        request: Request<ListPublicationsRequest>,
+    ) -> Result<Response<ListPublicationsResponse>, Status> {
+        PublicationRequestHandler::list_publications(self.get_app_lock().await, request).await
+    }
+
+    // Interface to retrieve a single publication by its event id
+    async fn publication_info(
+        &self,
+        request: Request<PublicationInfoRequest>,
+    ) -> Result<Response<PublicationInfoResponse>, Status> {
+        PublicationRequestHandler::publication_info(self.get_app_lock().await, request).await
+    }
+
+    // Interface to retrieve the events waiting to be acknowledged by enough relays
+    async fn list_outbox(
+        &self,
+        request: Request<ListOutboxRequest>,
+    ) -> Result<Response<ListOutboxResponse>, Status> {
+        PublicationRequestHandler::list_outbox(self.get_app_lock().await, request).await
+    }
+
+    // Interface to resume the job of a feed on instance
     async fn start_job(
         &self,
         request: Request<StartJobRequest>,
     ) -> Result<Response<StartJobResponse>, Status> {
-        let _app_lock = self.app.lock().await;
-        let _feed_id = &request.into_inner().feed_id;
-
-        Ok(Response::new(grpc::StartJobResponse {}))
+        FeedRequestHandler::start_job(self.get_app_lock().await, request).await
     }
 
-    // Interface to retrieve a job instance
-    // This should be renamed `stop_jobs` and
-    // should only shutdown the scheduler
+    // Interface to pause the job of a feed on instance
     async fn stop_job(
         &self,
         request: Request<StopJobRequest>,
     ) -> Result<Response<StopJobResponse>, Status> {
-        let _app_lock = self.app.lock().await;
-        let _feed_id = &request.into_inner().feed_id;
-
-        Ok(Response::new(grpc::StopJobResponse {}))
+        FeedRequestHandler::stop_job(self.get_app_lock().await, request).await
     }
 }
 
@@ -227,7 +388,7 @@ impl NostrssGrpc for NostrssServerService {
 mod tests {
 
     use super::*;
-    use crate::rss::config::Feed;
+    use crate::rss::config::{Feed, FeedKind, OnUpdate};
     use nostrss_grpc::grpc::AddFeedRequest;
 
     #[test]
@@ -243,6 +404,21 @@ mod tests {
                 template: None,
                 cache_size: Some(10),
                 pow_level: 20,
+                paused: None,
+                mirror_deletions: None,
+                auth: None,
+                headers: Vec::new(),
+                proxy: None,
+                shadow: None,
+                shadow_file: None,
+                kind: Some("article".to_string()),
+                on_update: Some("correction".to_string()),
+                catch_up: Some(CatchUpItem {
+                    policy: "max_entries".to_string(),
+                    count: Some(5),
+                    seconds: None,
+                }),
+                watch: Some(true),
             },
             save: Some(false),
         };
@@ -258,6 +434,18 @@ mod tests {
         let expected = "https://myrss.rs/";
         let url = feed.url.as_str();
         assert_eq!(url, expected);
+
+        assert_eq!(feed.kind, FeedKind::Article);
+        assert_eq!(feed.on_update, OnUpdate::Correction);
+        assert_eq!(feed.catch_up, CatchUp::MaxEntries { count: 5 });
+        assert!(feed.watch);
+
+        // The settings are returned as provided
+        let item = FeedItem::from(feed);
+        assert_eq!(item.kind, Some("article".to_string()));
+        assert_eq!(item.on_update, Some("correction".to_string()));
+        assert_eq!(item.catch_up.unwrap().count, Some(5));
+        assert_eq!(item.watch, Some(true));
     }
 
     #[test]
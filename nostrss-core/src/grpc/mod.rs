@@ -1,13 +1,15 @@
 pub mod feed_request;
 pub mod grpc_service;
 pub mod profile_request;
+pub mod publication_request;
+pub mod relay_request;
 
 #[cfg(test)]
 mod grpctest_utils {
     use std::{collections::HashMap, sync::Arc};
+    use tokio::sync::Mutex;
 
     use dotenv::from_filename;
-    use tokio::sync::Mutex;
 
     use crate::{
         app::app::{App, AppConfig},
@@ -15,6 +17,7 @@ mod grpctest_utils {
         profiles::{config::Profile, profiles::ProfileHandler},
         rss::{config::RssConfig, rss::RssInstance},
         scheduler::scheduler::schedule,
+        store::store::load_store,
     };
 
     pub async fn mock_app() -> App {
@@ -39,19 +42,17 @@ mod grpctest_utils {
         profiles.insert(test_profile.id.clone(), test_profile);
 
         let nostr_service = NostrService {
-            profiles,
+            profiles: Arc::new(Mutex::new(profiles)),
             ..Default::default()
         };
-        let scheduler = tokio_cron_scheduler::JobScheduler::new().await.unwrap();
         let mut app = App {
             rss,
-            scheduler: Arc::new(scheduler),
             feeds_jobs: HashMap::new(),
-            feeds_map: HashMap::new(),
+            seen_store: load_store(&None).unwrap(),
             nostr_service,
-            config: AppConfig {
+            config: Arc::new(Mutex::new(AppConfig {
                 ..Default::default()
-            },
+            })),
             profile_handler: ProfileHandler(HashMap::new()),
         };
 
@@ -59,12 +60,14 @@ mod grpctest_utils {
             let job = schedule(
                 feed.clone().schedule.as_str(),
                 feed.clone(),
-                Arc::new(Mutex::new(app.feeds_map.clone())),
+                Arc::clone(&app.seen_store),
                 app.nostr_service.get_client().await,
                 app.get_profiles().await,
                 app.get_config().await,
+                app.rss.http_client.clone(),
             )
-            .await;
+            .await
+            .unwrap();
 
             _ = &app.rss.feeds_jobs.insert(feed.id, job.guid());
         }
@@ -43,7 +43,7 @@ impl ProfileRequestHandler {
     ) -> Result<Response<ProfilesListResponse>, Status> {
         let mut profiles = Vec::new();
 
-        for profile in app.nostr_service.profiles.clone() {
+        for profile in app.nostr_service.profiles.lock().await.clone() {
             profiles.push(ProfileItem::from(profile.1));
         }
 
@@ -56,24 +56,40 @@ impl ProfileRequestHandler {
         request: Request<ProfileInfoRequest>,
     ) -> Result<Response<ProfileInfoResponse>, Status> {
         let id = &request.into_inner().id;
-        match app.nostr_service.profiles.get(id.trim()) {
-            Some(_) => Ok(Response::new(ProfileInfoResponse {
-                profile: ProfileItem::from(app.nostr_service.profiles[id.trim()].clone()),
+        match app.nostr_service.profiles.lock().await.get(id.trim()) {
+            Some(profile) => Ok(Response::new(ProfileInfoResponse {
+                profile: ProfileItem::from(profile.clone()),
             })),
             None => Err(Status::new(Code::NotFound, "Profile not found")),
         }
     }
 
     pub async fn add_profile(
-        mut app: MutexGuard<'_, App>,
+        app: MutexGuard<'_, App>,
         request: Request<AddProfileRequest>,
     ) -> Result<Response<AddProfileResponse>, Status> {
         let new_profile_item = request.into_inner().profile;
-
-        let profile = Profile::from(new_profile_item);
+        let relays_ids = new_profile_item.relays.clone();
+
+        let mut profile = Profile::from(new_profile_item);
+
+        // Relays are referenced by name among the relays of the instance
+        for relay_id in relays_ids {
+            match app.nostr_service.get_relay(relay_id.trim()) {
+                Some(relay) => profile.relays.push(relay),
+                None => {
+                    return Err(Status::new(
+                        Code::InvalidArgument,
+                        format!("Relay {} not found", relay_id),
+                    ))
+                }
+            }
+        }
 
         app.nostr_service
             .profiles
+            .lock()
+            .await
             .insert(profile.id.clone(), profile);
 
         Ok(Response::new(grpc::AddProfileResponse {}))
@@ -81,13 +97,18 @@ impl ProfileRequestHandler {
 
     // Interface to delete a profile on instance
     pub async fn delete_profile(
-        mut app: MutexGuard<'_, App>,
+        app: MutexGuard<'_, App>,
         request: Request<DeleteProfileRequest>,
     ) -> Result<Response<DeleteProfileResponse>, Status> {
         let delete_profile_inner = request.into_inner();
         let save = delete_profile_inner.save();
         let profile_id = &delete_profile_inner.id;
-        let profile = app.nostr_service.profiles.remove(profile_id.trim());
+        let profile = app
+            .nostr_service
+            .profiles
+            .lock()
+            .await
+            .remove(profile_id.trim());
 
         if profile.is_none() {
             return Err(Status::new(Code::NotFound, "No profile with that id found"));
@@ -155,6 +176,36 @@ mod tests {
         assert_eq!(response.profiles.len(), 3);
     }
 
+    #[tokio::test]
+    async fn add_profile_with_unknown_relay_test() {
+        let app = Arc::new(Mutex::new(mock_app().await));
+
+        let add_profile_request = AddProfileRequest {
+            profile: NewProfileItem {
+                id: "added".to_string(),
+                private_key: "abcdef0123456789abcdef0123456789abcdef0123456789abcdef0123456789"
+                    .to_string(),
+                relays: ["unknown".to_string()].to_vec(),
+                ..Default::default()
+            },
+            save: Some(false),
+        };
+
+        let result =
+            ProfileRequestHandler::add_profile(app.lock().await, Request::new(add_profile_request))
+                .await;
+
+        assert_eq!(result.unwrap_err().code(), Code::InvalidArgument);
+        assert!(!app
+            .lock()
+            .await
+            .nostr_service
+            .profiles
+            .lock()
+            .await
+            .contains_key("added"));
+    }
+
     #[tokio::test]
     async fn list_profiles_test() {
         let app = Arc::new(Mutex::new(mock_app().await));
@@ -0,0 +1,356 @@
+use nostrss_grpc::grpc::{
+    self, ListOutboxRequest, ListOutboxResponse, ListPublicationsRequest, ListPublicationsResponse,
+    OutboxDelivery, OutboxItem, PublicationInfoRequest, PublicationInfoResponse, PublicationItem,
+};
+use tokio::sync::MutexGuard;
+use tonic::{Code, Request, Response, Status};
+
+use crate::{
+    app::app::App,
+    store::{
+        ledger::Publication,
+        outbox::{Delivery, OutboxEvent},
+    },
+};
+
+impl From<Publication> for PublicationItem {
+    fn from(value: Publication) -> Self {
+        Self {
+            feed_id: value.feed_id,
+            entry_id: value.entry_id,
+            profile_id: value.profile_id,
+            event_id: value.event_id,
+            kind: value.kind,
+            targets: value.targets,
+            relays: value.relays,
+            timestamp: value.timestamp,
+            status: value.status.to_string(),
+        }
+    }
+}
+
+impl From<Delivery> for OutboxDelivery {
+    fn from(value: Delivery) -> Self {
+        Self {
+            relay: value.relay,
+            acked: value.acked,
+            attempts: value.attempts,
+            next_attempt: value.next_attempt,
+            last_error: value.last_error,
+        }
+    }
+}
+
+impl From<OutboxEvent> for OutboxItem {
+    fn from(value: OutboxEvent) -> Self {
+        Self {
+            feed_id: value.feed_id,
+            entry_id: value.entry_id,
+            profile_id: value.profile_id,
+            event_id: value.event_id,
+            status: value.status.to_string(),
+            required_acks: value.required_acks as u64,
+            deliveries: value
+                .deliveries
+                .into_iter()
+                .map(OutboxDelivery::from)
+                .collect(),
+            created_at: value.created_at,
+        }
+    }
+}
+
+pub struct PublicationRequestHandler {}
+
+impl PublicationRequestHandler {
+    // Interface to retrieve the publication ledger, newest first.
+    // Lists the publications of every feed if no feed is provided.
+    pub async fn list_publications(
+        app: MutexGuard<'_, App>,
+        request: Request<ListPublicationsRequest>,
+    ) -> Result<Response<ListPublicationsResponse>, Status> {
+        let data = request.into_inner();
+
+        let feed_ids: Vec<String> = match &data.feed_id {
+            Some(feed_id) => {
+                let feed_id = feed_id.trim();
+                if !app.rss.feeds.iter().any(|f| f.id == feed_id) {
+                    return Err(Status::new(
+                        Code::NotFound,
+                        "No feed found with provided id",
+                    ));
+                }
+                [feed_id.to_string()].to_vec()
+            }
+            None => app.rss.feeds.iter().map(|f| f.id.clone()).collect(),
+        };
+
+        let store = app.seen_store.lock().await;
+        let mut publications: Vec<Publication> = feed_ids
+            .iter()
+            .flat_map(|feed_id| store.get_publications(feed_id))
+            .collect();
+
+        publications.sort_by_key(|publication| std::cmp::Reverse(publication.timestamp));
+
+        if let Some(limit) = data.limit {
+            publications.truncate(limit as usize);
+        }
+
+        Ok(Response::new(grpc::ListPublicationsResponse {
+            publications: publications
+                .into_iter()
+                .map(PublicationItem::from)
+                .collect(),
+        }))
+    }
+
+    // Interface to retrieve a single publication by its event id
+    pub async fn publication_info(
+        app: MutexGuard<'_, App>,
+        request: Request<PublicationInfoRequest>,
+    ) -> Result<Response<PublicationInfoResponse>, Status> {
+        let event_id = request.into_inner().event_id;
+
+        match app.seen_store.lock().await.get_publication(event_id.trim()) {
+            Some(publication) => Ok(Response::new(PublicationInfoResponse {
+                publication: PublicationItem::from(publication),
+            })),
+            None => Err(Status::new(Code::NotFound, "Publication not found")),
+        }
+    }
+
+    // Interface to retrieve the events waiting in the outbox, oldest first
+    pub async fn list_outbox(
+        app: MutexGuard<'_, App>,
+        request: Request<ListOutboxRequest>,
+    ) -> Result<Response<ListOutboxResponse>, Status> {
+        let data = request.into_inner();
+        let feed_id = data.feed_id.as_ref().map(|feed_id| feed_id.trim());
+        let status = data.status.as_ref().map(|status| status.trim());
+
+        if let Some(feed_id) = feed_id {
+            if !app.rss.feeds.iter().any(|f| f.id == feed_id) {
+                return Err(Status::new(
+                    Code::NotFound,
+                    "No feed found with provided id",
+                ));
+            }
+        }
+
+        if let Some(status) = status {
+            if !["pending", "failed"].contains(&status) {
+                return Err(Status::new(
+                    Code::InvalidArgument,
+                    "Invalid status. Expected pending or failed",
+                ));
+            }
+        }
+
+        let mut events: Vec<OutboxEvent> = app
+            .seen_store
+            .lock()
+            .await
+            .get_outbox()
+            .into_iter()
+            .filter(|event| feed_id.is_none_or(|feed_id| event.feed_id == feed_id))
+            .filter(|event| status.is_none_or(|status| event.status.to_string() == status))
+            .collect();
+
+        events.sort_by_key(|event| event.created_at);
+
+        Ok(Response::new(grpc::ListOutboxResponse {
+            events: events.into_iter().map(OutboxItem::from).collect(),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use std::sync::Arc;
+
+    use crate::{grpc::grpctest_utils::mock_app, store::ledger::PublicationStatus};
+    use tokio::sync::Mutex;
+
+    fn publication(feed_id: &str, event_id: &str, timestamp: i64) -> Publication {
+        Publication {
+            feed_id: feed_id.to_string(),
+            entry_id: "a".to_string(),
+            profile_id: "default".to_string(),
+            event_id: event_id.to_string(),
+            kind: 1,
+            targets: ["wss://relay.example.com".to_string()].to_vec(),
+            relays: Vec::new(),
+            timestamp,
+            status: PublicationStatus::Failed,
+        }
+    }
+
+    #[tokio::test]
+    async fn list_publications_test() {
+        let app = Arc::new(Mutex::new(mock_app().await));
+
+        {
+            let app_lock = app.lock().await;
+            let mut store = app_lock.seen_store.lock().await;
+            store.add_publications("stackernews", [publication("stackernews", "1", 1)].to_vec());
+            store.add_publications("stackernews", [publication("stackernews", "3", 3)].to_vec());
+            store.add_publications(
+                "bitcoin-reddit",
+                [publication("bitcoin-reddit", "2", 2)].to_vec(),
+            );
+        }
+
+        let list_request = |feed_id: Option<&str>, limit: Option<u64>| {
+            Request::new(ListPublicationsRequest {
+                feed_id: feed_id.map(String::from),
+                limit,
+            })
+        };
+
+        let publications = PublicationRequestHandler::list_publications(
+            app.lock().await,
+            list_request(None, None),
+        )
+        .await
+        .unwrap()
+        .into_inner()
+        .publications;
+
+        let event_ids: Vec<&str> = publications.iter().map(|p| p.event_id.as_str()).collect();
+        assert_eq!(event_ids, ["3", "2", "1"].to_vec());
+        assert_eq!(publications[0].status, "failed");
+
+        let publications = PublicationRequestHandler::list_publications(
+            app.lock().await,
+            list_request(Some("stackernews"), Some(1)),
+        )
+        .await
+        .unwrap()
+        .into_inner()
+        .publications;
+
+        assert_eq!(publications.len(), 1);
+        assert_eq!(publications[0].event_id, "3");
+
+        let result = PublicationRequestHandler::list_publications(
+            app.lock().await,
+            list_request(Some("unknown"), None),
+        )
+        .await;
+        assert_eq!(result.unwrap_err().code(), Code::NotFound);
+    }
+
+    #[tokio::test]
+    async fn publication_info_test() {
+        let app = Arc::new(Mutex::new(mock_app().await));
+
+        {
+            let app_lock = app.lock().await;
+            let mut store = app_lock.seen_store.lock().await;
+            store.add_publications("stackernews", [publication("stackernews", "1", 1)].to_vec());
+        }
+
+        let info_request = |event_id: &str| {
+            Request::new(PublicationInfoRequest {
+                event_id: event_id.to_string(),
+            })
+        };
+
+        let result =
+            PublicationRequestHandler::publication_info(app.lock().await, info_request("1")).await;
+        let publication = result.unwrap().into_inner().publication;
+        assert_eq!(publication.feed_id, "stackernews");
+        assert_eq!(
+            publication.targets,
+            ["wss://relay.example.com".to_string()].to_vec()
+        );
+
+        let result =
+            PublicationRequestHandler::publication_info(app.lock().await, info_request("2")).await;
+        assert_eq!(result.unwrap_err().code(), Code::NotFound);
+    }
+
+    #[tokio::test]
+    async fn list_outbox_test() {
+        let app = Arc::new(Mutex::new(mock_app().await));
+        let targets = ["wss://relay.example.com".to_string()].to_vec();
+
+        {
+            let app_lock = app.lock().await;
+            let mut store = app_lock.seen_store.lock().await;
+
+            let pending = OutboxEvent::new(
+                "stackernews",
+                "a",
+                "default",
+                "1",
+                String::new(),
+                &targets,
+                &[],
+                2,
+            )
+            .unwrap();
+            let mut failed = OutboxEvent::new(
+                "bitcoin-reddit",
+                "b",
+                "default",
+                "2",
+                String::new(),
+                &targets,
+                &[],
+                1,
+            )
+            .unwrap();
+            failed.status = crate::store::outbox::OutboxStatus::Failed;
+
+            store.add_outbox_events("stackernews", [pending].to_vec());
+            store.add_outbox_events("bitcoin-reddit", [failed].to_vec());
+        }
+
+        let list_request = |feed_id: Option<&str>, status: Option<&str>| {
+            Request::new(ListOutboxRequest {
+                feed_id: feed_id.map(String::from),
+                status: status.map(String::from),
+            })
+        };
+
+        let events =
+            PublicationRequestHandler::list_outbox(app.lock().await, list_request(None, None))
+                .await
+                .unwrap()
+                .into_inner()
+                .events;
+
+        let event_ids: Vec<&str> = events.iter().map(|e| e.event_id.as_str()).collect();
+        assert_eq!(event_ids, ["2", "1"].to_vec());
+        assert_eq!(events[1].deliveries[0].attempts, 1);
+
+        let events = PublicationRequestHandler::list_outbox(
+            app.lock().await,
+            list_request(None, Some("pending")),
+        )
+        .await
+        .unwrap()
+        .into_inner()
+        .events;
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].feed_id, "stackernews");
+
+        let result = PublicationRequestHandler::list_outbox(
+            app.lock().await,
+            list_request(Some("unknown"), None),
+        )
+        .await;
+        assert_eq!(result.unwrap_err().code(), Code::NotFound);
+
+        let result = PublicationRequestHandler::list_outbox(
+            app.lock().await,
+            list_request(None, Some("unknown")),
+        )
+        .await;
+        assert_eq!(result.unwrap_err().code(), Code::InvalidArgument);
+    }
+}
@@ -1 +1,377 @@
+use std::{net::SocketAddr, str::FromStr};
 
+use nostrss_grpc::grpc::{
+    self, AddRelayRequest, AddRelayResponse, DeleteRelayRequest, DeleteRelayResponse,
+    RelayInfoRequest, RelayInfoResponse, RelayItem, RelaysListRequest, RelaysListResponse,
+};
+use tokio::sync::MutexGuard;
+use tonic::{Code, Request, Response, Status};
+
+use crate::{
+    app::app::App,
+    nostr::{relay::Relay, service::NostrServiceError},
+};
+
+impl From<Relay> for RelayItem {
+    fn from(value: Relay) -> Self {
+        Self {
+            name: value.name,
+            target: value.target,
+            active: value.active,
+            proxy: value.proxy.map(|proxy| proxy.to_string()),
+            pow_level: value.pow_level as u64,
+            status: None,
+        }
+    }
+}
+
+impl TryFrom<RelayItem> for Relay {
+    type Error = &'static str;
+
+    fn try_from(value: RelayItem) -> Result<Self, Self::Error> {
+        if nostr_sdk::Url::from_str(value.target.as_str()).is_err() {
+            return Err("Invalid relay url");
+        }
+
+        let proxy = match value.proxy {
+            Some(proxy) if !proxy.trim().is_empty() => match SocketAddr::from_str(proxy.trim()) {
+                Ok(proxy) => Some(proxy),
+                Err(_) => return Err("Invalid relay proxy address"),
+            },
+            _ => None,
+        };
+
+        let pow_level = match u8::try_from(value.pow_level) {
+            Ok(pow_level) => pow_level,
+            Err(_) => return Err("Invalid relay pow level"),
+        };
+
+        Ok(Self {
+            name: value.name,
+            target: value.target,
+            active: value.active,
+            proxy,
+            pow_level,
+        })
+    }
+}
+
+pub struct RelayRequestHandler {}
+
+impl RelayRequestHandler {
+    // Interface to retrieve the list of default relays on instance
+    // along with their connection status
+    pub async fn relays_list(
+        app: MutexGuard<'_, App>,
+        _: Request<RelaysListRequest>,
+    ) -> Result<Response<RelaysListResponse>, Status> {
+        let mut relays = Vec::new();
+
+        for relay in app.nostr_service.get_relays() {
+            let status = app.nostr_service.relay_status(&relay).await;
+            let mut relay_item = RelayItem::from(relay);
+            relay_item.status = status;
+            relays.push(relay_item);
+        }
+
+        Ok(Response::new(grpc::RelaysListResponse { relays }))
+    }
+
+    // Interface to retrieve the detailed configuration of a single relay on instance
+    pub async fn relay_info(
+        app: MutexGuard<'_, App>,
+        request: Request<RelayInfoRequest>,
+    ) -> Result<Response<RelayInfoResponse>, Status> {
+        let name = request.into_inner().name;
+
+        match app.nostr_service.get_relay(name.trim()) {
+            Some(relay) => {
+                let status = app.nostr_service.relay_status(&relay).await;
+                let mut relay_item = RelayItem::from(relay);
+                relay_item.status = status;
+
+                Ok(Response::new(RelayInfoResponse { relay: relay_item }))
+            }
+            None => Err(Status::new(Code::NotFound, "Relay not found")),
+        }
+    }
+
+    // Interface to add a relay on instance.
+    // The relay is added to the default profile, shared with the running jobs,
+    // so it is used for their next publications.
+    pub async fn add_relay(
+        mut app: MutexGuard<'_, App>,
+        request: Request<AddRelayRequest>,
+    ) -> Result<Response<AddRelayResponse>, Status> {
+        let add_relay_inner = request.into_inner();
+        let save = add_relay_inner.save();
+
+        let relay = match Relay::try_from(add_relay_inner.relay) {
+            Ok(relay) => relay,
+            Err(e) => return Err(Status::new(Code::InvalidArgument, e)),
+        };
+
+        match app.nostr_service.add_relay(relay).await {
+            Ok(_) => {}
+            Err(NostrServiceError::RelayAlreadyExistsError) => {
+                return Err(Status::new(
+                    Code::AlreadyExists,
+                    "A relay with that name or url already exists",
+                ))
+            }
+            Err(_) => return Err(Status::new(Code::InvalidArgument, "Invalid relay")),
+        };
+
+        if save {
+            _ = &app.update_relays_config().await;
+        }
+
+        Ok(Response::new(grpc::AddRelayResponse {}))
+    }
+
+    // Interface to delete a relay on instance.
+    // The relay is removed from every profile so the running jobs stop publishing to it.
+    pub async fn delete_relay(
+        mut app: MutexGuard<'_, App>,
+        request: Request<DeleteRelayRequest>,
+    ) -> Result<Response<DeleteRelayResponse>, Status> {
+        let delete_relay_inner = request.into_inner();
+        let save = delete_relay_inner.save();
+
+        match app
+            .nostr_service
+            .remove_relay(delete_relay_inner.name.trim())
+            .await
+        {
+            Ok(_) => {}
+            Err(NostrServiceError::LastProfileRelayError(profile_id)) => {
+                return Err(Status::new(
+                    Code::FailedPrecondition,
+                    format!(
+                        "Relay is the only relay of profile {}. Add another relay to the profile first",
+                        profile_id
+                    ),
+                ));
+            }
+            Err(_) => {
+                return Err(Status::new(Code::NotFound, "No relay with that name found"));
+            }
+        }
+
+        if save {
+            _ = &app.update_relays_config().await;
+            _ = &app.update_profile_config().await;
+        }
+
+        Ok(Response::new(grpc::DeleteRelayResponse {}))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use std::sync::Arc;
+
+    use crate::grpc::grpctest_utils::mock_app;
+    use tokio::sync::Mutex;
+
+    fn relay_item(name: &str, target: &str) -> RelayItem {
+        RelayItem {
+            name: name.to_string(),
+            target: target.to_string(),
+            active: false,
+            proxy: None,
+            pow_level: 0,
+            status: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn add_and_delete_relay_test() {
+        let app = Arc::new(Mutex::new(mock_app().await));
+
+        let request = Request::new(AddRelayRequest {
+            relay: relay_item("test", "wss://relay.example.com"),
+            save: Some(false),
+        });
+
+        let add_relay_result = {
+            let app_lock = app.lock().await;
+            RelayRequestHandler::add_relay(app_lock, request).await
+        };
+
+        assert!(add_relay_result.is_ok());
+
+        let relays_list_result = {
+            let app_lock = app.lock().await;
+            RelayRequestHandler::relays_list(app_lock, Request::new(RelaysListRequest {})).await
+        };
+
+        let relays = relays_list_result.unwrap().into_inner().relays;
+        assert_eq!(relays.len(), 1);
+        assert_eq!(relays[0].name, "test");
+        assert!(relays[0].status.is_some());
+
+        let relay_info_result = {
+            let app_lock = app.lock().await;
+            RelayRequestHandler::relay_info(
+                app_lock,
+                Request::new(RelayInfoRequest {
+                    name: "test".to_string(),
+                }),
+            )
+            .await
+        };
+
+        let relay = relay_info_result.unwrap().into_inner().relay;
+        assert_eq!(relay.target, "wss://relay.example.com");
+        assert!(!relay.active);
+
+        // Same name must be rejected
+        let request = Request::new(AddRelayRequest {
+            relay: relay_item("test", "wss://other.example.com"),
+            save: Some(false),
+        });
+
+        let add_relay_result = {
+            let app_lock = app.lock().await;
+            RelayRequestHandler::add_relay(app_lock, request).await
+        };
+
+        assert_eq!(add_relay_result.unwrap_err().code(), Code::AlreadyExists);
+
+        let request = Request::new(AddRelayRequest {
+            relay: relay_item("other", "wss://other.example.com"),
+            save: Some(false),
+        });
+
+        let add_relay_result = {
+            let app_lock = app.lock().await;
+            RelayRequestHandler::add_relay(app_lock, request).await
+        };
+
+        assert!(add_relay_result.is_ok());
+
+        // The relay is shared with the jobs through the default profile
+        {
+            let app_lock = app.lock().await;
+            let mut profiles = app_lock.nostr_service.profiles.lock().await;
+            assert_eq!(profiles["default"].relays.len(), 2);
+
+            let relay = Relay::try_from(relay_item("test", "wss://relay.example.com")).unwrap();
+            profiles.get_mut("test").unwrap().relays.push(relay);
+        }
+
+        let delete_relay = |app: Arc<Mutex<App>>| async move {
+            let app_lock = app.lock().await;
+            RelayRequestHandler::delete_relay(
+                app_lock,
+                Request::new(DeleteRelayRequest {
+                    name: "test".to_string(),
+                    save: Some(false),
+                }),
+            )
+            .await
+        };
+
+        // The only relay of a profile can not be deleted
+        let delete_relay_result = delete_relay(Arc::clone(&app)).await;
+        assert_eq!(
+            delete_relay_result.unwrap_err().code(),
+            Code::FailedPrecondition
+        );
+
+        {
+            let app_lock = app.lock().await;
+            assert!(app_lock.nostr_service.get_relay("test").is_some());
+
+            let mut profiles = app_lock.nostr_service.profiles.lock().await;
+            assert_eq!(profiles["test"].relays.len(), 1);
+            assert_eq!(profiles["default"].relays.len(), 2);
+
+            let relay = Relay::try_from(relay_item("other", "wss://other.example.com")).unwrap();
+            profiles.get_mut("test").unwrap().relays.push(relay);
+        }
+
+        let delete_relay_result = delete_relay(Arc::clone(&app)).await;
+        assert!(delete_relay_result.is_ok());
+
+        let app_lock = app.lock().await;
+        assert_eq!(app_lock.nostr_service.get_relays().len(), 1);
+        assert!(app_lock.nostr_service.get_relay("test").is_none());
+
+        // The relay is removed from every profile
+        let profiles = app_lock.nostr_service.profiles.lock().await;
+        assert!(profiles.values().all(|profile| profile.relays.len() == 1
+            && profile.relays[0].target == "wss://other.example.com"));
+    }
+
+    #[tokio::test]
+    async fn add_invalid_relay_test() {
+        let app = Arc::new(Mutex::new(mock_app().await));
+
+        let mut item = relay_item("test", "wss://relay.example.com");
+        item.proxy = Some("not a proxy".to_string());
+
+        let add_relay_result = {
+            let app_lock = app.lock().await;
+            RelayRequestHandler::add_relay(
+                app_lock,
+                Request::new(AddRelayRequest {
+                    relay: item,
+                    save: Some(false),
+                }),
+            )
+            .await
+        };
+
+        assert_eq!(add_relay_result.unwrap_err().code(), Code::InvalidArgument);
+
+        let add_relay_result = {
+            let app_lock = app.lock().await;
+            RelayRequestHandler::add_relay(
+                app_lock,
+                Request::new(AddRelayRequest {
+                    relay: relay_item("test", "not an url"),
+                    save: Some(false),
+                }),
+            )
+            .await
+        };
+
+        assert_eq!(add_relay_result.unwrap_err().code(), Code::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn unknown_relay_test() {
+        let app = Arc::new(Mutex::new(mock_app().await));
+
+        let relay_info_result = {
+            let app_lock = app.lock().await;
+            RelayRequestHandler::relay_info(
+                app_lock,
+                Request::new(RelayInfoRequest {
+                    name: "unknown".to_string(),
+                }),
+            )
+            .await
+        };
+
+        assert_eq!(relay_info_result.unwrap_err().code(), Code::NotFound);
+
+        let delete_relay_result = {
+            let app_lock = app.lock().await;
+            RelayRequestHandler::delete_relay(
+                app_lock,
+                Request::new(DeleteRelayRequest {
+                    name: "unknown".to_string(),
+                    save: Some(false),
+                }),
+            )
+            .await
+        };
+
+        assert_eq!(delete_relay_result.unwrap_err().code(), Code::NotFound);
+    }
+}
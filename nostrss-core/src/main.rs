@@ -1,17 +1,19 @@
 // mod commands;
 mod app;
 mod grpc;
+mod metrics;
 mod nostr;
 mod profiles;
 mod rss;
 mod scheduler;
+mod store;
 mod template;
-use crate::app::app::{App, AppConfig};
-use crate::scheduler::scheduler::schedule;
+use crate::app::app::{App, AppCommand, AppConfig};
 use clap::Parser;
 use dotenv::dotenv;
 use grpc::grpc_service::NostrssServerService;
-use log::info;
+use log::{error, info};
+use metrics::metrics::{serve_metrics, Metrics};
 use nostr_sdk::Result;
 use nostrss_grpc::grpc::nostrss_grpc_server::NostrssGrpcServer;
 use std::env;
@@ -36,7 +38,26 @@ async fn main() -> Result<()> {
     env_logger::init();
 
     // Create app instance
-    let app = App::new(AppConfig::parse()).await;
+    let app = match App::new(AppConfig::parse()).await {
+        Ok(app) => app,
+        Err(e) => {
+            error!("{}", e);
+            std::process::exit(1);
+        }
+    };
+
+    // One-shot commands exit once done
+    if let Some(AppCommand::Publish { feed_file, feed }) = app.config.lock().await.command.clone() {
+        match app.publish_feed_file(&feed_file, feed).await {
+            Ok(report) => println!("Feed processed : {}", report),
+            Err(e) => {
+                error!("{}", e);
+                std::process::exit(1);
+            }
+        }
+
+        return Ok(());
+    }
 
     // Extract initial feeds list
     let feeds = app.rss.feeds.clone();
@@ -50,12 +71,13 @@ async fn main() -> Result<()> {
 
         let profiles_arc = global_app_lock.get_profiles().await;
 
-        let profiles_lock = profiles_arc.lock().await;
-        let update_flag = global_app_lock.config.update.unwrap_or(true);
+        // The profiles are locked again while each profile is updated
+        let profiles = profiles_arc.lock().await.clone();
+        let update_flag = global_app_lock.config.lock().await.update.unwrap_or(true);
 
         match update_flag {
             true => {
-                for profile in profiles_lock.clone() {
+                for profile in profiles {
                     match global_app_lock
                         .nostr_service
                         .update_profile(profile.0.clone())
@@ -82,39 +104,27 @@ async fn main() -> Result<()> {
     Build job for each feed.
     */
     for feed in feeds {
+        if feed.paused {
+            info!("Feed {:?} is paused. Skipping job creation", feed.name);
+            continue;
+        }
+
         // Lock the app mutex
         let mut app_lock = global_app_arc.lock().await;
 
-        // Local instance of feed
-        let f = feed.clone();
-
-        let client_arc = app_lock.nostr_service.get_client().await;
-
-        // Arc the map of feeds for use in the scheduled jobs
-        let maps = Arc::new(Mutex::new(app_lock.feeds_map.clone()));
-        let app_config_arc = app_lock.get_config().await;
-        // Extract cronjob rule
-        let scheduler_rule = f.schedule.as_str();
-        let profiles = app_lock.get_profiles().await;
-        // Call job builder
-        let job = schedule(
-            scheduler_rule,
-            feed,
-            maps,
-            client_arc,
-            profiles,
-            app_config_arc,
-        )
-        .await;
-        info!("Job id for feed {:?}: {:?}", f.name, job.guid());
-
-        // Load job reference in jobs map
-        _ = &app_lock.rss.feeds_jobs.insert(f.id, job.guid());
-
-        // Load job in scheduler
-        _ = &app_lock.rss.scheduler.add(job).await;
+        // Build job and load it in scheduler.
+        // Feeds loaded from the configuration are not validated beforehand.
+        if let Err(e) = app_lock.schedule_feed(&feed).await {
+            log::error!("Feed {} could not be scheduled : {}", feed.id, e);
+        }
     }
 
+    // Build the job retrying the events that did not reach enough relays
+    {
+        let mut app_lock = global_app_arc.lock().await;
+        _ = app_lock.schedule_outbox().await;
+    };
+
     // Start jobs.
     // We scope the instructions in a block to avoidd
     // locking the app arc on the whole instance as we
@@ -124,6 +134,11 @@ async fn main() -> Result<()> {
         _ = &app_lock.rss.scheduler.start().await;
     };
 
+    // Metrics listener, enabled when an address is provided
+    if let Some(address) = Metrics::address() {
+        tokio::spawn(serve_metrics(address));
+    }
+
     // GRPC server
     {
         let local_app = Arc::clone(&global_app_arc);
@@ -0,0 +1,170 @@
+use std::{env, sync::LazyLock};
+
+use axum::{routing::get, Router};
+use log::{error, info};
+use prometheus::{
+    exponential_buckets, Encoder, HistogramOpts, HistogramVec, IntCounterVec, Opts, Registry,
+    TextEncoder,
+};
+
+/// The metrics of the instance, exported in the prometheus text format.
+pub static METRICS: LazyLock<Metrics> = LazyLock::new(Metrics::new);
+
+pub struct Metrics {
+    registry: Registry,
+    // Fetches of remote feeds, by url and result
+    pub feed_fetches: IntCounterVec,
+    // Duration of the fetches of remote feeds, by url
+    pub feed_fetch_duration: HistogramVec,
+    // Remote feeds that could not be parsed, by url
+    pub parse_errors: IntCounterVec,
+    // Entries published, by feed and profile
+    pub entries_published: IntCounterVec,
+    // Events that could not be sent to a relay, by relay
+    pub relay_send_failures: IntCounterVec,
+    // Time spent mining events, by difficulty
+    pub pow_duration: HistogramVec,
+    // Delay between the scheduled run of a feed job and its actual run, by feed
+    pub scheduler_lag: HistogramVec,
+}
+
+impl Metrics {
+    fn new() -> Self {
+        let registry = Registry::new_custom(Some("nostrss".to_string()), None)
+            .expect("Invalid metrics registry");
+
+        let feed_fetches = IntCounterVec::new(
+            Opts::new("feed_fetches_total", "Fetches of remote feeds"),
+            &["feed_id", "result"],
+        )
+        .unwrap();
+        let feed_fetch_duration = HistogramVec::new(
+            HistogramOpts::new(
+                "feed_fetch_duration_seconds",
+                "Duration of the fetches of remote feeds",
+            ),
+            &["feed_id"],
+        )
+        .unwrap();
+        let parse_errors = IntCounterVec::new(
+            Opts::new(
+                "feed_parse_errors_total",
+                "Remote feeds that could not be parsed",
+            ),
+            &["feed_id"],
+        )
+        .unwrap();
+        let entries_published = IntCounterVec::new(
+            Opts::new("entries_published_total", "Entries published"),
+            &["feed_id", "profile_id"],
+        )
+        .unwrap();
+        let relay_send_failures = IntCounterVec::new(
+            Opts::new(
+                "relay_send_failures_total",
+                "Events that could not be sent to a relay",
+            ),
+            &["relay"],
+        )
+        .unwrap();
+        let pow_duration = HistogramVec::new(
+            HistogramOpts::new("pow_duration_seconds", "Time spent mining events")
+                .buckets(exponential_buckets(0.001, 4.0, 10).unwrap()),
+            &["difficulty"],
+        )
+        .unwrap();
+        let scheduler_lag = HistogramVec::new(
+            HistogramOpts::new(
+                "scheduler_lag_seconds",
+                "Delay between the scheduled run of a feed job and its actual run",
+            ),
+            &["feed_id"],
+        )
+        .unwrap();
+
+        for collector in [
+            Box::new(feed_fetches.clone()) as Box<dyn prometheus::core::Collector>,
+            Box::new(feed_fetch_duration.clone()),
+            Box::new(parse_errors.clone()),
+            Box::new(entries_published.clone()),
+            Box::new(relay_send_failures.clone()),
+            Box::new(pow_duration.clone()),
+            Box::new(scheduler_lag.clone()),
+        ] {
+            registry.register(collector).unwrap();
+        }
+
+        Self {
+            registry,
+            feed_fetches,
+            feed_fetch_duration,
+            parse_errors,
+            entries_published,
+            relay_send_failures,
+            pow_duration,
+            scheduler_lag,
+        }
+    }
+
+    // Encodes the metrics in the prometheus text format
+    pub fn render(&self) -> String {
+        let mut buffer = Vec::new();
+
+        if let Err(e) = TextEncoder::new().encode(&self.registry.gather(), &mut buffer) {
+            error!("Error while encoding metrics : {}", e);
+        }
+
+        String::from_utf8(buffer).unwrap_or_default()
+    }
+
+    // The address of the metrics listener, provided by `METRICS_ADDRESS`.
+    // The listener is disabled if the variable is not set.
+    pub fn address() -> Option<String> {
+        match env::var("METRICS_ADDRESS") {
+            Ok(address) if !address.trim().is_empty() => Some(address.trim().to_string()),
+            _ => None,
+        }
+    }
+}
+
+/// Serves the metrics on the `/metrics` path of the provided address.
+pub async fn serve_metrics(address: String) {
+    let router = Router::new().route("/metrics", get(|| async { METRICS.render() }));
+
+    let listener = match tokio::net::TcpListener::bind(&address).await {
+        Ok(listener) => listener,
+        Err(e) => {
+            error!("Metrics listener could not bind {} : {}", address, e);
+            return;
+        }
+    };
+
+    info!("Serving metrics on {}", address);
+
+    if let Err(e) = axum::serve(listener, router).await {
+        error!("Metrics listener stopped : {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metrics_render() {
+        METRICS
+            .entries_published
+            .with_label_values(&["test", "default"])
+            .inc();
+        METRICS
+            .scheduler_lag
+            .with_label_values(&["test"])
+            .observe(0.5);
+
+        let rendered = METRICS.render();
+
+        assert!(rendered
+            .contains("nostrss_entries_published_total{feed_id=\"test\",profile_id=\"default\"}"));
+        assert!(rendered.contains("nostrss_scheduler_lag_seconds_count{feed_id=\"test\"} 1"));
+    }
+}
@@ -1,20 +1,27 @@
-use std::{collections::HashMap, fmt, sync::Arc};
+use std::{collections::HashMap, fmt, str::FromStr, sync::Arc};
 
-use log::debug;
-use nostr_sdk::{Client, EventBuilder, EventId, Keys, Metadata, Result};
+use log::{debug, error};
+use nostr_sdk::{prelude::RelayOptions, Client, EventBuilder, EventId, Keys, Metadata, Result};
 use reqwest::Url;
 use tokio::sync::Mutex;
 
 use crate::{
+    metrics::metrics::METRICS,
     nostr::NostrProfile,
     profiles::{config::Profile, profiles::ProfileHandler},
 };
 
 use super::relay::Relay;
 
+#[allow(clippy::enum_variant_names)]
 pub enum NostrServiceError {
     BroadcastError,
     ProfileNotFoundError,
+    RelayNotFoundError,
+    RelayAlreadyExistsError,
+    InvalidRelayError,
+    // The relay is the only relay of the profile
+    LastProfileRelayError(String),
 }
 
 impl fmt::Debug for NostrServiceError {
@@ -22,23 +29,31 @@ impl fmt::Debug for NostrServiceError {
         match self {
             NostrServiceError::BroadcastError => write!(f, "Broadcast error"),
             NostrServiceError::ProfileNotFoundError => write!(f, "Profile not found"),
+            NostrServiceError::RelayNotFoundError => write!(f, "Relay not found"),
+            NostrServiceError::RelayAlreadyExistsError => write!(f, "Relay already exists"),
+            NostrServiceError::InvalidRelayError => write!(f, "Invalid relay"),
+            NostrServiceError::LastProfileRelayError(profile_id) => {
+                write!(f, "Relay is the only relay of profile {}", profile_id)
+            }
         }
     }
 }
 
+/// The client and the profiles are shared with the feeds jobs,
+/// so the changes made at runtime apply to their next publications.
 #[derive(Debug, Clone)]
 pub struct NostrService {
-    pub client: Client,
+    pub client: Arc<Mutex<Client>>,
     pub default_relays: HashMap<Url, Relay>,
-    pub profiles: HashMap<String, Profile>,
+    pub profiles: Arc<Mutex<HashMap<String, Profile>>>,
 }
 
 impl Default for NostrService {
     fn default() -> Self {
         Self {
-            client: Client::new(&Keys::generate()),
+            client: Arc::new(Mutex::new(Client::new(&Keys::generate()))),
             default_relays: HashMap::new(),
-            profiles: HashMap::new(),
+            profiles: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 }
@@ -50,15 +65,15 @@ impl NostrService {
         let profiles = profile_handler.get_profiles();
         let default_relays = profile_handler.new_get_default_relays();
         Self {
-            client,
+            client: Arc::new(Mutex::new(client)),
             default_relays,
-            profiles,
+            profiles: Arc::new(Mutex::new(profiles)),
         }
     }
 
     pub async fn update_profile(&self, profile_id: String) -> Result<EventId, NostrServiceError> {
-        let profile = match self.profiles.get(&profile_id) {
-            Some(result) => result,
+        let profile = match self.profiles.lock().await.get(&profile_id) {
+            Some(result) => result.clone(),
             None => return Err(NostrServiceError::ProfileNotFoundError),
         };
 
@@ -104,10 +119,25 @@ impl NostrService {
             .to_event(&profile.get_keys())
             .unwrap();
 
-        // Broadcast metadata (NIP-01) to relays
-        let result = self.client.clone().send_event(event).await;
+        // Broadcast metadata (NIP-01) to the relays of the profile
+        let default_relays = self.get_relays();
+        let targets: Vec<String> = profile
+            .get_publishing_relays(&default_relays)
+            .into_iter()
+            .filter(|relay| relay.active)
+            .map(|relay| relay.target)
+            .collect();
+
+        let client = self.client.lock().await.clone();
+        let result = client.send_event_to(targets.clone(), event).await;
 
         if result.is_err() {
+            for target in &targets {
+                METRICS
+                    .relay_send_failures
+                    .with_label_values(&[target])
+                    .inc();
+            }
             return Err(NostrServiceError::BroadcastError);
         }
 
@@ -115,6 +145,109 @@ impl NostrService {
     }
 
     pub async fn get_client(&self) -> Arc<Mutex<Client>> {
-        Arc::new(Mutex::new(self.client.clone()))
+        Arc::clone(&self.client)
+    }
+
+    pub async fn get_profiles(&self) -> Arc<Mutex<HashMap<String, Profile>>> {
+        Arc::clone(&self.profiles)
+    }
+
+    // Retrieves the default relays, ordered by name
+    pub fn get_relays(&self) -> Vec<Relay> {
+        let mut relays: Vec<Relay> = self.default_relays.values().cloned().collect();
+        relays.sort_by(|a, b| a.name.cmp(&b.name));
+        relays
+    }
+
+    // Retrieves a default relay by its name
+    pub fn get_relay(&self, name: &str) -> Option<Relay> {
+        self.default_relays
+            .values()
+            .find(|relay| relay.name == name)
+            .cloned()
+    }
+
+    // Retrieves the connection status of a relay from the client pool
+    pub async fn relay_status(&self, relay: &Relay) -> Option<String> {
+        match self.client.lock().await.relay(relay.target.as_str()).await {
+            Ok(r) => Some(r.status().await.to_string()),
+            Err(_) => None,
+        }
+    }
+
+    // Adds a relay to the default relays.
+    // The relay is added to the client pool and to the relays of the default profile,
+    // which are shared with the jobs, so it is used for their next publications.
+    pub async fn add_relay(&mut self, relay: Relay) -> Result<(), NostrServiceError> {
+        let url = match Url::from_str(relay.target.as_str()) {
+            Ok(url) => url,
+            Err(_) => return Err(NostrServiceError::InvalidRelayError),
+        };
+
+        if self.default_relays.contains_key(&url) || self.get_relay(&relay.name).is_some() {
+            return Err(NostrServiceError::RelayAlreadyExistsError);
+        }
+
+        let opts = RelayOptions::new().proxy(relay.proxy);
+
+        let client = self.client.lock().await.clone();
+
+        if let Err(e) = client.add_relay_with_opts(url.as_str(), opts).await {
+            error!("Error adding relay {} : {}", relay.name, e);
+            return Err(NostrServiceError::InvalidRelayError);
+        }
+
+        if relay.active {
+            _ = client.connect_relay(url.as_str()).await;
+        }
+
+        if let Some(profile) = self.profiles.lock().await.get_mut("default") {
+            profile.relays.push(relay.clone());
+        }
+
+        self.default_relays.insert(url, relay);
+
+        Ok(())
+    }
+
+    // Removes a relay from the default relays and from the relays of every profile,
+    // so the running jobs stop publishing to it, then disconnects the client from it.
+    // The relay is not removed if it is the only relay of a profile, as the profile
+    // would then publish to the default relays.
+    pub async fn remove_relay(&mut self, name: &str) -> Result<Relay, NostrServiceError> {
+        let url = match self
+            .default_relays
+            .iter()
+            .find(|(_, relay)| relay.name == name)
+        {
+            Some((url, _)) => url.clone(),
+            None => return Err(NostrServiceError::RelayNotFoundError),
+        };
+
+        let is_relay =
+            |relay: &Relay| Url::from_str(relay.target.as_str()).ok() == Some(url.clone());
+
+        let mut profiles = self.profiles.lock().await;
+
+        if let Some(profile) = profiles
+            .values()
+            .find(|profile| !profile.relays.is_empty() && profile.relays.iter().all(is_relay))
+        {
+            return Err(NostrServiceError::LastProfileRelayError(profile.id.clone()));
+        }
+
+        for profile in profiles.values_mut() {
+            profile.relays.retain(|relay| !is_relay(relay));
+        }
+        drop(profiles);
+
+        if let Err(e) = self.client.lock().await.remove_relay(url.as_str()).await {
+            error!("Error removing relay {} : {}", name, e);
+        }
+
+        match self.default_relays.remove(&url) {
+            Some(relay) => Ok(relay),
+            None => Err(NostrServiceError::RelayNotFoundError),
+        }
     }
 }
@@ -373,6 +373,15 @@ impl Profile {
     pub fn set_relays_from_file(self, path: &str) -> Self {
         self.load_relays(path)
     }
+
+    // Retrieves the relays the profile publishes to.
+    // Profiles with no relays configured fall back to the default relays.
+    pub fn get_publishing_relays(&self, default_relays: &[Relay]) -> Vec<Relay> {
+        match self.relays.is_empty() {
+            true => default_relays.to_vec(),
+            false => self.relays.clone(),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -441,4 +450,33 @@ mod tests {
         profile = profile.set_relays(relays);
         assert_eq!(profile.description, Some("Ad lorem ipsum".to_string()));
     }
+
+    #[test]
+    fn test_get_publishing_relays() {
+        use super::Relay;
+
+        from_filename(".env.test").ok();
+
+        let relay = |name: &str| Relay {
+            name: name.to_string(),
+            target: format!("wss://{}.localhost", name),
+            active: true,
+            proxy: None,
+            pow_level: 0,
+        };
+
+        let default_relays = vec![relay("default")];
+
+        let profile = super::Profile::new("abcde".to_string(), None);
+        assert_eq!(
+            profile.get_publishing_relays(&default_relays),
+            default_relays
+        );
+
+        let profile = profile.set_relays(vec![relay("own")]);
+        assert_eq!(
+            profile.get_publishing_relays(&default_relays),
+            vec![relay("own")]
+        );
+    }
 }
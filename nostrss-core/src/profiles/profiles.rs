@@ -96,6 +96,62 @@ impl ProfileHandler {
         }
     }
 
+    pub fn save_relays(self, path: &str, relays: Vec<&Relay>) -> bool {
+        let path = Path::new(path);
+
+        if path.is_file() {
+            match path.extension() {
+                Some(ext) => match ext.to_str() {
+                    Some("yml") => {
+                        return self.save_yaml_relays(path, relays);
+                    }
+                    Some("yaml") => {
+                        return self.save_yaml_relays(path, relays);
+                    }
+                    Some("json") => {
+                        return self.save_json_relays(path, relays);
+                    }
+                    _ => {
+                        return false;
+                    }
+                },
+                None => {
+                    return false;
+                }
+            }
+        }
+
+        false
+    }
+
+    pub fn save_json_relays(self, path: &Path, relays: Vec<&Relay>) -> bool {
+        let file = File::create(path).unwrap();
+        let writer = std::io::BufWriter::new(file);
+        let result = serde_json::to_writer_pretty(writer, &relays);
+
+        match result {
+            Ok(_) => true,
+            Err(e) => {
+                error!("{}", e);
+                false
+            }
+        }
+    }
+
+    pub fn save_yaml_relays(self, path: &Path, relays: Vec<&Relay>) -> bool {
+        let file = File::create(path).unwrap();
+        let writer = std::io::BufWriter::new(file);
+        let result = serde_yaml::to_writer(writer, &relays);
+
+        match result {
+            Ok(_) => true,
+            Err(e) => {
+                error!("{}", e);
+                false
+            }
+        }
+    }
+
     pub fn load_profiles(self, path: &str) -> Self {
         let path = Path::new(path);
 
@@ -1,10 +1,12 @@
 #![allow(dead_code)]
 
-use log::{error, info};
+use log::{error, info, warn};
 use serde::{Deserialize, Serialize};
 use std::{
+    collections::BTreeMap,
     env::{self, VarError},
     fs::File,
+    net::SocketAddr,
     path::Path,
     str::FromStr,
 };
@@ -16,6 +18,197 @@ pub enum RssConfigErrors {
     ParsingError,
 }
 
+/// The policy applied on startup to the entries of a feed
+/// that are not known yet from the seen entries store.
+///
+/// Entries dates are read from the `updated` field of the entry
+/// or from its `published` field as fallback.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(tag = "policy", rename_all = "snake_case")]
+pub enum CatchUp {
+    // Every entry present on startup is marked as seen
+    None,
+    // Publishes the entries dated after the last processing of the feed
+    #[default]
+    SinceLastRun,
+    // Publishes at most `count` of the most recent entries
+    MaxEntries {
+        count: usize,
+    },
+    // Publishes the entries dated within the last `seconds`
+    MaxAge {
+        seconds: u64,
+    },
+}
+
+/// The kind of nostr event the entries of a feed are published as.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum FeedKind {
+    // Short text note (NIP-01) rendered from the feed template
+    #[default]
+    Note,
+    // Long-form content (NIP-23) built from the full content of the entry
+    Article,
+}
+
+/// The policy applied when an already published entry is edited by its publisher.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum OnUpdate {
+    // Edits are not published
+    #[default]
+    Ignore,
+    // Publishes a note replying to the original event with the updated content
+    Correction,
+    // Publishes the updated entry so it replaces the original event.
+    // Only effective for replaceable kinds, e.g: articles.
+    Replace,
+    // Deletes the original event (NIP-09) and publishes the updated entry
+    DeleteAndRepost,
+}
+
+/// A secret value of the config. Secrets should be provided through
+/// an environment variable, e.g: `{ env: FEED_TOKEN }`, rather than
+/// stored in the config file.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum Secret {
+    // The name of the environment variable providing the value
+    Env { env: String },
+    Value(String),
+}
+
+impl Secret {
+    // Retrieves the value of the secret
+    pub fn resolve(&self) -> Result<String, String> {
+        match self {
+            Secret::Env { env } => {
+                env::var(env).map_err(|_| format!("Environment variable {} is not set", env))
+            }
+            Secret::Value(value) => Ok(value.clone()),
+        }
+    }
+
+    // Describes the secret without revealing its value
+    pub fn masked(&self) -> String {
+        match self {
+            Secret::Env { env } => format!("env:{}", env),
+            Secret::Value(_) => "********".to_string(),
+        }
+    }
+}
+
+/// The authentication used to fetch a feed.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum FeedAuth {
+    // HTTP basic authentication
+    Basic {
+        username: String,
+        password: Option<Secret>,
+    },
+    // Bearer token sent in the `Authorization` header
+    Bearer {
+        token: Secret,
+    },
+}
+
+impl FeedAuth {
+    // Describes the authentication without revealing its secrets
+    pub fn masked(&self) -> String {
+        match self {
+            FeedAuth::Basic { username, password } => match password {
+                Some(password) => format!("basic {}:{}", username, password.masked()),
+                None => format!("basic {}", username),
+            },
+            FeedAuth::Bearer { token } => format!("bearer {}", token.masked()),
+        }
+    }
+}
+
+/// The TLS settings used to fetch a feed.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct TlsOptions {
+    // Accepts invalid certificates, e.g: self-signed ones
+    #[serde(default)]
+    pub accept_invalid_certs: bool,
+    // Path to an additional root certificate, in PEM format
+    pub ca_certificate: Option<String>,
+}
+
+/// The HTTP settings used to fetch a feed. Unset values fall back
+/// to the settings of the shared HTTP client.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct FetchOptions {
+    // Timeout of the request, in seconds
+    pub timeout: Option<u64>,
+    pub user_agent: Option<String>,
+    // Maximum size of the response body, in bytes
+    pub max_body_size: Option<u64>,
+    // Deprecated alias of the `proxy` of the feed, moved to it when the feeds are loaded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
+    pub tls: Option<TlsOptions>,
+}
+
+impl FetchOptions {
+    // Timeout of the requests, in seconds. Provided by `FETCH_TIMEOUT`, defaults to 30.
+    pub fn default_timeout() -> u64 {
+        env::var("FETCH_TIMEOUT")
+            .unwrap_or("30".to_string())
+            .parse::<u64>()
+            .unwrap_or(30)
+    }
+
+    // User agent of the requests. Provided by `FETCH_USER_AGENT`.
+    pub fn default_user_agent() -> String {
+        env::var("FETCH_USER_AGENT").unwrap_or(format!("nostrss/{}", env!("CARGO_PKG_VERSION")))
+    }
+
+    // Maximum size of the response bodies, in bytes.
+    // Provided by `FETCH_MAX_BODY_SIZE`, defaults to 10MB.
+    pub fn default_max_body_size() -> u64 {
+        env::var("FETCH_MAX_BODY_SIZE")
+            .unwrap_or("10485760".to_string())
+            .parse::<u64>()
+            .unwrap_or(10485760)
+    }
+
+    // Maximum number of redirects followed. Provided by `FETCH_MAX_REDIRECTS`, defaults to 10.
+    pub fn default_max_redirects() -> usize {
+        env::var("FETCH_MAX_REDIRECTS")
+            .unwrap_or("10".to_string())
+            .parse::<usize>()
+            .unwrap_or(10)
+    }
+
+    // Proxy of the feeds that have no proxy of their own. Provided by `FETCH_PROXY`,
+    // either as an url or as the address of a SOCKS5 proxy.
+    // This is the only setting that accepts an url.
+    pub fn default_proxy() -> Option<String> {
+        match env::var("FETCH_PROXY") {
+            Ok(proxy) if !proxy.trim().is_empty() => Some(Self::proxy_url(&proxy)),
+            _ => None,
+        }
+    }
+
+    // Converts a proxy setting to an url. Bare addresses are SOCKS5 proxies that
+    // resolve host names themselves, so `.onion` feeds can be fetched through Tor.
+    pub fn proxy_url(proxy: &str) -> String {
+        match SocketAddr::from_str(proxy.trim()) {
+            Ok(address) => format!("socks5h://{}", address),
+            Err(_) => proxy.trim().to_string(),
+        }
+    }
+
+    // Tells if the feed needs a dedicated HTTP client,
+    // as TLS settings are set at the client level
+    pub fn needs_dedicated_client(&self) -> bool {
+        self.tls.is_some()
+    }
+}
+
 /// The [`Feed`] struct represents a feed as provided through
 /// external file, be it either a `json` or a `yaml` file.
 ///
@@ -40,6 +233,44 @@ pub struct Feed {
     pub cache_size: Option<usize>,
     #[serde(default = "Feed::default_pow_level")]
     pub pow_level: u8,
+    // The policy for entries published while the program was not running
+    #[serde(default)]
+    pub catch_up: CatchUp,
+    // A paused feed is not scheduled
+    #[serde(default)]
+    pub paused: bool,
+    // A feed in shadow mode is processed as usual but its events are not sent.
+    // They are recorded into the ledger and, if set, written to the shadow file
+    #[serde(default)]
+    pub shadow: bool,
+    // The file the events of the feed are appended to while in shadow mode
+    #[serde(default)]
+    pub shadow_file: Option<String>,
+    // Runs the feed when its local file changes, instead of on the ticks of its schedule
+    #[serde(default)]
+    pub watch: bool,
+    // The kind of event published for the entries of the feed
+    #[serde(default)]
+    pub kind: FeedKind,
+    // The policy applied when a published entry is edited
+    #[serde(default)]
+    pub on_update: OnUpdate,
+    // Deletes the published events of the entries retracted from the feed
+    #[serde(default)]
+    pub mirror_deletions: bool,
+    // The url of the proxy the feed is fetched through, e.g: `socks5h://127.0.0.1:9050`
+    // or `http://127.0.0.1:3128`. Bare addresses are SOCKS5 proxies, as for relays
+    #[serde(default)]
+    pub proxy: Option<String>,
+    // Overrides of the HTTP settings used to fetch the feed
+    #[serde(default)]
+    pub fetch: Option<FetchOptions>,
+    // The authentication used to fetch the feed
+    #[serde(default)]
+    pub auth: Option<FeedAuth>,
+    // Additional headers sent when fetching the feed
+    #[serde(default)]
+    pub headers: Option<BTreeMap<String, Secret>>,
 }
 
 impl Feed {
@@ -64,6 +295,29 @@ impl Feed {
         profiles.contains(&id)
     }
 
+    // Moves the deprecated `fetch.proxy` setting to the proxy of the feed, unless
+    // the feed has another proxy. The value is moved as is and checked when the feed is fetched.
+    fn migrate_fetch_proxy(mut self) -> Self {
+        let Some(proxy) = self.fetch.as_mut().and_then(|fetch| fetch.proxy.take()) else {
+            return self;
+        };
+
+        warn!(
+            "fetch.proxy of feed {} is deprecated. Use the proxy of the feed instead.",
+            self.id
+        );
+
+        match &self.proxy {
+            Some(feed_proxy) if feed_proxy.trim() != proxy.trim() => warn!(
+                "fetch.proxy of feed {} is ignored in favor of the proxy of the feed.",
+                self.id
+            ),
+            _ => self.proxy = Some(proxy),
+        }
+
+        self
+    }
+
     // sets the tags for the feed
     fn set_tags(mut self, tags: Vec<String>) -> Self {
         self.tags = Some(tags);
@@ -101,6 +355,18 @@ impl Default for Feed {
             template: None,
             cache_size: Self::default_cache_size(),
             pow_level: 0,
+            catch_up: CatchUp::default(),
+            paused: false,
+            shadow: false,
+            shadow_file: None,
+            watch: false,
+            kind: FeedKind::default(),
+            on_update: OnUpdate::default(),
+            mirror_deletions: false,
+            proxy: None,
+            fetch: None,
+            auth: None,
+            headers: None,
         }
     }
 }
@@ -171,7 +437,7 @@ impl RssConfig {
             }
         };
 
-        self.feeds = feeds;
+        self.feeds = feeds.into_iter().map(Feed::migrate_fetch_proxy).collect();
         self
     }
 
@@ -192,7 +458,7 @@ impl RssConfig {
             }
         };
 
-        self.feeds = feeds;
+        self.feeds = feeds.into_iter().map(Feed::migrate_fetch_proxy).collect();
         self
     }
 
@@ -285,6 +551,94 @@ pub mod tests {
         assert_eq!(config.feeds.len(), 3);
     }
 
+    #[test]
+    fn test_proxy_url() {
+        assert_eq!(
+            FetchOptions::proxy_url("127.0.0.1:9050"),
+            "socks5h://127.0.0.1:9050"
+        );
+        assert_eq!(
+            FetchOptions::proxy_url("http://127.0.0.1:3128"),
+            "http://127.0.0.1:3128"
+        );
+    }
+
+    #[test]
+    fn test_migrate_fetch_proxy() {
+        let yaml = r#"
+            id: onion
+            name: Onion feed
+            url: http://example.onion/feed
+            schedule: "0 * * * * *"
+            fetch:
+              proxy: 127.0.0.1:9050
+        "#;
+
+        let feed: Feed = serde_yaml::from_str(yaml).unwrap();
+        let feed = feed.migrate_fetch_proxy();
+
+        assert_eq!(feed.proxy, Some("127.0.0.1:9050".to_string()));
+        assert_eq!(feed.fetch.unwrap().proxy, None);
+
+        // Proxy urls are moved as is
+        let feed = Feed {
+            fetch: Some(FetchOptions {
+                proxy: Some("http://127.0.0.1:3128".to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+        .migrate_fetch_proxy();
+
+        assert_eq!(feed.proxy, Some("http://127.0.0.1:3128".to_string()));
+        assert_eq!(feed.fetch.unwrap().proxy, None);
+
+        // The proxy of the feed takes precedence
+        let feed = Feed {
+            proxy: Some("socks5h://127.0.0.1:9050".to_string()),
+            fetch: Some(FetchOptions {
+                proxy: Some("http://127.0.0.1:3128".to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+        .migrate_fetch_proxy();
+
+        assert_eq!(feed.proxy, Some("socks5h://127.0.0.1:9050".to_string()));
+        assert_eq!(feed.fetch.unwrap().proxy, None);
+    }
+
+    #[test]
+    fn test_feed_auth() {
+        let yaml = r#"
+            id: private
+            name: Private feed
+            url: https://example.com/feed
+            schedule: "0/10 * * * * *"
+            auth:
+              type: basic
+              username: bot
+              password:
+                env: FEED_PASSWORD
+            headers:
+              X-Api-Key: plain
+        "#;
+
+        let feed: Feed = serde_yaml::from_str(yaml).unwrap();
+
+        assert_eq!(
+            feed.auth,
+            Some(FeedAuth::Basic {
+                username: "bot".to_string(),
+                password: Some(Secret::Env {
+                    env: "FEED_PASSWORD".to_string()
+                }),
+            })
+        );
+        assert_eq!(feed.auth.unwrap().masked(), "basic bot:env:FEED_PASSWORD");
+        assert_eq!(feed.headers.unwrap()["X-Api-Key"].masked(), "********");
+    }
+
     #[test]
     fn test_load_feeds_invalid_path() {
         let path = Some("invalid_path.json".to_string());
@@ -0,0 +1,212 @@
+use std::{collections::HashMap, sync::LazyLock};
+
+use log::info;
+use regex::Regex;
+use url::Url;
+
+use super::parser::{RssParser, RssParserError};
+
+// Media types of the feeds referenced by web pages
+const FEED_TYPES: [&str; 3] = [
+    "application/rss+xml",
+    "application/atom+xml",
+    "application/feed+json",
+];
+
+static LINK_TAG: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)<link\b[^>]*>").unwrap());
+
+static ATTRIBUTE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"([a-zA-Z_:-]+)\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s"'>]+))"#).unwrap()
+});
+
+/// A feed found at an url.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiscoveredFeed {
+    pub url: String,
+    pub title: Option<String>,
+    // The media type advertised by the web page, e.g: `application/rss+xml`
+    pub content_type: Option<String>,
+}
+
+/// Finds the feeds available at an url. The url is either the url of a feed, or the
+/// url of a web page referencing its feeds through `<link rel="alternate">` tags.
+/// Only the candidates that can be parsed as feeds are returned.
+pub async fn discover_feeds(
+    parser: &RssParser,
+    url: &str,
+) -> Result<Vec<DiscoveredFeed>, RssParserError> {
+    let (content, url) = parser.download(url).await?;
+
+    // The url is already the url of a feed
+    if let Ok(feed) = feed_rs::parser::parse(content.as_slice()) {
+        return Ok([DiscoveredFeed {
+            url,
+            title: feed.title.map(|title| title.content),
+            content_type: None,
+        }]
+        .to_vec());
+    }
+
+    let html = String::from_utf8_lossy(&content);
+    let mut feeds = Vec::new();
+
+    for candidate in find_candidates(&html, &url) {
+        match parser.read(candidate.url.clone()).await {
+            Ok(feed) => feeds.push(DiscoveredFeed {
+                title: candidate.title.or(feed.title.map(|title| title.content)),
+                ..candidate
+            }),
+            Err(e) => info!(
+                "Discarding feed candidate {} : {}",
+                candidate.url, e.message
+            ),
+        }
+    }
+
+    Ok(feeds)
+}
+
+// Extracts the feeds referenced by the `<link>` tags of a web page.
+// Relative urls are resolved against the url of the page.
+fn find_candidates(html: &str, base: &str) -> Vec<DiscoveredFeed> {
+    let base = Url::parse(base).ok();
+    let mut candidates: Vec<DiscoveredFeed> = Vec::new();
+
+    for tag in LINK_TAG.find_iter(html) {
+        let attributes: HashMap<String, String> = ATTRIBUTE
+            .captures_iter(tag.as_str())
+            .map(|captures| {
+                let value = captures
+                    .get(2)
+                    .or(captures.get(3))
+                    .or(captures.get(4))
+                    .map(|value| decode(value.as_str()))
+                    .unwrap_or_default();
+
+                (captures[1].to_lowercase(), value)
+            })
+            .collect();
+
+        let is_alternate = attributes.get("rel").is_some_and(|rel| {
+            rel.split_whitespace()
+                .any(|rel| rel.eq_ignore_ascii_case("alternate"))
+        });
+        let content_type = attributes.get("type").map(|t| t.trim().to_lowercase());
+        let is_feed = content_type
+            .as_ref()
+            .is_some_and(|t| FEED_TYPES.contains(&t.as_str()));
+
+        if !is_alternate || !is_feed {
+            continue;
+        }
+
+        let href = match attributes.get("href") {
+            Some(href) => href.trim(),
+            None => continue,
+        };
+
+        let url = match &base {
+            Some(base) => base.join(href),
+            None => Url::parse(href),
+        };
+
+        // Web pages can not reference local files
+        let url = match url {
+            Ok(url) if matches!(url.scheme(), "http" | "https") => url.to_string(),
+            _ => continue,
+        };
+
+        if candidates.iter().any(|candidate| candidate.url == url) {
+            continue;
+        }
+
+        candidates.push(DiscoveredFeed {
+            url,
+            title: attributes
+                .get("title")
+                .filter(|title| !title.trim().is_empty())
+                .cloned(),
+            content_type,
+        });
+    }
+
+    candidates
+}
+
+// Decodes the entities usually found in attribute values
+fn decode(value: &str) -> String {
+    value
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{routing::get, Router};
+
+    const PAGE: &str = r#"<html><head>
+        <link rel="stylesheet" href="/style.css">
+        <link rel="alternate" type="application/rss+xml" title="Posts" href="/feed">
+        <LINK REL='alternate' TYPE='application/atom+xml' HREF='/broken?a=1&amp;b=2'>
+        <link rel="alternate" type="application/rss+xml" href="/feed">
+        <link rel="alternate" type="text/html" hreflang="fr" href="/fr">
+        </head><body></body></html>"#;
+
+    const RSS: &str = r#"<?xml version="1.0"?><rss version="2.0"><channel><title>Blog</title>
+        <item><guid>1</guid><title>Entry</title></item></channel></rss>"#;
+
+    #[test]
+    fn test_find_candidates() {
+        let candidates = find_candidates(PAGE, "https://example.com/blog/");
+
+        assert_eq!(
+            candidates,
+            [
+                DiscoveredFeed {
+                    url: "https://example.com/feed".to_string(),
+                    title: Some("Posts".to_string()),
+                    content_type: Some("application/rss+xml".to_string()),
+                },
+                DiscoveredFeed {
+                    url: "https://example.com/broken?a=1&b=2".to_string(),
+                    title: None,
+                    content_type: Some("application/atom+xml".to_string()),
+                },
+            ]
+            .to_vec()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_discover_feeds() {
+        let router = Router::new()
+            .route("/", get(|| async { PAGE }))
+            .route("/feed", get(|| async { RSS }))
+            .route("/broken", get(|| async { "not a feed" }));
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router).await });
+
+        let parser = RssParser::new();
+
+        // Candidates that are not feeds are discarded
+        let feeds = discover_feeds(&parser, &format!("http://{}/", address))
+            .await
+            .unwrap();
+        assert_eq!(feeds.len(), 1);
+        assert_eq!(feeds[0].url, format!("http://{}/feed", address));
+        assert_eq!(feeds[0].title, Some("Posts".to_string()));
+
+        // The url of a feed is returned as is
+        let feeds = discover_feeds(&parser, &format!("http://{}/feed", address))
+            .await
+            .unwrap();
+        assert_eq!(feeds.len(), 1);
+        assert_eq!(feeds[0].title, Some("Blog".to_string()));
+    }
+}
@@ -1,3 +1,5 @@
 pub mod config;
+pub mod discovery;
 pub mod parser;
 pub mod rss;
+pub mod validation;
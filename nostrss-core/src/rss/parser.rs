@@ -2,48 +2,392 @@
 
 use feed_rs::model::{Entry, Feed as RemoteFeed};
 use log::info;
+use reqwest::{
+    header::{IF_MODIFIED_SINCE, IF_NONE_MATCH, USER_AGENT},
+    redirect::Policy,
+    Certificate, Client, Proxy, RequestBuilder, Response, StatusCode,
+};
+use std::collections::BTreeMap;
 use std::error::Error;
 use std::fmt;
+use std::fs;
+use std::time::{Duration, Instant};
 
-/// RSS parsing processor
-pub struct RssParser {}
+use crate::{
+    metrics::metrics::METRICS,
+    rss::config::{Feed, FeedAuth, FetchOptions, Secret},
+    store::cache::HttpCache,
+};
+
+/// The content of a remote feed, unless it did not change since the last fetch.
+pub enum FetchResponse {
+    Modified(Box<RemoteFeed>),
+    NotModified,
+}
+
+/// A fetched feed along the HTTP status of the response.
+/// Local feed files have no status.
+pub struct FetchedFeed {
+    pub response: FetchResponse,
+    pub status: Option<u16>,
+}
+
+/// RSS parsing processor.
+/// Feeds are fetched through the provided HTTP client, which
+/// is shared between the feeds so connections are reused.
+#[derive(Clone)]
+pub struct RssParser {
+    client: Client,
+    // The id of the feed, used as label of the metrics
+    // as its url may contain secrets
+    feed_id: String,
+    // The settings of the feed, applied on each request
+    options: FetchOptions,
+    auth: Option<FeedAuth>,
+    headers: BTreeMap<String, Secret>,
+}
 
 impl RssParser {
+    // Builds a parser for a feed. Feeds with proxy or TLS settings
+    // get a dedicated client, other ones use the shared client.
+    pub fn for_feed(client: &Client, feed: &Feed) -> Result<Self, RssParserError> {
+        let options = feed.fetch.clone().unwrap_or_default();
+
+        // The deprecated `fetch.proxy` setting is moved to the proxy
+        // of the feed when the feeds are loaded
+        let proxy = feed.proxy.as_deref().or(options.proxy.as_deref());
+
+        let client = match proxy.is_some() || options.needs_dedicated_client() {
+            true => Self::build_client(&options, proxy)?,
+            false => client.clone(),
+        };
+
+        Ok(Self {
+            client,
+            feed_id: feed.id.clone(),
+            options,
+            auth: feed.auth.clone(),
+            headers: feed.headers.clone().unwrap_or_default(),
+        })
+    }
+
+    // Builds an HTTP client with the settings provided by the environment,
+    // the TLS settings of the provided options and the proxy of a feed.
+    // Clients without a proxy use the global proxy, if any.
+    pub fn build_client(
+        options: &FetchOptions,
+        proxy: Option<&str>,
+    ) -> Result<Client, RssParserError> {
+        let mut builder = Client::builder()
+            .user_agent(FetchOptions::default_user_agent())
+            .timeout(Duration::from_secs(FetchOptions::default_timeout()))
+            .redirect(Policy::limited(FetchOptions::default_max_redirects()));
+
+        let proxy = match proxy {
+            Some(proxy) => Some(proxy.to_string()),
+            None => FetchOptions::default_proxy(),
+        };
+
+        if let Some(proxy) = proxy {
+            builder = builder.proxy(Self::proxy(&proxy)?);
+        }
+
+        if let Some(tls) = &options.tls {
+            builder = builder.danger_accept_invalid_certs(tls.accept_invalid_certs);
+
+            if let Some(path) = &tls.ca_certificate {
+                let certificate = fs::read(path)
+                    .map_err(|e| e.to_string())
+                    .and_then(|pem| Certificate::from_pem(&pem).map_err(|e| e.to_string()))
+                    .map_err(|e| {
+                        RssParserError::new(&format!("Invalid certificate {} : {}", path, e))
+                    })?;
+                builder = builder.add_root_certificate(certificate);
+            }
+        }
+
+        builder
+            .build()
+            .map_err(|e| RssParserError::new(&format!("Invalid HTTP client settings : {}", e)))
+    }
+
+    // Builds a proxy from its url. The `socks5h`, `socks5`, `http` and `https`
+    // schemes are supported. Bare addresses are SOCKS5 proxies.
+    pub fn proxy(proxy: &str) -> Result<Proxy, RssParserError> {
+        Proxy::all(FetchOptions::proxy_url(proxy))
+            .map_err(|e| RssParserError::new(&format!("Invalid proxy {} : {}", proxy, e)))
+    }
+
     // Reads a remote RSS feed.
-    pub async fn read(url: String) -> Result<RemoteFeed, RssParserError> {
+    pub async fn read(&self, url: String) -> Result<RemoteFeed, RssParserError> {
+        let (feed, _) = self.fetch(url).await?;
+
+        Ok(feed)
+    }
+
+    // Reads a remote RSS feed and returns it along the HTTP status of the response.
+    // A response with an unsuccessful status is an error.
+    pub async fn fetch(&self, url: String) -> Result<(RemoteFeed, Option<u16>), RssParserError> {
+        let mut cache = HttpCache::default();
+
+        let fetched = self.fetch_conditional(url, &mut cache, 0).await?;
+
+        match fetched.response {
+            FetchResponse::Modified(feed) => Ok((*feed, fetched.status)),
+            FetchResponse::NotModified => Err(RssParserError {
+                status: fetched.status,
+                ..RssParserError::new("Rss feed not modified")
+            }),
+        }
+    }
+
+    // Reads a remote RSS feed, sending the validators of the cache so an unchanged
+    // feed is not downloaded again. The cache is updated from the response headers.
+    pub async fn fetch_conditional(
+        &self,
+        url: String,
+        cache: &mut HttpCache,
+        now: i64,
+    ) -> Result<FetchedFeed, RssParserError> {
+        let started = Instant::now();
+        let result = self.request(url.clone(), cache, now).await;
+
+        METRICS
+            .feed_fetch_duration
+            .with_label_values(&[&self.feed_id])
+            .observe(started.elapsed().as_secs_f64());
+
+        let outcome = match &result {
+            Ok(FetchedFeed {
+                response: FetchResponse::Modified(_),
+                ..
+            }) => "success",
+            Ok(FetchedFeed {
+                response: FetchResponse::NotModified,
+                ..
+            }) => "not_modified",
+            Err(_) => "error",
+        };
+        METRICS
+            .feed_fetches
+            .with_label_values(&[&self.feed_id, outcome])
+            .inc();
+
+        result
+    }
+
+    async fn request(
+        &self,
+        url: String,
+        cache: &mut HttpCache,
+        now: i64,
+    ) -> Result<FetchedFeed, RssParserError> {
         info!("requesting {:?}", url);
 
+        if url.starts_with("file:") {
+            return self.read_file(&url).await;
+        }
+
+        let mut request = self.prepare(&url)?;
+
+        if let Some(etag) = &cache.etag {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+
+        if let Some(last_modified) = &cache.last_modified {
+            request = request.header(IF_MODIFIED_SINCE, last_modified);
+        }
+
         // fetch
-        let request_response = match reqwest::get(url).await {
+        let request_response = match request.send().await {
             Ok(value) => value,
             Err(_) => {
                 return Err(RssParserError::new("Error while fetching Rss Feed"));
             }
         };
 
-        // read
-        let content = match request_response.text().await {
-            Ok(result) => result,
-            Err(_) => {
-                return Err(RssParserError::new("Error while reading Rss feed response"));
+        let status = request_response.status();
+
+        if status == StatusCode::NOT_MODIFIED {
+            cache.update_not_modified(request_response.headers(), now);
+
+            return Ok(FetchedFeed {
+                response: FetchResponse::NotModified,
+                status: Some(status.as_u16()),
+            });
+        }
+
+        if !status.is_success() {
+            // e.g: 429 or 503 responses telling when to come back
+            cache.retry_after(request_response.headers(), now);
+
+            let error = format!("Rss feed responded with status {}", status);
+            return Err(RssParserError::new(&error).with_status(status.as_u16()));
+        }
+
+        cache.update_modified(request_response.headers(), now);
+
+        let content = self.read_body(request_response).await?;
+
+        // parse
+        let feed =
+            Self::parse(&self.feed_id, &content).map_err(|e| e.with_status(status.as_u16()))?;
+
+        Ok(FetchedFeed {
+            response: FetchResponse::Modified(Box::new(feed)),
+            status: Some(status.as_u16()),
+        })
+    }
+
+    // Downloads a remote document, e.g: a web page referencing feeds.
+    // Returns the content along the url of the document, once redirects are followed.
+    pub async fn download(&self, url: &str) -> Result<(Vec<u8>, String), RssParserError> {
+        info!("requesting {:?}", url);
+
+        let response = match self.prepare(url)?.send().await {
+            Ok(value) => value,
+            Err(_) => return Err(RssParserError::new("Error while fetching document")),
+        };
+
+        let status = response.status();
+        if !status.is_success() {
+            let error = format!("Document responded with status {}", status);
+            return Err(RssParserError::new(&error).with_status(status.as_u16()));
+        }
+
+        let url = response.url().to_string();
+        let content = self.read_body(response).await?;
+
+        Ok((content, url))
+    }
+
+    // Builds a request with the settings and the authentication of the feed
+    fn prepare(&self, url: &str) -> Result<RequestBuilder, RssParserError> {
+        let mut request = self.client.get(url);
+
+        if let Some(timeout) = self.options.timeout {
+            request = request.timeout(Duration::from_secs(timeout));
+        }
+
+        if let Some(user_agent) = &self.options.user_agent {
+            request = request.header(USER_AGENT, user_agent);
+        }
+
+        self.authorize(request)
+    }
+
+    // Reads the body of a response, up to the maximum body size
+    async fn read_body(&self, mut response: Response) -> Result<Vec<u8>, RssParserError> {
+        let status = response.status();
+        let max_body_size = self.max_body_size();
+        let too_large = || Self::too_large(max_body_size).with_status(status.as_u16());
+
+        if response
+            .content_length()
+            .is_some_and(|length| length > max_body_size)
+        {
+            return Err(too_large());
+        }
+
+        let mut content = Vec::new();
+        loop {
+            match response.chunk().await {
+                Ok(Some(chunk)) => {
+                    content.extend_from_slice(&chunk);
+
+                    if content.len() as u64 > max_body_size {
+                        return Err(too_large());
+                    }
+                }
+                Ok(None) => break,
+                Err(_) => {
+                    return Err(RssParserError::new("Error while reading Rss feed response")
+                        .with_status(status.as_u16()));
+                }
             }
+        }
+
+        Ok(content)
+    }
+
+    // Reads a local feed file, e.g: generated by a build pipeline.
+    // Files are read on each fetch as they are not cached.
+    async fn read_file(&self, url: &str) -> Result<FetchedFeed, RssParserError> {
+        let path = match url::Url::parse(url).map(|url| url.to_file_path()) {
+            Ok(Ok(path)) => path,
+            _ => return Err(RssParserError::new("Invalid feed file url")),
         };
 
-        // parse
-        let feed = match feed_rs::parser::parse(content.as_bytes()) {
-            Ok(feed) => feed,
-            Err(e) => {
-                let error = format!("Error while parsing Rss feed stream : {}", e);
-                return Err(RssParserError::new(&error));
+        let read_error = |e: std::io::Error| {
+            let error = format!(
+                "Error while reading Rss feed file {} : {}",
+                path.display(),
+                e
+            );
+            RssParserError::new(&error)
+        };
+
+        let max_body_size = self.max_body_size();
+        let metadata = tokio::fs::metadata(&path).await.map_err(read_error)?;
+        if metadata.len() > max_body_size {
+            return Err(Self::too_large(max_body_size));
+        }
+
+        let content = tokio::fs::read(&path).await.map_err(read_error)?;
+        let feed = Self::parse(&self.feed_id, &content)?;
+
+        Ok(FetchedFeed {
+            response: FetchResponse::Modified(Box::new(feed)),
+            status: None,
+        })
+    }
+
+    // Parses a feed document of the feed with the provided id
+    pub fn parse(feed_id: &str, content: &[u8]) -> Result<RemoteFeed, RssParserError> {
+        feed_rs::parser::parse(content).map_err(|e| {
+            METRICS.parse_errors.with_label_values(&[feed_id]).inc();
+            let error = format!("Error while parsing Rss feed stream : {}", e);
+            RssParserError::new(&error)
+        })
+    }
+
+    fn max_body_size(&self) -> u64 {
+        self.options
+            .max_body_size
+            .unwrap_or_else(FetchOptions::default_max_body_size)
+    }
+
+    fn too_large(max_body_size: u64) -> RssParserError {
+        RssParserError::new(&format!(
+            "Rss feed response exceeds {} bytes",
+            max_body_size
+        ))
+    }
+
+    // Applies the authentication and the additional headers of the feed to a request.
+    // Secrets are resolved on each request.
+    fn authorize(&self, mut request: RequestBuilder) -> Result<RequestBuilder, RssParserError> {
+        let resolve = |secret: &Secret| secret.resolve().map_err(|e| RssParserError::new(&e));
+
+        request = match &self.auth {
+            Some(FeedAuth::Basic { username, password }) => {
+                let password = password.as_ref().map(resolve).transpose()?;
+                request.basic_auth(username, password)
             }
+            Some(FeedAuth::Bearer { token }) => request.bearer_auth(resolve(token)?),
+            None => request,
         };
 
-        Ok(feed)
+        for (name, value) in &self.headers {
+            request = request.header(name, resolve(value)?);
+        }
+
+        Ok(request)
     }
 
     // Retrieves the first item from a remote feed
-    pub async fn get_first_item(url: String) -> Result<Entry, RssParserError> {
-        let feed = match Self::read(url).await {
+    pub async fn get_first_item(&self, url: String) -> Result<Entry, RssParserError> {
+        let feed = match self.read(url).await {
             Ok(feed) => feed,
             Err(e) => {
                 return Err(e);
@@ -54,8 +398,8 @@ impl RssParser {
     }
 
     // Retrieves all items from a remote feed
-    pub async fn get_items(url: String) -> Result<Vec<Entry>, RssParserError> {
-        let feed = match Self::read(url).await {
+    pub async fn get_items(&self, url: String) -> Result<Vec<Entry>, RssParserError> {
+        let feed = match self.read(url).await {
             Ok(feed) => feed,
             Err(e) => {
                 return Err(e);
@@ -65,22 +409,39 @@ impl RssParser {
         Ok(feed.entries)
     }
 
+    // Builds a parser with its own client, configured from the environment
     pub fn new() -> Self {
-        Self {}
+        let options = FetchOptions::default();
+
+        Self {
+            client: Self::build_client(&options, None).unwrap_or_default(),
+            feed_id: Feed::default().id,
+            options,
+            auth: None,
+            headers: BTreeMap::new(),
+        }
     }
 }
 
 #[derive(Debug)]
 pub struct RssParserError {
     pub message: String,
+    // HTTP status of the response, if the remote feed responded
+    pub status: Option<u16>,
 }
 
 impl RssParserError {
     pub fn new(message: &str) -> RssParserError {
         RssParserError {
             message: message.to_string(),
+            status: None,
         }
     }
+
+    pub fn with_status(mut self, status: u16) -> RssParserError {
+        self.status = Some(status);
+        self
+    }
 }
 
 impl fmt::Display for RssParserError {
@@ -90,3 +451,235 @@ impl fmt::Display for RssParserError {
 }
 
 impl Error for RssParserError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        http::{HeaderMap, StatusCode as HttpStatus},
+        routing::get,
+        Router,
+    };
+
+    const RSS: &str = r#"<?xml version="1.0"?>
+        <rss version="2.0"><channel><title>test</title>
+        <item><guid>a</guid><title>a</title></item>
+        </channel></rss>"#;
+
+    // Serves a feed that is only sent when the `If-None-Match` header does not match,
+    // and a feed that requires a bearer token and an api key
+    async fn serve_feed() -> String {
+        let router = Router::new()
+            .route(
+                "/private",
+                get(|headers: HeaderMap| async move {
+                    let authorized = headers.get("authorization").map(|v| v.as_bytes())
+                        == Some(b"Bearer secret")
+                        && headers.get("x-api-key").map(|v| v.as_bytes()) == Some(b"key");
+
+                    match authorized {
+                        true => (HttpStatus::OK, RSS),
+                        false => (HttpStatus::UNAUTHORIZED, ""),
+                    }
+                }),
+            )
+            .route(
+                "/feed",
+                get(|headers: HeaderMap| async move {
+                    match headers.get("if-none-match").map(|v| v.as_bytes()) {
+                        Some(b"\"v1\"") => (
+                            HttpStatus::NOT_MODIFIED,
+                            [("etag", "\"v1\""), ("cache-control", "max-age=60")],
+                            "",
+                        ),
+                        _ => (
+                            HttpStatus::OK,
+                            [("etag", "\"v1\""), ("cache-control", "no-cache")],
+                            RSS,
+                        ),
+                    }
+                }),
+            );
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router).await });
+
+        format!("http://{}", address)
+    }
+
+    #[tokio::test]
+    async fn test_fetch_conditional() {
+        let url = format!("{}/feed", serve_feed().await);
+        let mut cache = HttpCache::default();
+
+        let parser = RssParser::new();
+
+        let fetched = parser
+            .fetch_conditional(url.clone(), &mut cache, 1000)
+            .await
+            .unwrap();
+        assert_eq!(fetched.status, Some(200));
+        assert!(
+            matches!(fetched.response, FetchResponse::Modified(feed) if feed.entries.len() == 1)
+        );
+        assert_eq!(cache.etag, Some("\"v1\"".to_string()));
+        assert_eq!(cache.not_before, None);
+
+        // The feed is not downloaded again while unchanged
+        let fetched = parser
+            .fetch_conditional(url, &mut cache, 1000)
+            .await
+            .unwrap();
+        assert_eq!(fetched.status, Some(304));
+        assert!(matches!(fetched.response, FetchResponse::NotModified));
+        assert_eq!(cache.not_before, Some(1060));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_options() {
+        let url = format!("{}/feed", serve_feed().await);
+        let client = RssParser::new().client;
+
+        let feed = Feed {
+            fetch: Some(FetchOptions {
+                max_body_size: Some(10),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        // The shared client is used for feeds without proxy or TLS settings
+        let parser = RssParser::for_feed(&client, &feed).unwrap();
+        let result = parser.fetch(url).await;
+        assert_eq!(
+            result.err().unwrap().message,
+            "Rss feed response exceeds 10 bytes"
+        );
+
+        let feed = Feed {
+            fetch: Some(FetchOptions {
+                proxy: Some("not a proxy".to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        assert!(RssParser::for_feed(&client, &feed).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_through_proxy() {
+        let url = "http://feed.invalid/feed".to_string();
+        let client = RssParser::new().client;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        drop(listener);
+
+        // Feeds are not fetched directly when their proxy is unreachable,
+        // be it a SOCKS5 or an HTTP proxy
+        for proxy in [
+            address.to_string(),
+            format!("socks5h://{}", address),
+            format!("http://{}", address),
+        ] {
+            let feed = Feed {
+                proxy: Some(proxy),
+                ..Default::default()
+            };
+
+            let parser = RssParser::for_feed(&client, &feed).unwrap();
+            assert!(parser.get_items(url.clone()).await.is_err());
+        }
+
+        // The deprecated fetch proxy is used when it was not migrated
+        let feed = Feed {
+            fetch: Some(FetchOptions {
+                proxy: Some(format!("http://{}", address)),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let parser = RssParser::for_feed(&client, &feed).unwrap();
+        assert!(parser.get_items(url).await.is_err());
+
+        let feed = Feed {
+            proxy: Some("ftp://127.0.0.1:21".to_string()),
+            ..Default::default()
+        };
+        assert!(RssParser::for_feed(&client, &feed).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_file() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("feed.xml");
+        std::fs::write(&path, RSS).unwrap();
+
+        let url = url::Url::from_file_path(&path).unwrap().to_string();
+        let feed = Feed {
+            id: "file_feed".to_string(),
+            ..Default::default()
+        };
+        let parser = RssParser::for_feed(&Client::new(), &feed).unwrap();
+
+        let (feed, status) = parser.fetch(url).await.unwrap();
+        assert_eq!(feed.entries.len(), 1);
+        assert_eq!(status, None);
+
+        // Metrics are labelled with the feed id rather than its url
+        assert_eq!(
+            METRICS
+                .feed_fetches
+                .with_label_values(&["file_feed", "success"])
+                .get(),
+            1
+        );
+
+        let url = url::Url::from_file_path(directory.path().join("missing.xml"))
+            .unwrap()
+            .to_string();
+        assert!(parser.fetch(url).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_with_auth() {
+        let url = format!("{}/private", serve_feed().await);
+        let client = RssParser::new().client;
+
+        std::env::set_var("TEST_FEED_TOKEN", "secret");
+
+        let mut headers = BTreeMap::new();
+        headers.insert("X-Api-Key".to_string(), Secret::Value("key".to_string()));
+
+        let feed = Feed {
+            auth: Some(FeedAuth::Bearer {
+                token: Secret::Env {
+                    env: "TEST_FEED_TOKEN".to_string(),
+                },
+            }),
+            headers: Some(headers),
+            ..Default::default()
+        };
+
+        let parser = RssParser::for_feed(&client, &feed).unwrap();
+        assert!(parser.get_items(url.clone()).await.is_ok());
+
+        // Unresolved secrets are fetch errors
+        let feed = Feed {
+            auth: Some(FeedAuth::Bearer {
+                token: Secret::Env {
+                    env: "TEST_FEED_MISSING_TOKEN".to_string(),
+                },
+            }),
+            ..Default::default()
+        };
+
+        let parser = RssParser::for_feed(&client, &feed).unwrap();
+        assert_eq!(
+            parser.get_items(url).await.err().unwrap().message,
+            "Environment variable TEST_FEED_MISSING_TOKEN is not set"
+        );
+    }
+}
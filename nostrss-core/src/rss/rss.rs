@@ -3,8 +3,12 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
+use log::error;
+
 use super::config::Feed;
+use super::config::FetchOptions;
 use super::config::RssConfig;
+use super::parser::RssParser;
 use tokio_cron_scheduler::Job;
 use tokio_cron_scheduler::JobScheduler;
 use uuid::Uuid;
@@ -16,6 +20,8 @@ pub struct RssInstance {
     pub feeds_jobs: HashMap<String, Uuid>,
     pub feeds: Vec<Feed>,
     pub maps: HashMap<String, HashMap<String, String>>,
+    // The HTTP client shared by the feeds jobs
+    pub http_client: reqwest::Client,
 }
 
 impl RssInstance {
@@ -29,12 +35,22 @@ impl RssInstance {
         };
         let feeds = config.feeds.clone();
         let feeds_jobs = HashMap::new();
+
+        let http_client = match RssParser::build_client(&FetchOptions::default(), None) {
+            Ok(client) => client,
+            Err(e) => {
+                error!("{}. Using default HTTP client settings", e.message);
+                reqwest::Client::new()
+            }
+        };
+
         Self {
             config,
             scheduler,
             feeds_jobs,
             feeds,
             maps: HashMap::new(),
+            http_client,
         }
     }
 
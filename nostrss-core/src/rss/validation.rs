@@ -0,0 +1,137 @@
+use std::{collections::HashMap, fmt};
+
+use feed_rs::model::Entry;
+use tokio_cron_scheduler::Job;
+
+use crate::{profiles::config::Profile, template::template::TemplateProcessor};
+
+use super::{
+    config::{Feed, FeedKind},
+    parser::RssParser,
+};
+
+/// A problem that prevents a feed from being scheduled or its entries from being published.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeedProblem {
+    // The setting of the feed the problem relates to, e.g: `schedule`
+    pub field: String,
+    pub message: String,
+}
+
+impl FeedProblem {
+    pub fn new(field: &str, message: &str) -> Self {
+        Self {
+            field: field.to_string(),
+            message: message.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for FeedProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} : {}", self.field, self.message)
+    }
+}
+
+// Checks a cron pattern with the parser of the scheduler
+pub fn check_schedule(rule: &str) -> Result<(), String> {
+    Job::new(rule, |_, _| {})
+        .map(|_| ())
+        .map_err(|_| format!("Invalid cron pattern {}", rule))
+}
+
+/// Checks a feed before it is scheduled: its cron pattern, its profiles,
+/// that it can be fetched and parsed, and that its template renders for its first entry.
+/// Returns the problems found, if any.
+pub async fn validate_feed(
+    feed: &Feed,
+    http_client: &reqwest::Client,
+    profiles: &HashMap<String, Profile>,
+) -> Vec<FeedProblem> {
+    let mut problems = Vec::new();
+
+    if let Err(e) = check_schedule(&feed.schedule) {
+        problems.push(FeedProblem::new("schedule", &e));
+    }
+
+    for profile_id in feed.profiles.clone().unwrap_or_default() {
+        if !profiles.contains_key(&profile_id) {
+            let message = format!("No profile found with id {}", profile_id);
+            problems.push(FeedProblem::new("profiles", &message));
+        }
+    }
+
+    let parser = match RssParser::for_feed(http_client, feed) {
+        Ok(parser) => parser,
+        Err(e) => {
+            problems.push(FeedProblem::new("fetch", &e.message));
+            return problems;
+        }
+    };
+
+    let remote = match parser.read(feed.url.to_string()).await {
+        Ok(remote) => remote,
+        Err(e) => {
+            problems.push(FeedProblem::new("url", &e.message));
+            return problems;
+        }
+    };
+
+    // Articles are not built from the template of the feed
+    if feed.kind == FeedKind::Note {
+        let entry = remote.entries.first().cloned().unwrap_or(Entry::default());
+
+        if let Err(e) = TemplateProcessor::parse(feed.clone(), entry) {
+            problems.push(FeedProblem::new("template", &e.to_string()));
+        }
+    }
+
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture_url() -> nostr_sdk::Url {
+        let path = std::fs::canonicalize("./src/fixtures/feed.xml").unwrap();
+        nostr_sdk::Url::from_file_path(path).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_validate_feed() {
+        dotenv::from_filename(".env.test").ok();
+
+        let mut profiles = HashMap::new();
+        profiles.insert("default".to_string(), Profile::default());
+        let client = reqwest::Client::new();
+
+        let feed = Feed {
+            url: fixture_url(),
+            profiles: Some(["default".to_string()].to_vec()),
+            ..Default::default()
+        };
+        assert!(validate_feed(&feed, &client, &profiles).await.is_empty());
+
+        let feed = Feed {
+            url: fixture_url(),
+            schedule: "every minute".to_string(),
+            profiles: Some(["unknown".to_string()].to_vec()),
+            template: Some("./src/fixtures/missing.template".to_string()),
+            ..Default::default()
+        };
+        let problems = validate_feed(&feed, &client, &profiles).await;
+        let fields: Vec<&str> = problems.iter().map(|p| p.field.as_str()).collect();
+        assert_eq!(fields, ["schedule", "profiles", "template"].to_vec());
+
+        // The template is not checked if the feed can not be fetched
+        let feed = Feed {
+            url: nostr_sdk::Url::from_file_path("/nonexistent/feed.xml").unwrap(),
+            template: Some("./src/fixtures/missing.template".to_string()),
+            ..Default::default()
+        };
+        let problems = validate_feed(&feed, &client, &profiles).await;
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].field, "url");
+    }
+}
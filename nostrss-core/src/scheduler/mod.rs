@@ -1 +1,3 @@
+pub mod outbox;
 pub mod scheduler;
+pub mod watch;
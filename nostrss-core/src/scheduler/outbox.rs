@@ -0,0 +1,223 @@
+use std::{env, sync::Arc, time::Duration};
+
+use log::{error, info};
+use nostr_sdk::{Client, Event, JsonUtil};
+use tokio::sync::Mutex;
+use tokio_cron_scheduler::Job;
+
+use crate::store::{
+    ledger::Publication,
+    outbox::{OutboxEvent, OutboxStatus},
+    store::{flush_store, SharedSeenStore},
+};
+
+use super::scheduler::{now_timestamp, RssNostrJob};
+
+/// Builds the job that retries the events of the outbox.
+/// Returns `None` if the job could not be created.
+pub fn schedule_outbox(store: SharedSeenStore, client: Client) -> Option<Job> {
+    // Ticks are skipped while a previous one is still sending events
+    let running = Arc::new(Mutex::new(()));
+
+    let job = Job::new_repeated_async(OutboxJob::retry_interval(), move |_uuid, _lock| {
+        let store = Arc::clone(&store);
+        let client = client.clone();
+        let running = Arc::clone(&running);
+
+        Box::pin(async move {
+            let Ok(_running) = running.try_lock() else {
+                return;
+            };
+
+            OutboxJob::process(&store, &client, now_timestamp()).await;
+        })
+    });
+
+    match job {
+        Ok(job) => Some(job),
+        Err(e) => {
+            error!("Outbox job could not be created : {:?}", e);
+            None
+        }
+    }
+}
+
+pub struct OutboxJob {}
+
+impl OutboxJob {
+    // Interval between two checks of the outbox.
+    // Provided by `OUTBOX_RETRY_INTERVAL`, in seconds. Defaults to 10 seconds.
+    pub fn retry_interval() -> Duration {
+        let seconds = env::var("OUTBOX_RETRY_INTERVAL")
+            .unwrap_or("10".to_string())
+            .parse::<u64>()
+            .unwrap_or(10);
+
+        Duration::from_secs(seconds.max(1))
+    }
+
+    // Retries the outbox events that have deliveries due at `now`.
+    // The store lock is released while the events are sent.
+    pub async fn process(store: &SharedSeenStore, client: &Client, now: i64) {
+        let events: Vec<OutboxEvent> = store
+            .lock()
+            .await
+            .get_outbox()
+            .into_iter()
+            .filter(|event| event.status == OutboxStatus::Pending)
+            .filter(|event| event.deliveries.iter().any(|d| d.is_due(now)))
+            .collect();
+
+        for mut event in events {
+            Self::retry(client, &mut event, now).await;
+
+            let mut store_lock = store.lock().await;
+
+            // The ledger reflects the relays that acknowledged the event so far
+            if let Some(mut publication) = store_lock.get_publication(&event.event_id) {
+                publication.relays = event.get_acked_relays();
+                publication.status =
+                    Publication::get_status(&publication.targets, &publication.relays, false);
+                store_lock.update_publication(publication);
+            }
+
+            store_lock.update_outbox_event(event);
+        }
+
+        flush_store(store).await;
+    }
+
+    // Sends an outbox event to the relays that are due
+    // and records the outcome of each delivery.
+    async fn retry(client: &Client, event: &mut OutboxEvent, now: i64) {
+        let signed = match Event::from_json(&event.event) {
+            Ok(signed) => signed,
+            Err(e) => {
+                error!("Invalid event {} found in outbox : {}", event.event_id, e);
+                event.status = OutboxStatus::Failed;
+                return;
+            }
+        };
+
+        let due: Vec<String> = event
+            .deliveries
+            .iter()
+            .filter(|delivery| delivery.is_due(now))
+            .map(|delivery| delivery.relay.clone())
+            .collect();
+
+        let results = RssNostrJob::send_to_relays(client, &due, &signed).await;
+
+        for delivery in event
+            .deliveries
+            .iter_mut()
+            .filter(|delivery| due.contains(&delivery.relay))
+        {
+            match results.get(&delivery.relay) {
+                Some(Ok(_)) => delivery.acked = true,
+                Some(Err(e)) => delivery.fail(e.clone(), now),
+                None => delivery.fail("Event not sent".to_string(), now),
+            }
+        }
+
+        if event.is_delivered() {
+            info!(
+                "Event {} of feed {} delivered from outbox",
+                event.event_id, event.feed_id
+            );
+        } else if event.is_exhausted() {
+            error!(
+                "Event {} of feed {} could not be delivered to enough relays. Giving up.",
+                event.event_id, event.feed_id
+            );
+            event.status = OutboxStatus::Failed;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::{ledger::PublicationStatus, store::load_store};
+    use nostr_sdk::{EventBuilder, Keys, Kind};
+
+    #[tokio::test]
+    async fn test_outbox_retry() {
+        let keys = Keys::generate();
+        let event = EventBuilder::new(Kind::TextNote, "test", [])
+            .to_event(&keys)
+            .unwrap();
+        let event_id = event.id.to_hex();
+        let targets = ["wss://relay.example.com".to_string()].to_vec();
+
+        let outbox_event = OutboxEvent::new(
+            "test",
+            "a",
+            "default",
+            &event_id,
+            event.as_json(),
+            &targets,
+            &[],
+            0,
+        )
+        .unwrap();
+
+        let store = load_store(&None).unwrap();
+        {
+            let mut store_lock = store.lock().await;
+            store_lock.add_publications(
+                "test",
+                [Publication {
+                    feed_id: "test".to_string(),
+                    entry_id: "a".to_string(),
+                    profile_id: "default".to_string(),
+                    event_id: event_id.clone(),
+                    kind: 1,
+                    targets: targets.clone(),
+                    relays: Vec::new(),
+                    timestamp: 0,
+                    status: PublicationStatus::Failed,
+                }]
+                .to_vec(),
+            );
+            store_lock.add_outbox_events("test", [outbox_event].to_vec());
+        }
+
+        // The relay is not in the pool of the client, the attempt fails
+        let client = Client::new(keys);
+        let now = OutboxEvent::backoff(1);
+        OutboxJob::process(&store, &client, now).await;
+
+        let outbox = store.lock().await.get_outbox();
+        assert_eq!(outbox.len(), 1);
+        assert_eq!(outbox[0].deliveries[0].attempts, 2);
+        assert_eq!(
+            outbox[0].deliveries[0].next_attempt,
+            now + OutboxEvent::backoff(2)
+        );
+        assert!(outbox[0].deliveries[0].last_error.is_some());
+
+        // Deliveries that are not due are not retried
+        OutboxJob::process(&store, &client, now).await;
+        assert_eq!(store.lock().await.get_outbox()[0].deliveries[0].attempts, 2);
+
+        // The event fails once every attempt is exhausted
+        let mut exhausted = outbox[0].clone();
+        exhausted.deliveries[0].attempts = OutboxEvent::max_attempts() - 1;
+        store.lock().await.update_outbox_event(exhausted);
+
+        OutboxJob::process(&store, &client, i64::MAX).await;
+
+        let outbox = store.lock().await.get_outbox();
+        assert_eq!(outbox[0].status, OutboxStatus::Failed);
+        assert_eq!(
+            store
+                .lock()
+                .await
+                .get_publication(&event_id)
+                .unwrap()
+                .status,
+            PublicationStatus::Failed
+        );
+    }
+}
@@ -1,240 +1,1553 @@
-use feed_rs::model::Entry;
-use log::{debug, error};
-use nostr_sdk::{Client, EventBuilder, JsonUtil, Keys, Tag};
-use std::{collections::HashMap, sync::Arc};
-use tokio::sync::{Mutex, MutexGuard};
+use feed_rs::model::{Entry, Feed as RemoteFeed};
+use log::{debug, error, info, warn};
+use nostr_sdk::{
+    Client, Event, EventBuilder, EventId, JsonUtil, Keys, Kind, RelaySendOptions, Tag,
+    UnsignedEvent,
+};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    fs::OpenOptions,
+    io::Write,
+    sync::Arc,
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+use tokio::{
+    sync::{Mutex, MutexGuard},
+    task::JoinSet,
+};
 use tokio_cron_scheduler::Job;
 
 use crate::{
     app::app::AppConfig,
+    metrics::metrics::METRICS,
     nostr::relay::Relay,
     profiles::config::Profile,
-    rss::{config::Feed, parser::RssParser},
-    template::template::TemplateProcessor,
+    rss::{
+        config::{CatchUp, Feed, FeedKind, OnUpdate},
+        parser::{FetchResponse, FetchedFeed, RssParser, RssParserError},
+        validation::check_schedule,
+    },
+    store::{
+        errors::FeedError,
+        ledger::{Publication, PublicationStatus},
+        outbox::OutboxEvent,
+        report::TickReport,
+        store::{
+            flush_store, EntryRecord, PendingAction, PendingProfile, PublishedEvent,
+            SharedSeenStore,
+        },
+    },
+    template::{
+        article::Article,
+        template::{TemplateParserError, TemplateProcessor},
+    },
 };
 
-/// Cronjob creation method
+use super::watch::watch_feed;
+
+/// Cronjob creation method.
+/// Returns an error if the cron pattern of the feed is invalid.
 pub async fn schedule(
     rule: &str,
     feed: Feed,
-    map: Arc<Mutex<HashMap<String, Vec<String>>>>,
+    store: SharedSeenStore,
     client: Arc<Mutex<Client>>,
     profiles: Arc<Mutex<HashMap<String, Profile>>>,
     app_config: Arc<Mutex<AppConfig>>,
-) -> Job {
-    // Create a copy of the map arc that will be solely used into the job
-    let map_job_copy = Arc::clone(&map);
+    http_client: reqwest::Client,
+) -> Result<Job, String> {
+    check_schedule(rule)?;
+
+    // Create a copy of the store arc that will be solely used into the job
+    let store_job_copy = Arc::clone(&store);
+
+    // Invalid fetch settings are reported on each tick of the job
+    let parser = RssParser::for_feed(&http_client, &feed).map_err(|e| e.message);
+
+    // Unix timestamp in milliseconds of the next scheduled run,
+    // used to measure the lag of the scheduler
+    let next_run: Arc<Mutex<Option<i64>>> = Arc::new(Mutex::new(None));
+
+    // Watched feeds are run when their file changes instead of on the ticks
+    // of the job. The watcher is stopped once the job is dropped.
+    let watcher = match feed.watch {
+        true => watch_feed(
+            feed.clone(),
+            parser.clone(),
+            Arc::clone(&store),
+            Arc::clone(&client),
+            Arc::clone(&profiles),
+            Arc::clone(&app_config),
+        ),
+        false => None,
+    };
+    let watcher = Arc::new(watcher);
 
     let job_feed = feed.clone();
-    let job = Job::new_async(rule, move |uuid, _lock| {
+    let job_parser = parser.clone();
+    let job = Job::new_async(rule, move |uuid, mut lock| {
         // Copy feed for job execution
         let feed = job_feed.clone();
-
-        // Get the profiles ids associated to the feed for further use
-        let profile_ids = feed
-            .profiles
-            .clone()
-            .unwrap_or(["default".to_string()].to_vec());
+        let next_run = Arc::clone(&next_run);
+        let parser = job_parser.clone();
+        let watched = watcher.is_some();
 
         // Arc instances for current job
 
-        let map_arc = Arc::clone(&map_job_copy);
+        let store_arc = Arc::clone(&store_job_copy);
         let profiles_arc = Arc::clone(&profiles);
 
         let app_config_arc = Arc::clone(&app_config);
         let client_arc = Arc::clone(&client);
         Box::pin(async move {
-            let mut map_lock = map_arc.lock().await;
-            let feed = feed.clone();
-            let uuid = &uuid.to_string();
-            let mut map = map_lock[uuid].clone();
-
-            let client_lock = client_arc.lock().await;
-
-            let profiles_lock = profiles_arc.lock().await;
-            let app_config_lock = app_config_arc.lock().await;
-            match RssParser::get_items(feed.url.to_string()).await {
-                Ok(entries) => {
-                    // Calls the method that
-                    RssNostrJob::process(
-                        feed.clone(),
-                        profile_ids,
-                        entries,
-                        &mut map,
-                        client_lock,
-                        profiles_lock,
-                        app_config_lock,
-                    )
-                    .await;
-
-                    if feed.cache_size.is_some() {
-                        map.truncate(feed.cache_size.unwrap());
-                    }
+            if watched {
+                return;
+            }
 
-                    _ = &map_lock.insert(uuid.to_string(), map);
-                }
-                Err(_) => {
-                    error!(
-                        "Error while parsing RSS stream for feed with {} id. Skipping...",
-                        feed.id
-                    );
+            let started = now_timestamp_millis();
+            if let Some(scheduled) = *next_run.lock().await {
+                METRICS
+                    .scheduler_lag
+                    .with_label_values(&[&feed.id])
+                    .observe((started - scheduled).max(0) as f64 / 1000.0);
+            }
+
+            run_feed(
+                &feed,
+                &parser,
+                &store_arc,
+                &client_arc,
+                &profiles_arc,
+                &app_config_arc,
+            )
+            .await;
+
+            // A next tick not yet computed by the scheduler is ignored
+            *next_run.lock().await = match lock.next_tick_for_job(uuid).await {
+                Ok(Some(tick)) if tick.timestamp_millis() > started => {
+                    Some(tick.timestamp_millis())
                 }
+                _ => None,
             };
         })
     });
 
-    let job = match job {
-        Ok(j) => j,
+    let job = job.map_err(|e| format!("Invalid cron pattern {} : {}", rule, e))?;
+
+    let f = feed.clone();
+
+    // Initialize the Vec that will store the retained entries of feed for current feed.
+    // This avoids to spam the network on first fetch.
+    // If the store already retains entries for the feed, e.g: when
+    // the store is persisted, we resume from it and the catch-up policy
+    // of the feed decides which of the new entries will be broadcasted on next tick.
+    // The store lock is released while the feed is fetched.
+    let (known, last_run) = {
+        let store_lock = store.lock().await;
+        (store_lock.get(&f.id), store_lock.get_last_run(&f.id))
+    };
+
+    if known.is_some() {
+        info!("Resuming feed {} from stored entries", f.id);
+    }
+
+    let initial_snapshot = match &parser {
+        Ok(parser) => feed_snapshot(f.clone(), parser, known, last_run).await,
         Err(e) => {
-            println!("{:?}", e);
-            panic!()
+            error!("Invalid fetch settings for feed {} : {}", f.id, e);
+            known.unwrap_or_default()
         }
     };
+    store.lock().await.set(&f.id, initial_snapshot);
+    flush_store(&store).await;
 
-    let f = feed.clone();
+    Ok(job)
+}
 
-    // Initialize the Vec that will store the retained entries of feed for current feed.
-    // This avoids to spam the network on first fetch
-    let mut map_lock = map.lock().await;
-    let initial_snapshot = feed_snapshot(f).await;
-    map_lock.insert(job.guid().to_string(), initial_snapshot);
+// Fetches a feed and publishes its new entries
+pub async fn run_feed(
+    feed: &Feed,
+    parser: &Result<RssParser, String>,
+    store: &SharedSeenStore,
+    client: &Arc<Mutex<Client>>,
+    profiles: &Arc<Mutex<HashMap<String, Profile>>>,
+    app_config: &Arc<Mutex<AppConfig>>,
+) {
+    let mut http_cache = store.lock().await.get_http_cache(&feed.id);
+
+    // The tick is skipped while the remote feed asks not to be fetched again,
+    // through `Cache-Control: max-age` or `Retry-After`
+    let now = now_timestamp();
+    let fetched = match (parser, http_cache.is_fresh(now)) {
+        (Err(e), _) => Err(RssParserError::new(e)),
+        (Ok(_), true) => {
+            debug!(
+                "Feed {} is not to be fetched before {}. Skipping tick.",
+                feed.id,
+                http_cache.not_before.unwrap_or_default()
+            );
+            return;
+        }
+        (Ok(parser), false) => {
+            parser
+                .fetch_conditional(feed.url.to_string(), &mut http_cache, now)
+                .await
+        }
+    };
+
+    match fetched {
+        Ok(FetchedFeed {
+            response: FetchResponse::NotModified,
+            status,
+        }) => {
+            info!("Feed {} not modified since last fetch", feed.id);
+
+            let mut store_lock = store.lock().await;
+            let mut health = store_lock.get_health(&feed.id);
+            let entries_seen = health.entries_seen;
+            health.record_success(now, status, entries_seen, 0);
+
+            store_lock.set_health(&feed.id, health);
+            store_lock.set_http_cache(&feed.id, http_cache);
+            store_lock.set_last_run(&feed.id, now);
+        }
+        Ok(FetchedFeed {
+            response: FetchResponse::Modified(remote),
+            status,
+        }) => {
+            publish_feed(feed, *remote, status, store, client, profiles, app_config).await;
+            store.lock().await.set_http_cache(&feed.id, http_cache);
+        }
+        Err(e) => {
+            error!(
+                "Error while parsing RSS stream for feed with {} id. Skipping...",
+                feed.id
+            );
+
+            let error = JobError::Fetch(e.message);
+            let tick = TickReport {
+                timestamp: now_timestamp(),
+                error: Some(error.to_string()),
+                ..Default::default()
+            };
+
+            let mut store_lock = store.lock().await;
+            let mut health = store_lock.get_health(&feed.id);
+            health.record_failure(tick.timestamp, e.status, error.to_string());
+
+            store_lock.add_errors(&feed.id, [error.to_record(tick.timestamp)].to_vec());
+            store_lock.set_last_report(&feed.id, tick);
+            store_lock.set_health(&feed.id, health);
+            store_lock.set_http_cache(&feed.id, http_cache);
+        }
+    };
+
+    flush_store(store).await;
+}
+
+// Publishes the new entries of a fetched feed and records the outcome in the store.
+// The store lock is released while the entries are published
+// so other jobs are not blocked by a slow relay.
+pub async fn publish_feed(
+    feed: &Feed,
+    remote: RemoteFeed,
+    status: Option<u16>,
+    store: &SharedSeenStore,
+    client: &Arc<Mutex<Client>>,
+    profiles: &Arc<Mutex<HashMap<String, Profile>>>,
+    app_config: &Arc<Mutex<AppConfig>>,
+) -> TickReport {
+    // Get the profiles ids associated to the feed for further use
+    let profile_ids = feed
+        .profiles
+        .clone()
+        .unwrap_or(["default".to_string()].to_vec());
+
+    let (mut map, mut records) = {
+        let store_lock = store.lock().await;
+        (
+            store_lock.get(&feed.id).unwrap_or_default(),
+            store_lock.get_entry_records(&feed.id),
+        )
+    };
+
+    let entries = remote.entries;
+    let entries_seen = entries.len() as u64;
+
+    // Profiles, relays and settings are read on each tick so the changes made
+    // at runtime apply. The locks are not held while the entries are published.
+    let client = client.lock().await.clone();
+    let profiles = profiles.lock().await.clone();
+    let app_config = app_config.lock().await.clone();
+
+    let report = RssNostrJob::process(
+        feed.clone(),
+        profile_ids,
+        entries,
+        &mut map,
+        &mut records,
+        &client,
+        &profiles,
+        &app_config,
+    )
+    .await;
+
+    if feed.cache_size.is_some() {
+        map.truncate(feed.cache_size.unwrap());
+    }
+
+    // Records of the entries out of the cache are dropped
+    records.retain(|entry_id, _| map.contains(entry_id));
+
+    let tick = TickReport {
+        timestamp: now_timestamp(),
+        ..report.tick
+    };
+    info!("Feed {} processed : {}", feed.id, tick);
+
+    let mut store_lock = store.lock().await;
+    let mut health = store_lock.get_health(&feed.id);
+    health.record_success(
+        tick.timestamp,
+        status,
+        entries_seen,
+        tick.published.len() as u64,
+    );
+
+    store_lock.set(&feed.id, map);
+    store_lock.set_entry_records(&feed.id, records);
+    store_lock.add_publications(&feed.id, report.ledger);
+    store_lock.add_outbox_events(&feed.id, report.outbox);
+    store_lock.add_errors(
+        &feed.id,
+        report
+            .errors
+            .iter()
+            .map(|e| e.to_record(now_timestamp()))
+            .collect(),
+    );
+    store_lock.set_last_report(&feed.id, tick.clone());
+    store_lock.set_health(&feed.id, health);
+    store_lock.set_last_run(&feed.id, now_timestamp());
+
+    tick
+}
+
+// Retrieves a feed and returns a vec of ids for the feed.
+// This method is used to provide initial snapshot of the rss feeds
+// In order to avoid to spam relays with initial rss feed fetch.
+pub async fn feed_snapshot(
+    feed: Feed,
+    parser: &RssParser,
+    known: Option<Vec<String>>,
+    last_run: Option<i64>,
+) -> Vec<String> {
+    match parser.get_items(feed.url.to_string()).await {
+        Ok(entries) => catch_up_snapshot(&feed.catch_up, entries, known, last_run, now_timestamp()),
+        Err(_) => {
+            error!(
+                "Error while parsing RSS stream for feed with {} id. Skipping initial snapshot",
+                feed.id
+            );
+            known.unwrap_or_default()
+        }
+    }
+}
+
+// Applies the catch-up policy to the entries of a feed that are not known yet.
+// Returns the ids to be marked as seen. Entries left out of the snapshot
+// will be published on the first tick of the job.
+fn catch_up_snapshot(
+    policy: &CatchUp,
+    entries: Vec<Entry>,
+    known: Option<Vec<String>>,
+    last_run: Option<i64>,
+    now: i64,
+) -> Vec<String> {
+    let is_known = known.is_some();
+    let known = known.unwrap_or_default();
+
+    let mut pending: Vec<&Entry> = entries.iter().filter(|e| !known.contains(&e.id)).collect();
+
+    // Most recent entries first. Undated entries keep the feed order at the end.
+    pending.sort_by_key(|e| std::cmp::Reverse(entry_timestamp(e)));
+
+    let publish: Vec<&String> = match policy {
+        CatchUp::None => Vec::new(),
+        CatchUp::SinceLastRun => pending
+            .iter()
+            .filter(|e| match (entry_timestamp(e), last_run) {
+                (Some(date), Some(last_run)) => date > last_run,
+                // Without dates, entries missing from a stored feed are new since last run
+                _ => is_known,
+            })
+            .map(|e| &e.id)
+            .collect(),
+        CatchUp::MaxEntries { count } => pending.iter().take(*count).map(|e| &e.id).collect(),
+        CatchUp::MaxAge { seconds } => pending
+            .iter()
+            .filter(|e| match entry_timestamp(e) {
+                Some(date) => date >= now - *seconds as i64,
+                None => false,
+            })
+            .map(|e| &e.id)
+            .collect(),
+    };
+
+    if !publish.is_empty() {
+        info!("{} entries will be caught up on next tick", publish.len());
+    }
+
+    // New entries are kept in the feed order, before the known ones
+    let mut snapshot: Vec<String> = entries
+        .iter()
+        .filter(|e| !known.contains(&e.id) && !publish.contains(&&e.id))
+        .map(|e| e.id.clone())
+        .collect();
+    snapshot.extend(known);
+
+    snapshot
+}
+
+// Retrieves the date of an entry as a unix timestamp
+fn entry_timestamp(entry: &Entry) -> Option<i64> {
+    entry
+        .updated
+        .or(entry.published)
+        .map(|date| date.timestamp())
+}
+
+pub fn now_timestamp() -> i64 {
+    match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(duration) => duration.as_secs() as i64,
+        Err(_) => 0,
+    }
+}
+
+// Current unix timestamp, in milliseconds
+fn now_timestamp_millis() -> i64 {
+    match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(duration) => duration.as_millis() as i64,
+        Err(_) => 0,
+    }
+}
+
+/// The errors that can occur while processing a feed.
+/// Errors are recorded for the feed and never stop the other feeds.
+#[derive(Debug, Clone, PartialEq)]
+pub enum JobError {
+    // The feed could not be fetched or parsed
+    Fetch(String),
+    // The content of an entry could not be built
+    Template { entry_id: String, message: String },
+    ProfileNotFound { profile_id: String },
+    InvalidKeys { profile_id: String },
+    // The profile has no active relay to publish to
+    NoActiveRelay { profile_id: String },
+    // The event could not be mined to the required difficulty
+    Pow { profile_id: String, message: String },
+}
+
+impl fmt::Display for JobError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JobError::Fetch(message) => write!(f, "Feed could not be fetched : {}", message),
+            JobError::Template { entry_id, message } => {
+                write!(
+                    f,
+                    "Content of entry {} could not be built : {}",
+                    entry_id, message
+                )
+            }
+            JobError::ProfileNotFound { profile_id } => {
+                write!(f, "Profile {} not found", profile_id)
+            }
+            JobError::InvalidKeys { profile_id } => {
+                write!(f, "Invalid private key found for profile {}", profile_id)
+            }
+            JobError::NoActiveRelay { profile_id } => {
+                write!(f, "No active relay found for profile {}", profile_id)
+            }
+            JobError::Pow {
+                profile_id,
+                message,
+            } => write!(
+                f,
+                "Event of profile {} could not be mined : {}",
+                profile_id, message
+            ),
+        }
+    }
+}
+
+impl JobError {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            JobError::Fetch(_) => "fetch",
+            JobError::Template { .. } => "template",
+            JobError::ProfileNotFound { .. } => "profile_not_found",
+            JobError::InvalidKeys { .. } => "invalid_keys",
+            JobError::NoActiveRelay { .. } => "no_active_relay",
+            JobError::Pow { .. } => "pow",
+        }
+    }
+
+    // Retrieves the id of the profile the error occured for, if any
+    pub fn profile_id(&self) -> Option<&str> {
+        match self {
+            JobError::ProfileNotFound { profile_id }
+            | JobError::InvalidKeys { profile_id }
+            | JobError::NoActiveRelay { profile_id }
+            | JobError::Pow { profile_id, .. } => Some(profile_id),
+            JobError::Fetch(_) | JobError::Template { .. } => None,
+        }
+    }
+
+    // Builds the record of the error to be retained in the store
+    pub fn to_record(&self, timestamp: i64) -> FeedError {
+        let (profile_id, entry_id) = match self {
+            JobError::Fetch(_) => (None, None),
+            JobError::Template { entry_id, .. } => (None, Some(entry_id.clone())),
+            JobError::ProfileNotFound { profile_id }
+            | JobError::InvalidKeys { profile_id }
+            | JobError::NoActiveRelay { profile_id }
+            | JobError::Pow { profile_id, .. } => (Some(profile_id.clone()), None),
+        };
+
+        FeedError {
+            timestamp,
+            kind: self.kind().to_string(),
+            profile_id,
+            entry_id,
+            message: self.to_string(),
+        }
+    }
+}
+
+/// The publications made while processing a feed.
+#[derive(Debug, Default)]
+pub struct PublishReport {
+    // The events sent, to be recorded into the publication ledger
+    pub ledger: Vec<Publication>,
+    // The events that did not reach enough relays, to be retried
+    pub outbox: Vec<OutboxEvent>,
+    // The errors that occured while processing the feed
+    pub errors: Vec<JobError>,
+    // The outcome of the processing of each entry
+    pub tick: TickReport,
+}
+
+impl PublishReport {
+    // Logs an error and records it into the report
+    pub fn add_error(&mut self, error: JobError) {
+        error!("{}", error);
+        self.errors.push(error);
+    }
+
+    // Records the failures of the profiles an entry failed to be processed for.
+    // The entry is processed for the remaining profiles and each failure is
+    // reported. Returns the profiles the action is left to be retried for.
+    pub fn add_profile_errors(
+        &mut self,
+        entry_id: &str,
+        failures: Vec<ProfileFailure>,
+        action: PendingAction,
+    ) -> Vec<PendingProfile> {
+        failures
+            .into_iter()
+            .filter_map(|failure| {
+                let pending = failure.error.profile_id().map(|profile_id| PendingProfile {
+                    profile_id: profile_id.to_string(),
+                    action,
+                    relays: failure.relays,
+                });
+
+                self.tick.fail(entry_id, failure.error.to_string());
+                self.add_error(failure.error);
+
+                pending
+            })
+            .collect()
+    }
+}
+
+/// The failure of a profile to send the events of an entry.
+#[derive(Debug)]
+pub struct ProfileFailure {
+    pub error: JobError,
+    // The relays the events could not be sent to, every relay of the profile if empty
+    pub relays: Vec<String>,
+}
+
+/// The events sent for an entry, along the profiles they could not be sent for.
+#[derive(Debug)]
+pub struct SentEvents<T> {
+    pub events: Vec<T>,
+    pub pending: Vec<PendingProfile>,
+}
+
+impl<T> SentEvents<T> {
+    // Tells if the events could not be sent for a profile
+    pub fn is_pending(&self, profile_id: &str) -> bool {
+        self.pending
+            .iter()
+            .any(|pending| pending.profile_id == profile_id)
+    }
+}
+
+/// The shared state used to publish the events of a feed.
+/// Each event sent through the context is recorded into its report.
+pub struct PublishContext<'a> {
+    pub feed: &'a Feed,
+    pub client: &'a Client,
+    pub profiles: &'a HashMap<String, Profile>,
+    pub dry_run: bool,
+    pub report: PublishReport,
+}
+
+impl<'a> PublishContext<'a> {
+    pub fn new(
+        feed: &'a Feed,
+        client: &'a Client,
+        profiles: &'a HashMap<String, Profile>,
+        dry_run: bool,
+    ) -> Self {
+        Self {
+            feed,
+            client,
+            profiles,
+            // The events of a feed in shadow mode are never sent
+            dry_run: dry_run || feed.shadow,
+            report: PublishReport::default(),
+        }
+    }
+}
+
+/// An event that would be published for an entry, built but not signed.
+#[derive(Debug, Clone)]
+pub struct EventPreview {
+    pub entry_id: String,
+    pub profile_id: String,
+    // The minimum proof of work difficulty the event would be mined with
+    pub pow_level: u8,
+    pub event: Result<UnsignedEvent, JobError>,
+}
+
+pub struct RssNostrJob {}
+
+impl RssNostrJob {
+    pub async fn _client_prepare(
+        _client: MutexGuard<'_, Client>,
+        _profile: MutexGuard<'_, Profile>,
+    ) {
+    }
+
+    pub async fn _client_clean(_client: Client) {}
+
+    // Processes the entries of a feed and returns the publications made.
+    // An entry that fails is recorded into the report and retried on next tick,
+    // the processing goes on with the remaining entries.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn process(
+        feed: Feed,
+        profile_ids: Vec<String>,
+        entries: Vec<Entry>,
+        map: &mut Vec<String>,
+        records: &mut HashMap<String, EntryRecord>,
+        client: &Client,
+        profiles: &HashMap<String, Profile>,
+        app_config: &AppConfig,
+    ) -> PublishReport {
+        let mut ctx = PublishContext::new(&feed, client, profiles, app_config.dry_run);
+
+        if feed.mirror_deletions {
+            for entry_id in Self::get_retracted_entries(&entries, map) {
+                let Some(record) = records.get_mut(&entry_id) else {
+                    continue;
+                };
+
+                if record.events.is_empty() {
+                    continue;
+                }
+
+                info!(
+                    "Entry {} has been retracted from feed with id {}. Deleting its events.",
+                    entry_id, &feed.id
+                );
+
+                let deleted = Self::delete_entry_events(&mut ctx, &entry_id, record).await;
+
+                // Events are only deleted once. The events of the profiles
+                // the deletion failed for are kept so it is retried on next tick.
+                record
+                    .events
+                    .retain(|event| deleted.is_pending(&event.profile_id));
+
+                if deleted.pending.is_empty() {
+                    ctx.report.tick.deleted.push(entry_id);
+                }
+            }
+        }
+
+        for entry in entries {
+            let entry_id = &entry.id;
+            let fingerprint = Self::get_entry_fingerprint(&entry);
+
+            match &map.contains(entry_id) {
+                true => match records.get(entry_id) {
+                    Some(record) if record.is_outdated_by(&fingerprint) => {
+                        info!(
+                            "Entry {} on feed with id {} has been updated. Applying {:?} policy.",
+                            entry_id, &feed.id, &feed.on_update
+                        );
+
+                        // The entry is not published yet for the profiles pending
+                        // a publication, they get the updated entry on retry
+                        let record = record.clone();
+                        let pending =
+                            Self::get_pending(&record, &profile_ids, PendingAction::Publish);
+                        let update_ids: Vec<String> = profile_ids
+                            .iter()
+                            .filter(|profile_id| {
+                                !Self::is_pending(&record, profile_id, PendingAction::Publish)
+                            })
+                            .cloned()
+                            .collect();
+
+                        let sent =
+                            match Self::process_update(&mut ctx, &entry, &update_ids, &record, &[])
+                                .await
+                            {
+                                Ok(sent) => sent,
+                                Err(e) => {
+                                    // The previous record is kept so the update is retried
+                                    ctx.report.tick.fail(entry_id, e.to_string());
+                                    ctx.report.add_error(e);
+                                    continue;
+                                }
+                            };
+
+                        ctx.report.tick.updated.push(entry_id.clone());
+                        records.insert(
+                            entry_id.clone(),
+                            EntryRecord {
+                                events: sent.events,
+                                pending: [pending, sent.pending].concat(),
+                                ..fingerprint
+                            },
+                        );
+                    }
+                    Some(record) if !record.pending.is_empty() => {
+                        info!(
+                            "Entry {} on feed with id {} is pending for {} profiles, retrying.",
+                            entry_id,
+                            &feed.id,
+                            record.pending.len()
+                        );
+
+                        let record = record.clone();
+                        let sent =
+                            match Self::process_pending(&mut ctx, &entry, &profile_ids, &record)
+                                .await
+                            {
+                                Ok(sent) => sent,
+                                Err(e) => {
+                                    ctx.report.tick.fail(entry_id, e.to_string());
+                                    ctx.report.add_error(e);
+                                    continue;
+                                }
+                            };
+
+                        if sent.pending.is_empty() {
+                            ctx.report.tick.published.push(entry_id.clone());
+                        }
+
+                        records.insert(
+                            entry_id.clone(),
+                            EntryRecord {
+                                events: sent.events,
+                                pending: sent.pending,
+                                ..record
+                            },
+                        );
+                    }
+                    Some(_) => {
+                        debug!(
+                            "Found entry for {} on feed with id {}, skipping publication.",
+                            entry_id, &feed.id
+                        );
+                        ctx.report.tick.skipped += 1;
+                    }
+                    None => {
+                        // Entries known before their content was recorded,
+                        // e.g: from the initial snapshot, are only fingerprinted.
+                        records.insert(entry_id.clone(), fingerprint);
+                        ctx.report.tick.skipped += 1;
+                    }
+                },
+                false => {
+                    debug!(
+                        "Entry not found for {} on feed with id {}, publishing...",
+                        entry_id, &feed.id
+                    );
+
+                    let sent = match Self::publish_entry(&mut ctx, &entry, &profile_ids, &[]).await
+                    {
+                        // The entry is not marked as seen when it could not be
+                        // published for any profile, so it is retried on next tick
+                        Ok(sent)
+                            if !profile_ids.is_empty()
+                                && sent.pending.len() >= profile_ids.len() =>
+                        {
+                            continue;
+                        }
+                        Ok(sent) => sent,
+                        Err(e) => {
+                            ctx.report.tick.fail(entry_id, e.to_string());
+                            ctx.report.add_error(e);
+                            continue;
+                        }
+                    };
+
+                    // The entry is marked as seen and retried on next ticks
+                    // for the profiles it could not be published for
+                    ctx.report.tick.published.push(entry_id.clone());
+                    records.insert(
+                        entry_id.clone(),
+                        EntryRecord {
+                            events: sent.events,
+                            pending: sent.pending,
+                            ..fingerprint
+                        },
+                    );
+                    map.insert(0, entry.id);
+                }
+            }
+        }
+
+        ctx.report
+    }
+
+    // Applies the `on_update` policy of the feed to an edited entry for the provided profiles.
+    // Returns the events that represent the entry after the update, along the
+    // profiles the update could not be applied for.
+    // The events of the profiles that are retried are only sent to their `pending` relays.
+    async fn process_update(
+        ctx: &mut PublishContext<'_>,
+        entry: &Entry,
+        profile_ids: &[String],
+        record: &EntryRecord,
+        pending: &[PendingProfile],
+    ) -> Result<SentEvents<PublishedEvent>, JobError> {
+        let feed = ctx.feed;
+
+        match feed.on_update {
+            OnUpdate::Ignore => Ok(SentEvents {
+                events: record.events.clone(),
+                pending: Vec::new(),
+            }),
+            OnUpdate::Correction => {
+                let message = TemplateProcessor::parse(feed.clone(), entry.clone())
+                    .map_err(|e| Self::template_error(entry, e))?;
+
+                let mut failures = Vec::new();
+
+                for profile_id in profile_ids {
+                    let relays = Self::get_pending_relays(pending, profile_id);
+                    let profile = match Self::get_profile(ctx.profiles, profile_id) {
+                        Ok(profile) => profile,
+                        Err(error) => {
+                            failures.push(ProfileFailure { error, relays });
+                            continue;
+                        }
+                    };
+
+                    let original_events: Vec<Tag> = Self::get_profile_events(record, profile_id)
+                        .into_iter()
+                        .map(Tag::event)
+                        .collect();
+
+                    // The correction replies to the first event published for the entry
+                    let Some(reply_to) = original_events.first() else {
+                        debug!(
+                            "No event published by profile {} for entry {}. Correction skipped.",
+                            profile_id, entry.id
+                        );
+                        continue;
+                    };
+
+                    let mut tags = vec![reply_to.clone()];
+                    tags.append(&mut Self::get_tags(&feed.tags));
+                    tags.push(Self::get_nip48(entry.id.clone()));
+
+                    let builder = EventBuilder::new(Kind::TextNote, &message, tags);
+                    let (_, failure) =
+                        Self::send_event(ctx, profile, &entry.id, builder, &relays).await;
+                    failures.extend(failure);
+                }
+
+                // Corrections refer to the original events, which are kept
+                Ok(SentEvents {
+                    events: record.events.clone(),
+                    pending: ctx.report.add_profile_errors(
+                        &entry.id,
+                        failures,
+                        PendingAction::Update,
+                    ),
+                })
+            }
+            OnUpdate::Replace => match feed.kind {
+                FeedKind::Article => {
+                    let sent = Self::publish_entry(ctx, entry, profile_ids, pending).await?;
+
+                    // Replaced events are kept so they are deleted along the entry
+                    Ok(SentEvents {
+                        events: [record.events.clone(), sent.events].concat(),
+                        pending: sent.pending,
+                    })
+                }
+                FeedKind::Note => {
+                    warn!(
+                        "Notes of feed {} are not replaceable. Update of entry {} ignored.",
+                        feed.id, entry.id
+                    );
+                    Ok(SentEvents {
+                        events: record.events.clone(),
+                        pending: Vec::new(),
+                    })
+                }
+            },
+            OnUpdate::DeleteAndRepost => {
+                let deleted = Self::delete_entry_events(ctx, &entry.id, record).await;
+
+                // The entry is only reposted for the profiles its events were deleted for.
+                // Reposted events are new events, sent to every relay of the profile.
+                let repost_ids: Vec<String> = profile_ids
+                    .iter()
+                    .filter(|profile_id| !deleted.is_pending(profile_id))
+                    .cloned()
+                    .collect();
+                let sent = Self::publish_entry(ctx, entry, &repost_ids, &[]).await?;
+
+                let mut events: Vec<PublishedEvent> = record
+                    .events
+                    .iter()
+                    .filter(|event| deleted.is_pending(&event.profile_id))
+                    .cloned()
+                    .collect();
+                events.extend(sent.events);
+
+                Ok(SentEvents {
+                    events,
+                    pending: [deleted.pending, sent.pending].concat(),
+                })
+            }
+        }
+    }
+
+    // Sends an entry again for the profiles it is pending for, if they still are profiles
+    // of the feed. Returns the events of the entry along the profiles still pending.
+    async fn process_pending(
+        ctx: &mut PublishContext<'_>,
+        entry: &Entry,
+        profile_ids: &[String],
+        record: &EntryRecord,
+    ) -> Result<SentEvents<PublishedEvent>, JobError> {
+        let ids = |action| -> Vec<String> {
+            Self::get_pending(record, profile_ids, action)
+                .into_iter()
+                .map(|pending| pending.profile_id)
+                .collect()
+        };
+        let (publish_ids, update_ids) = (ids(PendingAction::Publish), ids(PendingAction::Update));
+
+        let published = Self::publish_entry(ctx, entry, &publish_ids, &record.pending).await?;
+
+        // The update is only applied to the events of the pending profiles
+        let (updated_events, events): (Vec<PublishedEvent>, Vec<PublishedEvent>) = record
+            .events
+            .iter()
+            .cloned()
+            .partition(|event| update_ids.contains(&event.profile_id));
+        let updated = Self::process_update(
+            ctx,
+            entry,
+            &update_ids,
+            &EntryRecord {
+                events: updated_events,
+                ..record.clone()
+            },
+            &record.pending,
+        )
+        .await?;
+
+        Ok(SentEvents {
+            events: [events, updated.events, published.events].concat(),
+            pending: [published.pending, updated.pending].concat(),
+        })
+    }
+
+    // Requests the deletion (NIP-09) of the events published for an entry.
+    // Each deletion is signed by the profile that published the events and
+    // sent to its relays. Returns the ids of the deletion events sent, along
+    // the profiles the deletion could not be sent for.
+    pub async fn delete_entry_events(
+        ctx: &mut PublishContext<'_>,
+        entry_id: &str,
+        record: &EntryRecord,
+    ) -> SentEvents<EventId> {
+        let mut profile_ids: Vec<&String> = record
+            .events
+            .iter()
+            .map(|event| &event.profile_id)
+            .collect();
+        profile_ids.sort();
+        profile_ids.dedup();
+
+        let mut deletion_ids = Vec::new();
+        let mut failures = Vec::new();
+
+        for profile_id in &profile_ids {
+            let profile = match Self::get_profile(ctx.profiles, profile_id) {
+                Ok(profile) => profile,
+                Err(error) => {
+                    failures.push(ProfileFailure {
+                        error,
+                        relays: Vec::new(),
+                    });
+                    continue;
+                }
+            };
+
+            let event_ids = Self::get_profile_events(record, profile_id);
+
+            if event_ids.is_empty() {
+                continue;
+            }
+
+            let builder = EventBuilder::delete(event_ids);
+            let (mut ids, failure) = Self::send_event(ctx, profile, entry_id, builder, &[]).await;
+            deletion_ids.append(&mut ids);
+
+            // Deletions are sent again to every relay of the profile on retry
+            if let Some(failure) = failure {
+                failures.push(ProfileFailure {
+                    relays: Vec::new(),
+                    ..failure
+                });
+            }
+        }
+
+        SentEvents {
+            events: deletion_ids,
+            pending: ctx
+                .report
+                .add_profile_errors(entry_id, failures, PendingAction::Update),
+        }
+    }
+
+    // Retrieves the known entries that have been removed from the feed.
+    // Entries are only considered retracted when an older known entry is
+    // still present in the feed, so entries leaving the bottom of the
+    // feed as new ones are published are not mistaken for retractions.
+    fn get_retracted_entries(entries: &[Entry], map: &[String]) -> Vec<String> {
+        let present: Vec<&String> = entries.iter().map(|entry| &entry.id).collect();
+
+        let oldest_present = match map.iter().rposition(|id| present.contains(&id)) {
+            Some(idx) => idx,
+            None => return Vec::new(),
+        };
+
+        map[..oldest_present]
+            .iter()
+            .filter(|id| !present.contains(id))
+            .cloned()
+            .collect()
+    }
+
+    // Publishes an entry for each of the provided profiles. The events of the profiles
+    // that are retried are only sent to their `pending` relays.
+    // Returns the published events, along the profiles the entry could not be published for.
+    async fn publish_entry(
+        ctx: &mut PublishContext<'_>,
+        entry: &Entry,
+        profile_ids: &[String],
+        pending: &[PendingProfile],
+    ) -> Result<SentEvents<PublishedEvent>, JobError> {
+        let feed = ctx.feed;
+
+        let (kind, message, content_tags) =
+            Self::get_content(feed, entry).map_err(|e| Self::template_error(entry, e))?;
+
+        let mut events = Vec::new();
+        let mut failures = Vec::new();
+
+        for profile_id in profile_ids {
+            let relays = Self::get_pending_relays(pending, profile_id);
+            let profile = match Self::get_profile(ctx.profiles, profile_id) {
+                Ok(profile) => profile,
+                Err(error) => {
+                    failures.push(ProfileFailure { error, relays });
+                    continue;
+                }
+            };
+
+            let tags = Self::get_entry_tags(ctx.profiles, feed, profile, entry, &content_tags);
+            let builder = EventBuilder::new(kind, &message, tags);
+            let sent = ctx.report.ledger.len();
+
+            // The events sent before a failure are kept, so they are not sent again on retry
+            let (event_ids, failure) =
+                Self::send_event(ctx, profile, &entry.id, builder, &relays).await;
+            failures.extend(failure);
+
+            // Only entries acknowledged by a relay are counted
+            if ctx.report.ledger[sent..]
+                .iter()
+                .any(|publication| !publication.relays.is_empty())
+            {
+                METRICS
+                    .entries_published
+                    .with_label_values(&[&feed.id, profile_id])
+                    .inc();
+            }
+
+            for event_id in event_ids {
+                events.push(PublishedEvent {
+                    profile_id: profile_id.clone(),
+                    event_id: event_id.to_hex(),
+                });
+            }
+        }
+
+        Ok(SentEvents {
+            events,
+            pending: ctx
+                .report
+                .add_profile_errors(&entry.id, failures, PendingAction::Publish),
+        })
+    }
+
+    // Builds the events that would be published for the entries of a feed,
+    // without signing nor sending them. Errors are reported for each event.
+    pub fn preview(
+        feed: &Feed,
+        entries: &[Entry],
+        profile_ids: &[String],
+        profiles: &HashMap<String, Profile>,
+    ) -> Vec<EventPreview> {
+        let mut previews = Vec::new();
+
+        for entry in entries {
+            let content =
+                Self::get_content(feed, entry).map_err(|e| Self::template_error(entry, e));
+
+            for profile_id in profile_ids {
+                let profile = Self::get_profile(profiles, profile_id);
+                let pow_level = profile
+                    .as_ref()
+                    .map(|p| p.pow_level)
+                    .unwrap_or_default()
+                    .max(feed.pow_level);
+
+                let event = content.clone().and_then(|(kind, message, content_tags)| {
+                    let profile = profile?;
+                    let keys = Keys::parse(profile.private_key.as_str()).map_err(|_| {
+                        JobError::InvalidKeys {
+                            profile_id: profile.id.clone(),
+                        }
+                    })?;
+
+                    let tags = Self::get_entry_tags(profiles, feed, profile, entry, &content_tags);
+                    let builder = EventBuilder::new(kind, &message, tags);
+
+                    Ok(builder.to_unsigned_event(keys.public_key()))
+                });
+
+                previews.push(EventPreview {
+                    entry_id: entry.id.clone(),
+                    profile_id: profile_id.clone(),
+                    pow_level,
+                    event,
+                });
+            }
+        }
+
+        previews
+    }
+
+    // Signs an event with the keys of the profile and sends it to the active
+    // relays of the profile. The event is mined once per difficulty required
+    // by the relays. Each event is recorded into the ledger of the context
+    // with the relays that acknowledged it, and queued into the outbox
+    // if not enough relays acknowledged it. Only the provided `relays` of the
+    // profile are targeted, every active relay of the profile if empty.
+    // Returns the ids of the events sent or queued, along the failure of the
+    // profile if the events could not be sent to some of its relays.
+    async fn send_event(
+        ctx: &mut PublishContext<'_>,
+        profile: &Profile,
+        entry_id: &str,
+        builder: EventBuilder,
+        relays: &[String],
+    ) -> (Vec<EventId>, Option<ProfileFailure>) {
+        let failure = |error| ProfileFailure {
+            error,
+            relays: relays.to_vec(),
+        };
+
+        let keys = match Keys::parse(profile.private_key.as_str()) {
+            Ok(keys) => keys,
+            Err(_) => {
+                let error = JobError::InvalidKeys {
+                    profile_id: profile.id.clone(),
+                };
+                return (Vec::new(), Some(failure(error)));
+            }
+        };
+
+        // Events of a profile are only sent to its own relays
+        let active_relays: Vec<Relay> =
+            Self::get_active_relays(&Self::get_profile_relays(ctx.profiles, profile))
+                .into_iter()
+                .filter(|relay| relays.is_empty() || relays.contains(&relay.target))
+                .collect();
+
+        // The entry is reported as failed so it is retried on next tick
+        if active_relays.is_empty() {
+            let error = JobError::NoActiveRelay {
+                profile_id: profile.id.clone(),
+            };
+            return (Vec::new(), Some(failure(error)));
+        }
+
+        let min_pow_level = profile.pow_level.max(ctx.feed.pow_level);
+        let relays_by_pow_level = Self::get_relays_by_pow_level(&active_relays, min_pow_level);
+
+        let mut event_ids = Vec::new();
+        let mut pow_error = None;
+        let mut failed_relays = Vec::new();
+
+        for (pow_level, targets) in relays_by_pow_level {
+            let mining = Instant::now();
+            let event = match builder.clone().to_pow_event(&keys, pow_level) {
+                Ok(event) => event,
+                Err(e) => {
+                    // The event is still sent to the relays of the other difficulties,
+                    // only the relays of this difficulty are retried
+                    pow_error.get_or_insert(JobError::Pow {
+                        profile_id: profile.id.clone(),
+                        message: e.to_string(),
+                    });
+                    failed_relays.extend(targets);
+                    continue;
+                }
+            };
+            METRICS
+                .pow_duration
+                .with_label_values(&[&pow_level.to_string()])
+                .observe(mining.elapsed().as_secs_f64());
+
+            let acked = match ctx.dry_run {
+                true => {
+                    log::info!("dry-mode on : {:?}", event.as_json());
+                    Self::write_shadow_event(ctx.feed, &event);
+                    Vec::new()
+                }
+                false => {
+                    let results = Self::send_to_relays(ctx.client, &targets, &event).await;
+                    Self::get_acked_relays(&targets, &results)
+                }
+            };
+
+            let now = now_timestamp();
+            let publication = Publication {
+                feed_id: ctx.feed.id.clone(),
+                entry_id: entry_id.to_string(),
+                profile_id: profile.id.clone(),
+                event_id: event.id.to_hex(),
+                kind: event.kind.as_u64(),
+                status: Publication::get_status(&targets, &acked, ctx.dry_run),
+                targets,
+                relays: acked,
+                timestamp: now,
+            };
+
+            match publication.status {
+                PublicationStatus::Failed => log::error!(
+                    "Error publishing entry : no relay acknowledged event {}",
+                    event.id
+                ),
+                PublicationStatus::DryRun => {}
+                _ => log::info!(
+                    "Entry published with id {} on {}/{} relays",
+                    event.id,
+                    publication.relays.len(),
+                    publication.targets.len()
+                ),
+            }
+
+            let queued = match ctx.dry_run {
+                true => None,
+                false => OutboxEvent::new(
+                    &publication.feed_id,
+                    entry_id,
+                    &profile.id,
+                    &publication.event_id,
+                    event.as_json(),
+                    &publication.targets,
+                    &publication.relays,
+                    now,
+                ),
+            };
+
+            if let Some(outbox_event) = queued {
+                warn!(
+                    "Event {} acknowledged by {}/{} required relays. Queued for retry.",
+                    event.id,
+                    publication.relays.len(),
+                    outbox_event.required_acks
+                );
+                ctx.report.outbox.push(outbox_event);
+                event_ids.push(event.id);
+            } else if publication.is_sent() {
+                event_ids.push(event.id);
+            }
+
+            ctx.report.ledger.push(publication);
+        }
+
+        let failure = pow_error.map(|error| ProfileFailure {
+            error,
+            relays: failed_relays,
+        });
+
+        (event_ids, failure)
+    }
+
+    // Appends an event to the shadow file of a feed in shadow mode, one JSON event per line
+    fn write_shadow_event(feed: &Feed, event: &Event) {
+        let Some(path) = feed.shadow_file.as_ref().filter(|_| feed.shadow) else {
+            return;
+        };
+
+        let result = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .and_then(|mut file| writeln!(file, "{}", event.as_json()));
+
+        if let Err(e) = result {
+            error!(
+                "Error writing event of feed {} to shadow file {} : {}",
+                feed.id, path, e
+            );
+        }
+    }
+
+    // Sends an event to each target relay concurrently.
+    // Returns the result of the sending for each relay, indexed by relay url.
+    pub async fn send_to_relays(
+        client: &Client,
+        targets: &[String],
+        event: &Event,
+    ) -> HashMap<String, Result<(), String>> {
+        let mut results = HashMap::new();
+        let mut sendings = JoinSet::new();
+
+        for target in targets {
+            let relay = match client.relay(target.as_str()).await {
+                Ok(relay) => relay,
+                Err(e) => {
+                    log::error!("Relay {} not found in pool : {}", target, e);
+                    METRICS
+                        .relay_send_failures
+                        .with_label_values(&[target])
+                        .inc();
+                    results.insert(target.clone(), Err(e.to_string()));
+                    continue;
+                }
+            };
+
+            let target = target.clone();
+            let event = event.clone();
+            sendings.spawn(async move {
+                let result = relay
+                    .send_event(event, RelaySendOptions::new())
+                    .await
+                    .map(|_| ())
+                    .map_err(|e| e.to_string());
+
+                if let Err(e) = &result {
+                    log::error!("Error publishing entry on {} : {}", target, e);
+                    METRICS
+                        .relay_send_failures
+                        .with_label_values(&[&target])
+                        .inc();
+                }
 
-    job
-}
+                (target, result)
+            });
+        }
 
-// Retrieves a feed and returns a vec of ids for the feed.
-// This method is used to provide initial snapshot of the rss feeds
-// In order to avoid to spam relays with initial rss feed fetch.
-pub async fn feed_snapshot(feed: Feed) -> Vec<String> {
-    let mut entries_snapshot = Vec::new();
-    match RssParser::get_items(feed.url.to_string()).await {
-        Ok(entries) => {
-            for entry in entries {
-                entries_snapshot.push(entry.id)
+        while let Some(sending) = sendings.join_next().await {
+            if let Ok((target, result)) = sending {
+                results.insert(target, result);
             }
         }
-        Err(_) => {
-            error!(
-                "Error while parsing RSS stream for feed with {} id. Skipping initial snapshot",
-                feed.id
-            );
-        }
-    };
 
-    entries_snapshot
-}
+        results
+    }
 
-pub struct RssNostrJob {}
+    // Retrieves the relays that acknowledged an event, in the order of the targets
+    pub fn get_acked_relays(
+        targets: &[String],
+        results: &HashMap<String, Result<(), String>>,
+    ) -> Vec<String> {
+        targets
+            .iter()
+            .filter(|target| matches!(results.get(*target), Some(Ok(_))))
+            .cloned()
+            .collect()
+    }
 
-impl RssNostrJob {
-    pub async fn _client_prepare(
-        _client: MutexGuard<'_, Client>,
-        _profile: MutexGuard<'_, Profile>,
-    ) {
+    fn get_profile<'a>(
+        profiles_lock: &'a HashMap<String, Profile>,
+        profile_id: &str,
+    ) -> Result<&'a Profile, JobError> {
+        profiles_lock
+            .get(profile_id)
+            .ok_or_else(|| JobError::ProfileNotFound {
+                profile_id: profile_id.to_string(),
+            })
     }
 
-    pub async fn _client_clean(_client: Client) {}
+    fn template_error(entry: &Entry, error: TemplateParserError) -> JobError {
+        JobError::Template {
+            entry_id: entry.id.clone(),
+            message: error.to_string(),
+        }
+    }
 
-    pub async fn process(
-        feed: Feed,
-        profile_ids: Vec<String>,
-        entries: Vec<Entry>,
-        map: &mut Vec<String>,
-        client: MutexGuard<'_, Client>,
-        profiles_lock: MutexGuard<'_, HashMap<String, Profile>>,
-        app_config_lock: MutexGuard<'_, AppConfig>,
-    ) {
-        for entry in entries {
-            let entry_id = &entry.id;
+    // Retrieves the relays of a profile, falling back to the default relays
+    fn get_profile_relays(
+        profiles_lock: &HashMap<String, Profile>,
+        profile: &Profile,
+    ) -> Vec<Relay> {
+        let default_relays = match profiles_lock.get("default") {
+            Some(default_profile) => default_profile.relays.clone(),
+            None => Vec::new(),
+        };
 
-            match &map.contains(entry_id) {
-                true => {
-                    debug!(
-                        "Found entry for {} on feed with id {}, skipping publication.",
-                        entry_id, &feed.id
-                    );
-                }
-                false => {
-                    debug!(
-                        "Entry not found for {} on feed with id {}, publishing...",
-                        entry_id, &feed.id
-                    );
+        profile.get_publishing_relays(&default_relays)
+    }
 
-                    let message = match TemplateProcessor::parse(feed.clone(), entry.clone()) {
-                        Ok(message) => message,
-                        Err(e) => {
-                            // make tick fail in non-critical way
-                            error!("{}", e);
-                            return;
-                        }
-                    };
+    // Retrieves the profiles of the feed an entry is pending for, for an action
+    fn get_pending(
+        record: &EntryRecord,
+        profile_ids: &[String],
+        action: PendingAction,
+    ) -> Vec<PendingProfile> {
+        record
+            .pending
+            .iter()
+            .filter(|pending| pending.action == action && profile_ids.contains(&pending.profile_id))
+            .cloned()
+            .collect()
+    }
 
-                    for profile_id in &profile_ids {
-                        let mut tags = Self::get_tags(&feed.tags);
+    // Retrieves the relays an entry is pending on for a profile, every relay if empty
+    fn get_pending_relays(pending: &[PendingProfile], profile_id: &str) -> Vec<String> {
+        pending
+            .iter()
+            .find(|pending| pending.profile_id == profile_id)
+            .map(|pending| pending.relays.clone())
+            .unwrap_or_default()
+    }
 
-                        // Declare NIP-48.
-                        tags.push(Self::get_nip48(entry.id.clone()));
+    // Tells if an entry is pending for a profile, for an action
+    fn is_pending(record: &EntryRecord, profile_id: &str, action: PendingAction) -> bool {
+        record
+            .pending
+            .iter()
+            .any(|pending| pending.action == action && pending.profile_id == profile_id)
+    }
 
-                        let profile = profiles_lock.get(profile_id);
+    // Retrieves the ids of the events published by a profile for an entry
+    fn get_profile_events(record: &EntryRecord, profile_id: &str) -> Vec<EventId> {
+        record
+            .events
+            .iter()
+            .filter(|event| event.profile_id == profile_id)
+            .filter_map(|event| EventId::from_hex(&event.event_id).ok())
+            .collect()
+    }
 
-                        if profile.is_none() {
-                            error!(
-                                "Profile {} for stream {} not found. Job skipped.",
-                                profile_id, feed.name
-                            );
-                            return;
-                        }
+    // Builds the fingerprint used to detect the edition of an entry
+    fn get_entry_fingerprint(entry: &Entry) -> EntryRecord {
+        let content = [
+            entry.title.as_ref().map(|t| t.content.clone()),
+            entry.summary.as_ref().map(|s| s.content.clone()),
+            entry.content.as_ref().and_then(|c| c.body.clone()),
+            entry.links.first().map(|l| l.href.clone()),
+        ]
+        .map(|part| part.unwrap_or_default())
+        .join("\n");
 
-                        let profile = profile.unwrap();
+        EntryRecord {
+            hash: format!("{:x}", md5::compute(content)),
+            updated: entry.updated.map(|date| date.timestamp()),
+            events: Vec::new(),
+            pending: Vec::new(),
+        }
+    }
 
-                        let keys = match Keys::parse(profile.private_key.as_str()) {
-                            Ok(val) => val,
-                            Err(e) => {
-                                println!("{:?}", e);
-                                // warn!("Invalid private key found for Nostr. Generating random keys...");
-                                panic!("Invalid private key found. This should not happen.");
-                            }
-                        };
+    // Builds the content of the event to publish for an entry,
+    // along with its kind and the tags specific to that kind.
+    fn get_content(
+        feed: &Feed,
+        entry: &Entry,
+    ) -> Result<(Kind, String, Vec<Tag>), TemplateParserError> {
+        match feed.kind {
+            FeedKind::Note => {
+                let message = TemplateProcessor::parse(feed.clone(), entry.clone())?;
+                Ok((Kind::TextNote, message, Vec::new()))
+            }
+            FeedKind::Article => {
+                let article = Article::from(entry);
+                Ok((
+                    Kind::LongFormTextNote,
+                    article.content.clone(),
+                    article.get_tags(),
+                ))
+            }
+        }
+    }
 
-                        // _ = RssNostrJob::client_prepare(client,profile).await;
+    // Builds the tags of the event published by a profile for an entry
+    fn get_entry_tags(
+        profiles: &HashMap<String, Profile>,
+        feed: &Feed,
+        profile: &Profile,
+        entry: &Entry,
+        content_tags: &[Tag],
+    ) -> Vec<Tag> {
+        let mut tags = content_tags.to_vec();
+        tags.append(&mut Self::get_tags(&feed.tags));
 
-                        let recommended_relays_ids =
-                            profile.recommended_relays.clone().unwrap_or(Vec::new());
-                        let mut recommended_relays_tags = Self::get_recommended_relays(
-                            recommended_relays_ids,
-                            &profile.relays.clone(),
-                        );
+        // Declare NIP-48.
+        tags.push(Self::get_nip48(entry.id.clone()));
 
-                        _ = &tags.append(&mut recommended_relays_tags);
-
-                        let event = EventBuilder::new(nostr_sdk::Kind::TextNote, &message, tags)
-                            .to_pow_event(&keys, profile.pow_level);
-
-                        match event {
-                            Ok(e) => {
-                                let dry_run_flag = app_config_lock.dry_run;
-
-                                match dry_run_flag {
-                                    true => {
-                                        log::info!("dry-mode on : {:?}", e.as_json());
-                                    }
-                                    false => match client.send_event(e).await {
-                                        Ok(event_id) => {
-                                            log::info!("Entry published with id {}", event_id)
-                                        }
-                                        Err(e) => log::error!("Error publishing entry : {}", e),
-                                    },
-                                }
-                            }
-                            Err(_) => panic!("Note couldn't be sent"),
-                        };
+        let relays = Self::get_profile_relays(profiles, profile);
 
-                        // _ = RssNostrJob::client_clean(client,profile).await;
-                    }
-                    map.insert(0, entry.id);
-                }
-            }
-        }
+        let recommended_relays_ids = profile.recommended_relays.clone().unwrap_or_default();
+        let mut recommended_relays_tags =
+            Self::get_recommended_relays(recommended_relays_ids, &relays);
+
+        tags.append(&mut recommended_relays_tags);
+
+        tags
     }
 
     fn get_tags(feed_tags: &Option<Vec<String>>) -> Vec<Tag> {
@@ -257,6 +1570,29 @@ impl RssNostrJob {
         }
     }
 
+    fn get_active_relays(relays: &[Relay]) -> Vec<Relay> {
+        relays
+            .iter()
+            .filter(|relay| relay.active)
+            .cloned()
+            .collect()
+    }
+
+    // Groups the relays targets by the pow difficulty to publish to them.
+    // The difficulty of a relay is the maximum of its own level and `min_pow_level`.
+    fn get_relays_by_pow_level(relays: &[Relay], min_pow_level: u8) -> BTreeMap<u8, Vec<String>> {
+        let mut relays_by_pow_level: BTreeMap<u8, Vec<String>> = BTreeMap::new();
+
+        for relay in relays {
+            relays_by_pow_level
+                .entry(relay.pow_level.max(min_pow_level))
+                .or_default()
+                .push(relay.target.clone());
+        }
+
+        relays_by_pow_level
+    }
+
     fn get_recommended_relays(recommended_relays_ids: Vec<String>, relays: &[Relay]) -> Vec<Tag> {
         let mut relay_tags = Vec::new();
         for relay_name in recommended_relays_ids {
@@ -284,6 +1620,93 @@ mod tests {
     #[test]
     fn test_nip_48_signal() {}
 
+    // Entries "a" and "b" are dated 1700000000 and 1700000100, "c" is undated
+    fn catch_up_entries() -> Vec<Entry> {
+        let rss = r#"<?xml version="1.0"?>
+            <rss version="2.0"><channel><title>test</title>
+            <item><guid>a</guid><title>a</title><pubDate>Tue, 14 Nov 2023 22:13:20 GMT</pubDate></item>
+            <item><guid>b</guid><title>b</title><pubDate>Tue, 14 Nov 2023 22:15:00 GMT</pubDate></item>
+            <item><guid>c</guid><title>c</title></item>
+            </channel></rss>"#;
+
+        feed_rs::parser::parse(rss.as_bytes()).unwrap().entries
+    }
+
+    #[test]
+    fn test_catch_up_none() {
+        let known = Some(["a".to_string()].to_vec());
+        let snapshot = catch_up_snapshot(
+            &CatchUp::None,
+            catch_up_entries(),
+            known,
+            Some(0),
+            1700000200,
+        );
+
+        assert_eq!(snapshot, ["b", "c", "a"].to_vec());
+    }
+
+    #[test]
+    fn test_catch_up_since_last_run() {
+        // Without stored state, every entry is marked as seen
+        let snapshot = catch_up_snapshot(
+            &CatchUp::SinceLastRun,
+            catch_up_entries(),
+            None,
+            None,
+            1700000200,
+        );
+        assert_eq!(snapshot, ["a", "b", "c"].to_vec());
+
+        // With stored state, entries dated after last run and undated new ones are published
+        let known = Some(["a".to_string()].to_vec());
+        let snapshot = catch_up_snapshot(
+            &CatchUp::SinceLastRun,
+            catch_up_entries(),
+            known,
+            Some(1700000050),
+            1700000200,
+        );
+        assert_eq!(snapshot, ["a"].to_vec());
+
+        // Entries dated before last run are not published
+        let known = Some(Vec::new());
+        let snapshot = catch_up_snapshot(
+            &CatchUp::SinceLastRun,
+            catch_up_entries(),
+            known,
+            Some(1700000050),
+            1700000200,
+        );
+        assert_eq!(snapshot, ["a"].to_vec());
+    }
+
+    #[test]
+    fn test_catch_up_max_entries() {
+        let snapshot = catch_up_snapshot(
+            &CatchUp::MaxEntries { count: 1 },
+            catch_up_entries(),
+            None,
+            None,
+            1700000200,
+        );
+
+        assert_eq!(snapshot, ["a", "c"].to_vec());
+    }
+
+    #[test]
+    fn test_catch_up_max_age() {
+        let snapshot = catch_up_snapshot(
+            &CatchUp::MaxAge { seconds: 150 },
+            catch_up_entries(),
+            None,
+            None,
+            1700000200,
+        );
+
+        assert_eq!(snapshot, ["a", "c"].to_vec());
+    }
+
     #[test]
     fn test_get_tags() {
         let relay_ids = ["test".to_string()].to_vec();
@@ -344,4 +1767,672 @@ mod tests {
         assert_eq!(tag.as_vec()[0], "t");
         assert_eq!(tag.as_vec()[1], "ad");
     }
+
+    #[test]
+    fn test_relays_by_pow_level() {
+        let relay = |name: &str, active: bool, pow_level: u8| Relay {
+            name: name.to_string(),
+            target: format!("wss://{}.localhost", name),
+            active,
+            proxy: None,
+            pow_level,
+        };
+
+        let relays = [
+            relay("a", true, 0),
+            relay("b", true, 8),
+            relay("c", false, 0),
+            relay("d", true, 2),
+            relay("e", true, 8),
+        ];
+
+        let active_relays = RssNostrJob::get_active_relays(&relays);
+        assert_eq!(active_relays.len(), 4);
+        assert!(!active_relays.iter().any(|relay| relay.name == "c"));
+
+        let relays_by_pow_level = RssNostrJob::get_relays_by_pow_level(&active_relays, 2);
+
+        // Relays under the minimum difficulty are merged into its level
+        assert_eq!(relays_by_pow_level.len(), 2);
+        assert_eq!(
+            relays_by_pow_level[&2],
+            [
+                "wss://a.localhost".to_string(),
+                "wss://d.localhost".to_string()
+            ]
+            .to_vec()
+        );
+        assert_eq!(
+            relays_by_pow_level[&8],
+            [
+                "wss://b.localhost".to_string(),
+                "wss://e.localhost".to_string()
+            ]
+            .to_vec()
+        );
+    }
+
+    // A relay that is never connected, for the tests in dry run mode
+    fn active_relay() -> Relay {
+        Relay {
+            name: "test".to_string(),
+            target: "wss://relay.invalid".to_string(),
+            active: true,
+            proxy: None,
+            pow_level: 0,
+        }
+    }
+
+    fn update_entries(description: &str) -> Vec<Entry> {
+        let rss = format!(
+            r#"<?xml version="1.0"?>
+            <rss version="2.0"><channel><title>test</title>
+            <item><guid>a</guid><title>a</title><link>https://example.com/a</link><description>{}</description></item>
+            </channel></rss>"#,
+            description
+        );
+
+        feed_rs::parser::parse(rss.as_bytes()).unwrap().entries
+    }
+
+    #[test]
+    fn test_entry_fingerprint() {
+        let entry = &update_entries("first")[0];
+        let fingerprint = RssNostrJob::get_entry_fingerprint(entry);
+
+        assert_eq!(fingerprint, RssNostrJob::get_entry_fingerprint(entry));
+        assert!(
+            fingerprint.is_outdated_by(&RssNostrJob::get_entry_fingerprint(
+                &update_entries("edited")[0]
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_process_records_entries() {
+        dotenv::from_filename(".env.test").ok();
+
+        let feed = Feed {
+            id: "test".to_string(),
+            on_update: OnUpdate::DeleteAndRepost,
+            ..Default::default()
+        };
+
+        let mut profiles = HashMap::new();
+        profiles.insert(
+            "default".to_string(),
+            Profile {
+                relays: [active_relay()].to_vec(),
+                ..Default::default()
+            },
+        );
+
+        let client = Client::new(Keys::generate());
+        let app_config = AppConfig {
+            dry_run: true,
+            ..Default::default()
+        };
+
+        let profile_ids = ["default".to_string()].to_vec();
+
+        // Entries already known without record are only fingerprinted
+        let mut map = ["a".to_string()].to_vec();
+        let mut records = HashMap::new();
+
+        RssNostrJob::process(
+            feed.clone(),
+            profile_ids.clone(),
+            update_entries("first"),
+            &mut map,
+            &mut records,
+            &client,
+            &profiles,
+            &app_config,
+        )
+        .await;
+
+        let first_hash = records["a"].hash.clone();
+        assert_eq!(map, ["a".to_string()].to_vec());
+        assert_eq!(
+            records["a"],
+            RssNostrJob::get_entry_fingerprint(&update_entries("first")[0])
+        );
+
+        // An edited entry is processed and its record updated
+        RssNostrJob::process(
+            feed.clone(),
+            profile_ids.clone(),
+            update_entries("edited"),
+            &mut map,
+            &mut records,
+            &client,
+            &profiles,
+            &app_config,
+        )
+        .await;
+
+        assert_eq!(map.len(), 1);
+        assert_ne!(records["a"].hash, first_hash);
+    }
+
+    #[test]
+    fn test_retracted_entries() {
+        // Feed contains "a", "b" and "c" (newest first)
+        let entries = catch_up_entries();
+
+        // "x" was published between "a" and "b" and has been removed
+        let map = ["a", "x", "b", "c", "y"].map(String::from).to_vec();
+
+        // "y" is older than every entry of the feed, it left the feed naturally
+        assert_eq!(
+            RssNostrJob::get_retracted_entries(&entries, &map),
+            ["x".to_string()].to_vec()
+        );
+
+        let map = ["z"].map(String::from).to_vec();
+        assert!(RssNostrJob::get_retracted_entries(&entries, &map).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_process_reports_errors() {
+        dotenv::from_filename(".env.test").ok();
+
+        let feed = Feed {
+            id: "test".to_string(),
+            ..Default::default()
+        };
+
+        let mut profiles = HashMap::new();
+        profiles.insert(
+            "broken".to_string(),
+            Profile {
+                id: "broken".to_string(),
+                private_key: "invalid".to_string(),
+                ..Default::default()
+            },
+        );
+
+        let client = Client::new(Keys::generate());
+        let app_config = AppConfig {
+            dry_run: true,
+            ..Default::default()
+        };
+
+        let mut map = Vec::new();
+        let mut records = HashMap::new();
+
+        let report = RssNostrJob::process(
+            feed.clone(),
+            ["broken".to_string()].to_vec(),
+            update_entries("first"),
+            &mut map,
+            &mut records,
+            &client,
+            &profiles,
+            &app_config,
+        )
+        .await;
+
+        assert_eq!(
+            report.errors,
+            [JobError::InvalidKeys {
+                profile_id: "broken".to_string()
+            }]
+            .to_vec()
+        );
+
+        // The entry is not marked as seen so it is retried on next tick
+        assert!(map.is_empty());
+
+        let report = RssNostrJob::process(
+            feed,
+            ["unknown".to_string()].to_vec(),
+            update_entries("first"),
+            &mut map,
+            &mut records,
+            &client,
+            &profiles,
+            &app_config,
+        )
+        .await;
+
+        let record = report.errors[0].to_record(0);
+        assert_eq!(record.kind, "profile_not_found");
+        assert_eq!(record.profile_id, Some("unknown".to_string()));
+
+        // Entries of a profile without active relay are retried on next tick
+        profiles.insert(
+            "no_relay".to_string(),
+            Profile {
+                id: "no_relay".to_string(),
+                private_key: Keys::generate().secret_key().unwrap().to_secret_hex(),
+                ..Default::default()
+            },
+        );
+
+        let report = RssNostrJob::process(
+            Feed {
+                id: "test".to_string(),
+                ..Default::default()
+            },
+            ["no_relay".to_string()].to_vec(),
+            update_entries("first"),
+            &mut map,
+            &mut records,
+            &client,
+            &profiles,
+            &app_config,
+        )
+        .await;
+
+        assert_eq!(
+            report.errors,
+            [JobError::NoActiveRelay {
+                profile_id: "no_relay".to_string()
+            }]
+            .to_vec()
+        );
+        assert_eq!(report.tick.failed.len(), 1);
+        assert!(map.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_process_continues_after_failure() {
+        dotenv::from_filename(".env.test").ok();
+
+        let feed = Feed {
+            id: "test".to_string(),
+            ..Default::default()
+        };
+
+        let mut profiles = HashMap::new();
+        profiles.insert(
+            "good".to_string(),
+            Profile {
+                id: "good".to_string(),
+                private_key: Keys::generate().secret_key().unwrap().to_secret_hex(),
+                relays: [active_relay()].to_vec(),
+                ..Default::default()
+            },
+        );
+
+        let client = Client::new(Keys::generate());
+        let app_config = AppConfig {
+            dry_run: true,
+            ..Default::default()
+        };
+
+        let mut map = ["a".to_string()].to_vec();
+        let mut records = HashMap::new();
+
+        // A missing profile does not prevent the other profiles from publishing
+        let report = RssNostrJob::process(
+            feed.clone(),
+            ["unknown".to_string(), "good".to_string()].to_vec(),
+            catch_up_entries(),
+            &mut map,
+            &mut records,
+            &client,
+            &profiles,
+            &app_config,
+        )
+        .await;
+
+        assert_eq!(report.tick.published, ["b", "c"].to_vec());
+        assert_eq!(report.tick.skipped, 1);
+        assert_eq!(report.tick.failed.len(), 2);
+        assert_eq!(report.tick.failed[0].entry_id, "b");
+        assert_eq!(
+            report.tick.failed[0].reason,
+            JobError::ProfileNotFound {
+                profile_id: "unknown".to_string()
+            }
+            .to_string()
+        );
+        assert_eq!(map, ["c", "b", "a"].to_vec());
+
+        // Entries failing for every profile are reported and retried on next tick
+        let mut map = ["a".to_string()].to_vec();
+        let report = RssNostrJob::process(
+            feed,
+            ["unknown".to_string()].to_vec(),
+            catch_up_entries(),
+            &mut map,
+            &mut records,
+            &client,
+            &profiles,
+            &app_config,
+        )
+        .await;
+
+        assert!(report.tick.published.is_empty());
+        assert_eq!(report.tick.failed.len(), 2);
+        assert_eq!(report.errors.len(), 2);
+        assert_eq!(map, ["a"].to_vec());
+    }
+
+    #[tokio::test]
+    async fn test_process_retries_pending_profiles() {
+        dotenv::from_filename(".env.test").ok();
+
+        let feed = Feed {
+            id: "test".to_string(),
+            ..Default::default()
+        };
+
+        let profile = |id: &str| Profile {
+            id: id.to_string(),
+            private_key: Keys::generate().secret_key().unwrap().to_secret_hex(),
+            relays: [active_relay()].to_vec(),
+            ..Default::default()
+        };
+
+        let mut profiles = HashMap::new();
+        profiles.insert("good".to_string(), profile("good"));
+
+        let client = Client::new(Keys::generate());
+        let app_config = AppConfig {
+            dry_run: true,
+            ..Default::default()
+        };
+
+        let profile_ids = ["late".to_string(), "good".to_string()].to_vec();
+        let mut map = ["a".to_string()].to_vec();
+        let mut records = HashMap::new();
+
+        let report = RssNostrJob::process(
+            feed.clone(),
+            profile_ids.clone(),
+            catch_up_entries(),
+            &mut map,
+            &mut records,
+            &client,
+            &profiles,
+            &app_config,
+        )
+        .await;
+        assert_eq!(report.ledger.len(), 2);
+
+        // The entry is seen and pending for the profile it failed for
+        assert_eq!(map, ["c", "b", "a"].to_vec());
+        assert_eq!(
+            records["b"].pending,
+            [PendingProfile {
+                profile_id: "late".to_string(),
+                action: PendingAction::Publish,
+                relays: Vec::new(),
+            }]
+            .to_vec()
+        );
+
+        // Only the pending profile publishes the entry on retry
+        profiles.insert("late".to_string(), profile("late"));
+
+        let report = RssNostrJob::process(
+            feed,
+            profile_ids,
+            catch_up_entries(),
+            &mut map,
+            &mut records,
+            &client,
+            &profiles,
+            &app_config,
+        )
+        .await;
+
+        assert_eq!(report.tick.published, ["b", "c"].to_vec());
+        assert!(report.tick.failed.is_empty());
+        assert_eq!(report.ledger.len(), 2);
+        assert!(report
+            .ledger
+            .iter()
+            .all(|publication| publication.profile_id == "late"));
+        assert!(records["b"].pending.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_process_retries_pending_relays() {
+        dotenv::from_filename(".env.test").ok();
+
+        let feed = Feed {
+            id: "test".to_string(),
+            ..Default::default()
+        };
+
+        // The relays of the profile are mined for different difficulties
+        let pow_relay = Relay {
+            name: "pow".to_string(),
+            target: "wss://pow.relay.invalid".to_string(),
+            pow_level: 1,
+            ..active_relay()
+        };
+
+        let mut profiles = HashMap::new();
+        profiles.insert(
+            "default".to_string(),
+            Profile {
+                relays: [active_relay(), pow_relay.clone()].to_vec(),
+                ..Default::default()
+            },
+        );
+
+        let client = Client::new(Keys::generate());
+        let app_config = AppConfig {
+            dry_run: true,
+            ..Default::default()
+        };
+
+        // The entry has been sent to the first relay, the event of the pow relay failed
+        let entry = &update_entries("first")[0];
+        let mut map = ["a".to_string()].to_vec();
+        let mut records = HashMap::new();
+        records.insert(
+            "a".to_string(),
+            EntryRecord {
+                pending: [PendingProfile {
+                    profile_id: "default".to_string(),
+                    action: PendingAction::Publish,
+                    relays: [pow_relay.target.clone()].to_vec(),
+                }]
+                .to_vec(),
+                ..RssNostrJob::get_entry_fingerprint(entry)
+            },
+        );
+
+        let report = RssNostrJob::process(
+            feed,
+            ["default".to_string()].to_vec(),
+            update_entries("first"),
+            &mut map,
+            &mut records,
+            &client,
+            &profiles,
+            &app_config,
+        )
+        .await;
+
+        // Only the relays of the failed difficulty are retried
+        assert_eq!(report.ledger.len(), 1);
+        assert_eq!(report.ledger[0].targets, [pow_relay.target].to_vec());
+        assert_eq!(report.tick.published, ["a"].to_vec());
+        assert!(records["a"].pending.is_empty());
+    }
+
+    #[test]
+    fn test_preview() {
+        dotenv::from_filename(".env.test").ok();
+
+        let feed = Feed {
+            id: "test".to_string(),
+            tags: Some(["rss".to_string()].to_vec()),
+            pow_level: 4,
+            ..Default::default()
+        };
+
+        let keys = Keys::generate();
+        let mut profiles = HashMap::new();
+        profiles.insert(
+            "good".to_string(),
+            Profile {
+                id: "good".to_string(),
+                private_key: keys.secret_key().unwrap().to_secret_hex(),
+                ..Default::default()
+            },
+        );
+
+        let previews = RssNostrJob::preview(
+            &feed,
+            &catch_up_entries()[..1],
+            &["good".to_string(), "unknown".to_string()],
+            &profiles,
+        );
+
+        assert_eq!(previews.len(), 2);
+        assert_eq!(previews[0].entry_id, "a");
+        assert_eq!(previews[0].pow_level, 4);
+
+        let event = previews[0].event.clone().unwrap();
+        assert_eq!(event.pubkey, keys.public_key());
+        assert!(event.tags.contains(&Tag::Hashtag("rss".to_string())));
+        assert!(event
+            .tags
+            .contains(&RssNostrJob::get_nip48("a".to_string())));
+
+        assert_eq!(
+            previews[1].event.clone().unwrap_err(),
+            JobError::ProfileNotFound {
+                profile_id: "unknown".to_string()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_process_shadow_feed() {
+        dotenv::from_filename(".env.test").ok();
+
+        let directory = tempfile::tempdir().unwrap();
+        let shadow_file = directory.path().join("shadow.jsonl");
+
+        let feed = Feed {
+            id: "test".to_string(),
+            shadow: true,
+            shadow_file: Some(shadow_file.display().to_string()),
+            ..Default::default()
+        };
+
+        let mut profiles = HashMap::new();
+        profiles.insert(
+            "good".to_string(),
+            Profile {
+                id: "good".to_string(),
+                private_key: Keys::generate().secret_key().unwrap().to_secret_hex(),
+                relays: [Relay {
+                    name: "unreachable".to_string(),
+                    target: "wss://relay.invalid".to_string(),
+                    active: true,
+                    proxy: None,
+                    pow_level: 0,
+                }]
+                .to_vec(),
+                ..Default::default()
+            },
+        );
+
+        // Other feeds publish for real
+        let client = Client::new(Keys::generate());
+        let app_config = AppConfig::default();
+
+        let mut map = ["a".to_string()].to_vec();
+        let mut records = HashMap::new();
+
+        let report = RssNostrJob::process(
+            feed,
+            ["good".to_string()].to_vec(),
+            catch_up_entries(),
+            &mut map,
+            &mut records,
+            &client,
+            &profiles,
+            &app_config,
+        )
+        .await;
+
+        // Entries are processed as usual but their events are not sent
+        assert_eq!(report.tick.published, ["b", "c"].to_vec());
+        assert_eq!(map, ["c", "b", "a"].to_vec());
+        assert!(report.outbox.is_empty());
+        assert!(report
+            .ledger
+            .iter()
+            .all(|publication| publication.status == PublicationStatus::DryRun));
+
+        let content = std::fs::read_to_string(&shadow_file).unwrap();
+        let events: Vec<Event> = content
+            .lines()
+            .map(|line| Event::from_json(line).unwrap())
+            .collect();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].id.to_hex(), report.ledger[0].event_id);
+    }
+
+    #[tokio::test]
+    async fn test_process_counts_accepted_entries() {
+        dotenv::from_filename(".env.test").ok();
+
+        let feed = Feed {
+            id: "not_accepted".to_string(),
+            ..Default::default()
+        };
+
+        // The relay of the profile is not in the pool of the client
+        let mut profiles = HashMap::new();
+        profiles.insert(
+            "good".to_string(),
+            Profile {
+                id: "good".to_string(),
+                private_key: Keys::generate().secret_key().unwrap().to_secret_hex(),
+                relays: [Relay {
+                    name: "unknown".to_string(),
+                    target: "wss://relay.invalid".to_string(),
+                    active: true,
+                    proxy: None,
+                    pow_level: 0,
+                }]
+                .to_vec(),
+                ..Default::default()
+            },
+        );
+
+        let client = Client::new(Keys::generate());
+        let app_config = AppConfig::default();
+
+        let mut map = ["a".to_string()].to_vec();
+        let mut records = HashMap::new();
+
+        let report = RssNostrJob::process(
+            feed,
+            ["good".to_string()].to_vec(),
+            catch_up_entries(),
+            &mut map,
+            &mut records,
+            &client,
+            &profiles,
+            &app_config,
+        )
+        .await;
+
+        // Events are queued for retry but no entry is counted as published
+        assert_eq!(report.outbox.len(), 2);
+        assert!(report
+            .ledger
+            .iter()
+            .all(|publication| publication.status == PublicationStatus::Failed));
+        assert_eq!(
+            METRICS
+                .entries_published
+                .with_label_values(&["not_accepted", "good"])
+                .get(),
+            0
+        );
+    }
 }
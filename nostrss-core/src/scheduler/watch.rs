@@ -0,0 +1,202 @@
+use std::{collections::HashMap, path::Path, sync::Arc, time::Duration};
+
+use log::{error, info};
+use nostr_sdk::Client;
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+use tokio::sync::{mpsc, oneshot, Mutex};
+
+use crate::{
+    app::app::AppConfig,
+    profiles::config::Profile,
+    rss::{config::Feed, parser::RssParser},
+    store::store::SharedSeenStore,
+};
+
+use super::scheduler::run_feed;
+
+// Delay during which the changes of a file are gathered in a single run,
+// as a file is usually written in several steps
+const DEBOUNCE_DELAY: Duration = Duration::from_millis(200);
+
+/// Runs a feed each time its local file changes.
+/// The file is watched until the returned guard is dropped.
+/// Returns `None` if the feed can not be watched, e.g: for remote feeds.
+pub fn watch_feed(
+    feed: Feed,
+    parser: Result<RssParser, String>,
+    store: SharedSeenStore,
+    client: Arc<Mutex<Client>>,
+    profiles: Arc<Mutex<HashMap<String, Profile>>>,
+    app_config: Arc<Mutex<AppConfig>>,
+) -> Option<oneshot::Sender<()>> {
+    let path = match feed.url.scheme() {
+        "file" => feed.url.to_file_path().ok()?,
+        _ => {
+            error!(
+                "Feed {} is not a local file and can not be watched. Using its schedule instead",
+                feed.id
+            );
+            return None;
+        }
+    };
+
+    let (events_tx, mut events) = mpsc::unbounded_channel();
+    let mut watcher = match notify::recommended_watcher(move |event| {
+        _ = events_tx.send(event);
+    }) {
+        Ok(watcher) => watcher,
+        Err(e) => {
+            error!("Error while watching feed {} : {}", feed.id, e);
+            return None;
+        }
+    };
+
+    // The directory is watched as files are often replaced rather than written in place
+    let directory = path.parent().unwrap_or(Path::new("/"));
+    if let Err(e) = watcher.watch(directory, RecursiveMode::NonRecursive) {
+        error!("Error while watching feed {} : {}", feed.id, e);
+        return None;
+    }
+
+    info!("Watching {} for feed {}", path.display(), feed.id);
+
+    let (guard, mut stopped) = oneshot::channel::<()>();
+
+    tokio::spawn(async move {
+        // The watcher lives as long as the task
+        let _watcher = watcher;
+
+        loop {
+            tokio::select! {
+                _ = &mut stopped => break,
+                event = events.recv() => match event {
+                    Some(Ok(event)) if is_change(&event, &path) => {
+                        tokio::time::sleep(DEBOUNCE_DELAY).await;
+                        while events.try_recv().is_ok() {}
+
+                        info!("Feed {} file changed", feed.id);
+                        run_feed(&feed, &parser, &store, &client, &profiles, &app_config).await;
+                    }
+                    Some(Ok(_)) => {}
+                    Some(Err(e)) => error!("Error while watching feed {} : {}", feed.id, e),
+                    None => break,
+                },
+            }
+        }
+
+        info!("Stopped watching {} for feed {}", path.display(), feed.id);
+    });
+
+    Some(guard)
+}
+
+// Tells if an event is a write or a replacement of the watched file
+fn is_change(event: &Event, path: &Path) -> bool {
+    matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_))
+        && event.paths.iter().any(|p| p == path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{nostr::relay::Relay, scheduler::scheduler::schedule, store::store::load_store};
+    use nostr_sdk::Keys;
+    use tokio_cron_scheduler::JobScheduler;
+
+    fn rss(items: usize) -> String {
+        let items: String = (0..items)
+            .map(|i| format!("<item><guid>{}</guid><title>Entry {}</title></item>", i, i))
+            .collect();
+
+        format!(
+            "<?xml version=\"1.0\"?><rss version=\"2.0\"><channel><title>Test</title>{}</channel></rss>",
+            items
+        )
+    }
+
+    #[tokio::test]
+    async fn test_watch_feed() {
+        dotenv::from_filename(".env.test").ok();
+
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("feed.xml");
+        std::fs::write(&path, rss(1)).unwrap();
+
+        let feed = Feed {
+            id: "watched".to_string(),
+            url: nostr_sdk::Url::from_file_path(&path).unwrap(),
+            profiles: Some(["good".to_string()].to_vec()),
+            watch: true,
+            ..Default::default()
+        };
+
+        let mut profiles = HashMap::new();
+        profiles.insert(
+            "good".to_string(),
+            Profile {
+                id: "good".to_string(),
+                private_key: Keys::generate().secret_key().unwrap().to_secret_hex(),
+                relays: [Relay {
+                    name: "test".to_string(),
+                    target: "wss://relay.invalid".to_string(),
+                    active: true,
+                    proxy: None,
+                    pow_level: 0,
+                }]
+                .to_vec(),
+                ..Default::default()
+            },
+        );
+
+        let store = load_store(&None).unwrap();
+        let job = schedule(
+            "0 0 0 1 1 *",
+            feed,
+            Arc::clone(&store),
+            Arc::new(Mutex::new(Client::new(Keys::generate()))),
+            Arc::new(Mutex::new(profiles)),
+            Arc::new(Mutex::new(AppConfig {
+                dry_run: true,
+                ..Default::default()
+            })),
+            reqwest::Client::new(),
+        )
+        .await
+        .unwrap();
+
+        let scheduler = JobScheduler::new().await.unwrap();
+        let uuid = scheduler.add(job).await.unwrap();
+
+        // The feed is run when its file changes
+        std::fs::write(&path, rss(2)).unwrap();
+
+        let report = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                if let Some(report) = store.lock().await.get_last_report("watched") {
+                    return report;
+                }
+                tokio::time::sleep(Duration::from_millis(50)).await;
+            }
+        })
+        .await
+        .expect("The feed should be run once its file changed");
+        assert_eq!(report.published, ["1".to_string()].to_vec());
+
+        // The watcher task ends once the job is removed.
+        // The job and the task hold the only other references to the store.
+        scheduler.remove(&uuid).await.unwrap();
+
+        let ended = tokio::time::timeout(Duration::from_secs(5), async {
+            while Arc::strong_count(&store) > 1 {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+            }
+        })
+        .await;
+        assert!(ended.is_ok(), "The watcher task should end with its job");
+
+        std::fs::write(&path, rss(3)).unwrap();
+
+        let health = store.lock().await.get_health("watched");
+        assert_eq!(health.entries_seen, 2);
+    }
+}
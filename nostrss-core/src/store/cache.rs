@@ -0,0 +1,186 @@
+use std::time::{Duration, UNIX_EPOCH};
+
+use reqwest::header::{HeaderMap, CACHE_CONTROL, ETAG, LAST_MODIFIED, RETRY_AFTER};
+use serde::{Deserialize, Serialize};
+
+/// The HTTP cache state of a feed, used to send conditional requests
+/// and to respect the delays requested by the remote server.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct HttpCache {
+    // Value of the `ETag` header of the last response, sent as `If-None-Match`
+    #[serde(default)]
+    pub etag: Option<String>,
+    // Value of the `Last-Modified` header of the last response, sent as `If-Modified-Since`
+    #[serde(default)]
+    pub last_modified: Option<String>,
+    // Unix timestamp before which the feed is not fetched again,
+    // from `Cache-Control: max-age` or `Retry-After`
+    #[serde(default)]
+    pub not_before: Option<i64>,
+}
+
+impl HttpCache {
+    // Tells if the feed can be fetched at `now`
+    pub fn is_fresh(&self, now: i64) -> bool {
+        self.not_before.is_some_and(|not_before| now < not_before)
+    }
+
+    // Updates the cache from the headers of a successful response.
+    // The validators describe the new content, so missing ones are removed.
+    pub fn update_modified(&mut self, headers: &HeaderMap, now: i64) {
+        self.etag = Self::get_header(headers, ETAG.as_str());
+        self.last_modified = Self::get_header(headers, LAST_MODIFIED.as_str());
+
+        self.update_max_age(headers, now);
+    }
+
+    // Updates the cache from the headers of a not modified response.
+    // Validators missing from the response are kept.
+    pub fn update_not_modified(&mut self, headers: &HeaderMap, now: i64) {
+        if let Some(etag) = Self::get_header(headers, ETAG.as_str()) {
+            self.etag = Some(etag);
+        }
+
+        if let Some(last_modified) = Self::get_header(headers, LAST_MODIFIED.as_str()) {
+            self.last_modified = Some(last_modified);
+        }
+
+        self.update_max_age(headers, now);
+    }
+
+    fn update_max_age(&mut self, headers: &HeaderMap, now: i64) {
+        self.not_before = Self::get_max_age(headers).map(|max_age| now.saturating_add(max_age));
+    }
+
+    // Delays the next fetch according to the `Retry-After` header of a response
+    pub fn retry_after(&mut self, headers: &HeaderMap, now: i64) {
+        if let Some(delay) = Self::get_retry_after(headers, now) {
+            self.not_before = Some(now.saturating_add(delay));
+        }
+    }
+
+    fn get_header(headers: &HeaderMap, name: &str) -> Option<String> {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.to_string())
+    }
+
+    // Retrieves the `max-age` directive of the `Cache-Control` header, in seconds.
+    // Responses that must not be cached have no max age.
+    fn get_max_age(headers: &HeaderMap) -> Option<i64> {
+        let cache_control = Self::get_header(headers, CACHE_CONTROL.as_str())?.to_lowercase();
+        let directives: Vec<&str> = cache_control.split(',').map(|d| d.trim()).collect();
+
+        if directives.contains(&"no-cache") || directives.contains(&"no-store") {
+            return None;
+        }
+
+        directives
+            .iter()
+            .find_map(|directive| directive.strip_prefix("max-age="))
+            .and_then(|max_age| max_age.trim_matches('"').parse::<i64>().ok())
+            .filter(|max_age| *max_age > 0)
+    }
+
+    // Retrieves the delay of the `Retry-After` header, in seconds.
+    // The header is either a number of seconds or an HTTP date.
+    fn get_retry_after(headers: &HeaderMap, now: i64) -> Option<i64> {
+        let retry_after = Self::get_header(headers, RETRY_AFTER.as_str())?;
+
+        if let Ok(seconds) = retry_after.trim().parse::<i64>() {
+            return Some(seconds.max(0));
+        }
+
+        let date = httpdate::parse_http_date(retry_after.trim()).ok()?;
+        let timestamp = date
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or(Duration::ZERO)
+            .as_secs() as i64;
+
+        Some((timestamp - now).max(0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn test_http_cache_update() {
+        let mut headers = HeaderMap::new();
+        headers.insert(ETAG, HeaderValue::from_static("\"abc\""));
+        headers.insert(
+            LAST_MODIFIED,
+            HeaderValue::from_static("Tue, 14 Nov 2023 22:13:20 GMT"),
+        );
+        headers.insert(
+            CACHE_CONTROL,
+            HeaderValue::from_static("public, max-age=300"),
+        );
+
+        let mut cache = HttpCache::default();
+        cache.update_modified(&headers, 1000);
+
+        assert_eq!(cache.etag, Some("\"abc\"".to_string()));
+        assert_eq!(
+            cache.last_modified,
+            Some("Tue, 14 Nov 2023 22:13:20 GMT".to_string())
+        );
+        assert_eq!(cache.not_before, Some(1300));
+        assert!(cache.is_fresh(1299));
+        assert!(!cache.is_fresh(1300));
+
+        // Validators are kept when missing from a not modified response
+        let mut headers = HeaderMap::new();
+        headers.insert(CACHE_CONTROL, HeaderValue::from_static("no-cache"));
+        cache.update_not_modified(&headers, 2000);
+
+        assert_eq!(cache.etag, Some("\"abc\"".to_string()));
+        assert_eq!(cache.not_before, None);
+    }
+
+    #[test]
+    fn test_http_cache_update_modified_without_etag() {
+        let mut cache = HttpCache {
+            etag: Some("\"abc\"".to_string()),
+            last_modified: Some("Tue, 14 Nov 2023 22:13:20 GMT".to_string()),
+            not_before: None,
+        };
+
+        // The validators of the previous content no longer match
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            LAST_MODIFIED,
+            HeaderValue::from_static("Wed, 15 Nov 2023 08:00:00 GMT"),
+        );
+        cache.update_modified(&headers, 1000);
+
+        assert_eq!(cache.etag, None);
+        assert_eq!(
+            cache.last_modified,
+            Some("Wed, 15 Nov 2023 08:00:00 GMT".to_string())
+        );
+
+        cache.update_modified(&HeaderMap::new(), 2000);
+        assert_eq!(cache, HttpCache::default());
+    }
+
+    #[test]
+    fn test_http_cache_retry_after() {
+        let mut cache = HttpCache::default();
+
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("120"));
+        cache.retry_after(&headers, 1000);
+        assert_eq!(cache.not_before, Some(1120));
+
+        headers.insert(
+            RETRY_AFTER,
+            HeaderValue::from_static("Tue, 14 Nov 2023 22:15:00 GMT"),
+        );
+        cache.retry_after(&headers, 1700000000);
+        assert_eq!(cache.not_before, Some(1700000100));
+    }
+}
@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+// The number of errors retained by feed. Oldest errors are dropped first.
+pub const RETAINED_ERRORS: usize = 20;
+
+/// An error that occured while processing a feed.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FeedError {
+    // Unix timestamp of the error
+    pub timestamp: i64,
+    // The kind of error, e.g: `fetch` or `invalid_keys`
+    pub kind: String,
+    #[serde(default)]
+    pub profile_id: Option<String>,
+    // The id (guid) of the entry being processed
+    #[serde(default)]
+    pub entry_id: Option<String>,
+    pub message: String,
+}
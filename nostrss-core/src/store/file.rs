@@ -0,0 +1,319 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+use log::error;
+
+use super::{
+    cache::HttpCache,
+    errors::FeedError,
+    health::FeedHealth,
+    ledger::Publication,
+    memory::MemoryStore,
+    outbox::OutboxEvent,
+    report::TickReport,
+    store::{EntryRecord, FeedRecord, SeenStore},
+};
+
+/// Persistent store backed by a `json` file.
+///
+/// The whole store is kept in a [`MemoryStore`]. The file is only
+/// rewritten when the store is flushed, once per tick of a job,
+/// so a restart resumes from the last state flushed.
+#[derive(Debug, Clone)]
+pub struct FileStore {
+    pub path: PathBuf,
+    pub memory: MemoryStore,
+    // Number of changes of the memory store already written to the file.
+    // Shared with the snapshots being written.
+    written: Arc<Mutex<u64>>,
+}
+
+impl FileStore {
+    // Builds a new file store. Loads the existing state
+    // if a store file is found at `path`.
+    // A store file that can not be parsed is moved aside so it is not overwritten.
+    pub fn new(path: &str) -> Result<Self, String> {
+        let path = PathBuf::from(path);
+        let feeds = Self::load(&path)?;
+
+        Ok(Self {
+            path,
+            memory: MemoryStore::from_records(feeds),
+            written: Arc::new(Mutex::new(0)),
+        })
+    }
+
+    fn load(path: &PathBuf) -> Result<HashMap<String, FeedRecord>, String> {
+        if !path.is_file() {
+            return Ok(HashMap::new());
+        }
+
+        let file =
+            fs::File::open(path).map_err(|e| format!("Store file could not be opened : {}", e))?;
+
+        match serde_json::from_reader(file) {
+            Ok(feeds) => Ok(feeds),
+            Err(e) => {
+                let corrupted_path = PathBuf::from(format!("{}.corrupted", path.display()));
+                fs::rename(path, &corrupted_path)
+                    .map_err(|e| format!("Invalid store file could not be moved : {}", e))?;
+
+                error!(
+                    "Error parsing store file : {}. File moved to {}",
+                    e,
+                    corrupted_path.display()
+                );
+
+                Ok(HashMap::new())
+            }
+        }
+    }
+}
+
+/// The state of a [`FileStore`] waiting to be written to its file.
+pub struct FileSnapshot {
+    path: PathBuf,
+    feeds: HashMap<String, FeedRecord>,
+    changes: u64,
+    written: Arc<Mutex<u64>>,
+}
+
+impl FileSnapshot {
+    // Writes the snapshot into a temporary file first and then
+    // moves it to the store path so a crash while writing
+    // never leaves a truncated store behind.
+    // Snapshots older than the last one written are skipped.
+    pub fn write(self) -> bool {
+        let mut written = match self.written.lock() {
+            Ok(written) => written,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+
+        if *written >= self.changes {
+            return true;
+        }
+
+        let content = match serde_json::to_string(&self.feeds) {
+            Ok(content) => content,
+            Err(e) => {
+                error!("Error serializing store : {}", e);
+                return false;
+            }
+        };
+
+        let tmp_path = self.path.with_extension("tmp");
+
+        if let Err(e) = fs::write(&tmp_path, content) {
+            error!("Error writing store file : {}", e);
+            return false;
+        }
+
+        match fs::rename(&tmp_path, &self.path) {
+            Ok(_) => {
+                *written = self.changes;
+                true
+            }
+            Err(e) => {
+                error!("Error writing store file : {}", e);
+                false
+            }
+        }
+    }
+}
+
+impl SeenStore for FileStore {
+    fn get(&self, feed_id: &str) -> Option<Vec<String>> {
+        self.memory.get(feed_id)
+    }
+
+    fn set(&mut self, feed_id: &str, entries: Vec<String>) {
+        self.memory.set(feed_id, entries)
+    }
+
+    fn get_last_run(&self, feed_id: &str) -> Option<i64> {
+        self.memory.get_last_run(feed_id)
+    }
+
+    fn set_last_run(&mut self, feed_id: &str, timestamp: i64) {
+        self.memory.set_last_run(feed_id, timestamp)
+    }
+
+    fn get_entry_records(&self, feed_id: &str) -> HashMap<String, EntryRecord> {
+        self.memory.get_entry_records(feed_id)
+    }
+
+    fn set_entry_records(&mut self, feed_id: &str, records: HashMap<String, EntryRecord>) {
+        self.memory.set_entry_records(feed_id, records)
+    }
+
+    fn get_publications(&self, feed_id: &str) -> Vec<Publication> {
+        self.memory.get_publications(feed_id)
+    }
+
+    fn get_publication(&self, event_id: &str) -> Option<Publication> {
+        self.memory.get_publication(event_id)
+    }
+
+    fn add_publications(&mut self, feed_id: &str, publications: Vec<Publication>) {
+        self.memory.add_publications(feed_id, publications)
+    }
+
+    fn update_publication(&mut self, publication: Publication) {
+        self.memory.update_publication(publication)
+    }
+
+    fn get_outbox(&self) -> Vec<OutboxEvent> {
+        self.memory.get_outbox()
+    }
+
+    fn add_outbox_events(&mut self, feed_id: &str, events: Vec<OutboxEvent>) {
+        self.memory.add_outbox_events(feed_id, events)
+    }
+
+    fn update_outbox_event(&mut self, event: OutboxEvent) {
+        self.memory.update_outbox_event(event)
+    }
+
+    fn get_error_count(&self, feed_id: &str) -> u64 {
+        self.memory.get_error_count(feed_id)
+    }
+
+    fn get_errors(&self, feed_id: &str) -> Vec<FeedError> {
+        self.memory.get_errors(feed_id)
+    }
+
+    fn add_errors(&mut self, feed_id: &str, errors: Vec<FeedError>) {
+        self.memory.add_errors(feed_id, errors)
+    }
+
+    fn get_last_report(&self, feed_id: &str) -> Option<TickReport> {
+        self.memory.get_last_report(feed_id)
+    }
+
+    fn set_last_report(&mut self, feed_id: &str, report: TickReport) {
+        self.memory.set_last_report(feed_id, report)
+    }
+
+    fn get_health(&self, feed_id: &str) -> FeedHealth {
+        self.memory.get_health(feed_id)
+    }
+
+    fn set_health(&mut self, feed_id: &str, health: FeedHealth) {
+        self.memory.set_health(feed_id, health)
+    }
+
+    fn get_http_cache(&self, feed_id: &str) -> HttpCache {
+        self.memory.get_http_cache(feed_id)
+    }
+
+    fn set_http_cache(&mut self, feed_id: &str, cache: HttpCache) {
+        self.memory.set_http_cache(feed_id, cache)
+    }
+
+    fn remove(&mut self, feed_id: &str) {
+        self.memory.remove(feed_id)
+    }
+
+    // Changes that failed to be written are part of the next snapshot
+    fn take_snapshot(&mut self) -> Option<FileSnapshot> {
+        let written = match self.written.lock() {
+            Ok(written) => *written,
+            Err(poisoned) => *poisoned.into_inner(),
+        };
+
+        if written >= self.memory.changes() {
+            return None;
+        }
+
+        Some(FileSnapshot {
+            path: self.path.clone(),
+            feeds: self.memory.feeds.clone(),
+            changes: self.memory.changes(),
+            written: Arc::clone(&self.written),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_file_store_persistence() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("store.json");
+        let path = path.to_str().unwrap();
+
+        let entries = ["a".to_string(), "b".to_string()].to_vec();
+
+        let mut store = FileStore::new(path).unwrap();
+        assert_eq!(store.get("test"), None);
+
+        store.set("test", entries.clone());
+        store.set_last_run("test", 1700000000);
+        store.set("other", Vec::new());
+
+        // Changes are only written on flush
+        assert!(!dir.path().join("store.json").is_file());
+        assert!(store.take_snapshot().unwrap().write());
+
+        // A new instance should resume from the written file
+        let mut store = FileStore::new(path).unwrap();
+        assert_eq!(store.get("test"), Some(entries));
+        assert_eq!(store.get_last_run("test"), Some(1700000000));
+        assert_eq!(store.get("other"), Some(Vec::new()));
+        assert_eq!(store.get_last_run("other"), None);
+
+        store.remove("other");
+        assert!(store.take_snapshot().unwrap().write());
+
+        let store = FileStore::new(path).unwrap();
+        assert_eq!(store.get("other"), None);
+    }
+
+    #[test]
+    fn test_file_store_snapshots() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("store.json");
+
+        let mut store = FileStore::new(path.to_str().unwrap()).unwrap();
+        assert!(store.take_snapshot().is_none());
+
+        store.set("test", ["a".to_string()].to_vec());
+        let older = store.take_snapshot().unwrap();
+
+        store.set("test", ["b".to_string()].to_vec());
+        let newer = store.take_snapshot().unwrap();
+
+        // A snapshot written after a more recent one is skipped
+        assert!(newer.write());
+        assert!(older.write());
+        assert!(store.take_snapshot().is_none());
+
+        let store = FileStore::new(path.to_str().unwrap()).unwrap();
+        assert_eq!(store.get("test"), Some(["b".to_string()].to_vec()));
+    }
+
+    #[test]
+    fn test_file_store_invalid_file() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("store.json");
+        fs::write(&path, "not a json store").unwrap();
+
+        let store = FileStore::new(path.to_str().unwrap()).unwrap();
+        assert_eq!(store.memory.feeds.len(), 0);
+
+        // The invalid file is kept aside
+        assert!(!path.is_file());
+        let corrupted_path = dir.path().join("store.json.corrupted");
+        assert_eq!(
+            fs::read_to_string(corrupted_path).unwrap(),
+            "not a json store"
+        );
+    }
+}
@@ -0,0 +1,80 @@
+use serde::{Deserialize, Serialize};
+
+/// The health of a feed, updated on each fetch of the feed.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct FeedHealth {
+    // Unix timestamp of the last fetch, successful or not
+    #[serde(default)]
+    pub last_fetch: Option<i64>,
+    // Unix timestamp of the last successful fetch
+    #[serde(default)]
+    pub last_success: Option<i64>,
+    // Number of fetches that failed since the last successful one
+    #[serde(default)]
+    pub consecutive_failures: u64,
+    #[serde(default)]
+    pub last_error: Option<String>,
+    // HTTP status of the last response of the remote feed
+    #[serde(default)]
+    pub http_status: Option<u16>,
+    // Number of entries of the feed on the last successful fetch
+    #[serde(default)]
+    pub entries_seen: u64,
+    // Number of entries published since the feed is tracked
+    #[serde(default)]
+    pub entries_published: u64,
+}
+
+impl FeedHealth {
+    // Records a successful fetch of the feed
+    pub fn record_success(
+        &mut self,
+        timestamp: i64,
+        http_status: Option<u16>,
+        entries_seen: u64,
+        entries_published: u64,
+    ) {
+        self.last_fetch = Some(timestamp);
+        self.last_success = Some(timestamp);
+        self.consecutive_failures = 0;
+        self.http_status = http_status;
+        self.entries_seen = entries_seen;
+        self.entries_published += entries_published;
+    }
+
+    // Records a failed fetch of the feed
+    pub fn record_failure(&mut self, timestamp: i64, http_status: Option<u16>, error: String) {
+        self.last_fetch = Some(timestamp);
+        self.consecutive_failures += 1;
+        self.http_status = http_status;
+        self.last_error = Some(error);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_feed_health() {
+        let mut health = FeedHealth::default();
+
+        health.record_failure(1, Some(503), "unavailable".to_string());
+        health.record_failure(2, None, "timeout".to_string());
+        assert_eq!(health.consecutive_failures, 2);
+        assert_eq!(health.last_success, None);
+        assert_eq!(health.last_error, Some("timeout".to_string()));
+
+        health.record_success(3, Some(200), 10, 2);
+        health.record_success(4, Some(200), 11, 1);
+        assert_eq!(health.consecutive_failures, 0);
+        assert_eq!(health.last_fetch, Some(4));
+        assert_eq!(health.last_success, Some(4));
+        assert_eq!(health.http_status, Some(200));
+        assert_eq!(health.entries_seen, 11);
+        assert_eq!(health.entries_published, 3);
+
+        // The last error is kept once the feed recovers
+        assert_eq!(health.last_error, Some("timeout".to_string()));
+    }
+}
@@ -0,0 +1,110 @@
+use std::{env, fmt};
+
+use serde::{Deserialize, Serialize};
+
+/// The outcome of a publication.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum PublicationStatus {
+    // Every targeted relay acknowledged the event
+    Published,
+    // Some of the targeted relays acknowledged the event
+    Partial,
+    // No relay acknowledged the event
+    Failed,
+    // The event was built but not sent as the program runs in dry-run mode
+    DryRun,
+}
+
+impl fmt::Display for PublicationStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PublicationStatus::Published => write!(f, "published"),
+            PublicationStatus::Partial => write!(f, "partial"),
+            PublicationStatus::Failed => write!(f, "failed"),
+            PublicationStatus::DryRun => write!(f, "dry_run"),
+        }
+    }
+}
+
+/// An entry of the publication ledger.
+/// Links an entry of a feed to an event sent to nostr relays.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Publication {
+    pub feed_id: String,
+    // The id (guid) of the entry
+    pub entry_id: String,
+    pub profile_id: String,
+    // Hex representation of the event id
+    pub event_id: String,
+    // Kind of the event
+    pub kind: u64,
+    // The relays the event was sent to
+    #[serde(default)]
+    pub targets: Vec<String>,
+    // The relays that acknowledged the event
+    #[serde(default)]
+    pub relays: Vec<String>,
+    // Unix timestamp of the publication
+    pub timestamp: i64,
+    pub status: PublicationStatus,
+}
+
+impl Publication {
+    // Computes the status of a publication from the relays that acknowledged it
+    pub fn get_status(targets: &[String], relays: &[String], dry_run: bool) -> PublicationStatus {
+        if dry_run {
+            return PublicationStatus::DryRun;
+        }
+
+        match relays.len() {
+            0 => PublicationStatus::Failed,
+            n if n >= targets.len() => PublicationStatus::Published,
+            _ => PublicationStatus::Partial,
+        }
+    }
+
+    // Tells if at least one relay acknowledged the event
+    pub fn is_sent(&self) -> bool {
+        matches!(
+            self.status,
+            PublicationStatus::Published | PublicationStatus::Partial
+        )
+    }
+
+    // The number of publications retained by feed in the ledger.
+    // Oldest publications are dropped first.
+    pub fn ledger_size() -> usize {
+        env::var("LEDGER_SIZE")
+            .unwrap_or("1000".to_string())
+            .parse::<usize>()
+            .unwrap_or(1000)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_publication_status() {
+        let targets = ["wss://a".to_string(), "wss://b".to_string()].to_vec();
+
+        assert_eq!(
+            Publication::get_status(&targets, &targets, false),
+            PublicationStatus::Published
+        );
+        assert_eq!(
+            Publication::get_status(&targets, &targets[..1], false),
+            PublicationStatus::Partial
+        );
+        assert_eq!(
+            Publication::get_status(&targets, &[], false),
+            PublicationStatus::Failed
+        );
+        assert_eq!(
+            Publication::get_status(&targets, &[], true),
+            PublicationStatus::DryRun
+        );
+    }
+}
@@ -0,0 +1,263 @@
+use std::collections::HashMap;
+
+use super::{
+    cache::HttpCache,
+    errors::FeedError,
+    health::FeedHealth,
+    ledger::Publication,
+    outbox::OutboxEvent,
+    report::TickReport,
+    store::{EntryRecord, FeedRecord, SeenStore},
+};
+
+/// Volatile store. Retained entries are lost when the program stops.
+///
+/// Changes are counted so a persistent backend wrapping the store,
+/// see [`super::file::FileStore`], knows when its state has to be written.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryStore {
+    pub feeds: HashMap<String, FeedRecord>,
+    // Number of changes made to the store
+    changes: u64,
+}
+
+impl MemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Builds a store resuming from previously retained records
+    pub fn from_records(feeds: HashMap<String, FeedRecord>) -> Self {
+        Self { feeds, changes: 0 }
+    }
+
+    // Retrieves the number of changes made to the store
+    pub fn changes(&self) -> u64 {
+        self.changes
+    }
+
+    // Retrieves the record of a feed to be changed
+    fn record(&mut self, feed_id: &str) -> &mut FeedRecord {
+        self.changes += 1;
+        self.feeds.entry(feed_id.to_string()).or_default()
+    }
+}
+
+impl SeenStore for MemoryStore {
+    fn get(&self, feed_id: &str) -> Option<Vec<String>> {
+        self.feeds.get(feed_id).map(|record| record.entries.clone())
+    }
+
+    fn set(&mut self, feed_id: &str, entries: Vec<String>) {
+        self.record(feed_id).entries = entries;
+    }
+
+    fn get_last_run(&self, feed_id: &str) -> Option<i64> {
+        self.feeds.get(feed_id).and_then(|record| record.last_run)
+    }
+
+    fn set_last_run(&mut self, feed_id: &str, timestamp: i64) {
+        self.record(feed_id).last_run = Some(timestamp);
+    }
+
+    fn get_entry_records(&self, feed_id: &str) -> HashMap<String, EntryRecord> {
+        self.feeds
+            .get(feed_id)
+            .map(|record| record.entry_records.clone())
+            .unwrap_or_default()
+    }
+
+    fn set_entry_records(&mut self, feed_id: &str, records: HashMap<String, EntryRecord>) {
+        self.record(feed_id).entry_records = records;
+    }
+
+    fn get_publications(&self, feed_id: &str) -> Vec<Publication> {
+        self.feeds
+            .get(feed_id)
+            .map(|record| record.publications.clone())
+            .unwrap_or_default()
+    }
+
+    fn get_publication(&self, event_id: &str) -> Option<Publication> {
+        self.feeds
+            .values()
+            .flat_map(|record| record.publications.iter())
+            .find(|publication| publication.event_id == event_id)
+            .cloned()
+    }
+
+    fn add_publications(&mut self, feed_id: &str, publications: Vec<Publication>) {
+        if publications.is_empty() {
+            return;
+        }
+
+        self.record(feed_id).add_publications(publications);
+    }
+
+    fn update_publication(&mut self, publication: Publication) {
+        let updated = match self.feeds.get_mut(&publication.feed_id) {
+            Some(record) => record.update_publication(publication),
+            None => false,
+        };
+
+        if updated {
+            self.changes += 1;
+        }
+    }
+
+    fn get_outbox(&self) -> Vec<OutboxEvent> {
+        self.feeds
+            .values()
+            .flat_map(|record| record.outbox.iter())
+            .cloned()
+            .collect()
+    }
+
+    fn add_outbox_events(&mut self, feed_id: &str, events: Vec<OutboxEvent>) {
+        if events.is_empty() {
+            return;
+        }
+
+        self.record(feed_id).outbox.extend(events);
+    }
+
+    fn update_outbox_event(&mut self, event: OutboxEvent) {
+        let updated = match self.feeds.get_mut(&event.feed_id) {
+            Some(record) => record.update_outbox_event(event),
+            None => false,
+        };
+
+        if updated {
+            self.changes += 1;
+        }
+    }
+
+    fn get_error_count(&self, feed_id: &str) -> u64 {
+        self.feeds
+            .get(feed_id)
+            .map(|record| record.error_count)
+            .unwrap_or_default()
+    }
+
+    fn get_errors(&self, feed_id: &str) -> Vec<FeedError> {
+        self.feeds
+            .get(feed_id)
+            .map(|record| record.errors.clone())
+            .unwrap_or_default()
+    }
+
+    fn add_errors(&mut self, feed_id: &str, errors: Vec<FeedError>) {
+        if errors.is_empty() {
+            return;
+        }
+
+        self.record(feed_id).add_errors(errors);
+    }
+
+    fn get_last_report(&self, feed_id: &str) -> Option<TickReport> {
+        self.feeds
+            .get(feed_id)
+            .and_then(|record| record.last_report.clone())
+    }
+
+    fn set_last_report(&mut self, feed_id: &str, report: TickReport) {
+        self.record(feed_id).last_report = Some(report);
+    }
+
+    fn get_health(&self, feed_id: &str) -> FeedHealth {
+        self.feeds
+            .get(feed_id)
+            .map(|record| record.health.clone())
+            .unwrap_or_default()
+    }
+
+    fn set_health(&mut self, feed_id: &str, health: FeedHealth) {
+        self.record(feed_id).health = health;
+    }
+
+    fn get_http_cache(&self, feed_id: &str) -> HttpCache {
+        self.feeds
+            .get(feed_id)
+            .map(|record| record.http_cache.clone())
+            .unwrap_or_default()
+    }
+
+    fn set_http_cache(&mut self, feed_id: &str, cache: HttpCache) {
+        self.record(feed_id).http_cache = cache;
+    }
+
+    fn remove(&mut self, feed_id: &str) {
+        if self.feeds.remove(feed_id).is_some() {
+            self.changes += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::ledger::PublicationStatus;
+
+    #[test]
+    fn test_memory_store() {
+        let mut store = MemoryStore::new();
+        let entries = ["a".to_string(), "b".to_string()].to_vec();
+
+        store.set("test", entries.clone());
+        assert_eq!(store.get("test"), Some(entries));
+        assert_eq!(store.get("unknown"), None);
+
+        store.remove("test");
+        assert_eq!(store.get("test"), None);
+    }
+
+    #[test]
+    fn test_memory_store_changes() {
+        let mut store = MemoryStore::new();
+        assert_eq!(store.changes(), 0);
+
+        store.set("test", ["a".to_string()].to_vec());
+        store.set_last_run("test", 1700000000);
+        assert_eq!(store.changes(), 2);
+
+        // Calls leaving the store untouched are not counted
+        store.add_publications("test", Vec::new());
+        store.add_errors("test", Vec::new());
+        store.remove("unknown");
+        assert_eq!(store.changes(), 2);
+
+        store.remove("test");
+        assert_eq!(store.changes(), 3);
+    }
+
+    #[test]
+    fn test_memory_store_publications() {
+        let mut store = MemoryStore::new();
+        let publication = |event_id: &str| Publication {
+            feed_id: "test".to_string(),
+            entry_id: "a".to_string(),
+            profile_id: "default".to_string(),
+            event_id: event_id.to_string(),
+            kind: 1,
+            targets: Vec::new(),
+            relays: Vec::new(),
+            timestamp: 0,
+            status: PublicationStatus::DryRun,
+        };
+
+        store.add_publications("test", [publication("1"), publication("2")].to_vec());
+        store.add_publications("test", [publication("3")].to_vec());
+
+        // Newest publications first
+        let event_ids: Vec<String> = store
+            .get_publications("test")
+            .into_iter()
+            .map(|p| p.event_id)
+            .collect();
+        assert_eq!(event_ids, ["3", "2", "1"].map(String::from).to_vec());
+
+        assert_eq!(store.get_publication("2"), Some(publication("2")));
+        assert_eq!(store.get_publication("4"), None);
+        assert!(store.get_publications("unknown").is_empty());
+    }
+}
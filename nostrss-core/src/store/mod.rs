@@ -0,0 +1,9 @@
+pub mod cache;
+pub mod errors;
+pub mod file;
+pub mod health;
+pub mod ledger;
+pub mod memory;
+pub mod outbox;
+pub mod report;
+pub mod store;
@@ -0,0 +1,228 @@
+use std::{env, fmt};
+
+use serde::{Deserialize, Serialize};
+
+// The number of failed events retained by feed in the outbox.
+// Oldest failed events are dropped first.
+pub const RETAINED_FAILED_EVENTS: usize = 20;
+
+/// The state of an event waiting in the outbox.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum OutboxStatus {
+    // The event is retried until enough relays acknowledge it
+    Pending,
+    // Every relay exhausted its attempts. The event is no longer retried
+    Failed,
+}
+
+impl fmt::Display for OutboxStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OutboxStatus::Pending => write!(f, "pending"),
+            OutboxStatus::Failed => write!(f, "failed"),
+        }
+    }
+}
+
+/// The delivery of an outbox event to a single relay.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Delivery {
+    // The url of the relay
+    pub relay: String,
+    pub acked: bool,
+    // Number of failed attempts
+    pub attempts: u32,
+    // Unix timestamp of the next attempt
+    pub next_attempt: i64,
+    #[serde(default)]
+    pub last_error: Option<String>,
+}
+
+impl Delivery {
+    // Tells if the delivery has to be attempted at `now`
+    pub fn is_due(&self, now: i64) -> bool {
+        !self.acked && self.attempts < OutboxEvent::max_attempts() && self.next_attempt <= now
+    }
+
+    // Records a failed attempt and schedules the next one
+    // with an exponential backoff
+    pub fn fail(&mut self, error: String, now: i64) {
+        self.attempts += 1;
+        self.next_attempt = now.saturating_add(OutboxEvent::backoff(self.attempts));
+        self.last_error = Some(error);
+    }
+}
+
+/// A signed event that did not reach enough relays when published.
+/// The event is retried on the relays that did not acknowledge it.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct OutboxEvent {
+    pub feed_id: String,
+    // The id (guid) of the entry
+    pub entry_id: String,
+    pub profile_id: String,
+    // Hex representation of the event id
+    pub event_id: String,
+    // The signed event, as json
+    pub event: String,
+    // Number of acknowledgements required for the event to leave the outbox
+    pub required_acks: usize,
+    pub deliveries: Vec<Delivery>,
+    // Unix timestamp of the first publication attempt
+    pub created_at: i64,
+    pub status: OutboxStatus,
+}
+
+impl OutboxEvent {
+    // Builds an outbox event from a first publication attempt.
+    // Returns `None` if enough relays already acknowledged the event.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        feed_id: &str,
+        entry_id: &str,
+        profile_id: &str,
+        event_id: &str,
+        event: String,
+        targets: &[String],
+        acked: &[String],
+        now: i64,
+    ) -> Option<Self> {
+        let required_acks = Self::min_acks().min(targets.len());
+
+        if acked.len() >= required_acks {
+            return None;
+        }
+
+        let deliveries = targets
+            .iter()
+            .map(|relay| {
+                let acked = acked.contains(relay);
+                Delivery {
+                    relay: relay.clone(),
+                    acked,
+                    attempts: match acked {
+                        true => 0,
+                        false => 1,
+                    },
+                    next_attempt: now.saturating_add(Self::backoff(1)),
+                    last_error: None,
+                }
+            })
+            .collect();
+
+        Some(Self {
+            feed_id: feed_id.to_string(),
+            entry_id: entry_id.to_string(),
+            profile_id: profile_id.to_string(),
+            event_id: event_id.to_string(),
+            event,
+            required_acks,
+            deliveries,
+            created_at: now,
+            status: OutboxStatus::Pending,
+        })
+    }
+
+    // The relays that acknowledged the event
+    pub fn get_acked_relays(&self) -> Vec<String> {
+        self.deliveries
+            .iter()
+            .filter(|delivery| delivery.acked)
+            .map(|delivery| delivery.relay.clone())
+            .collect()
+    }
+
+    // Tells if enough relays acknowledged the event
+    pub fn is_delivered(&self) -> bool {
+        self.get_acked_relays().len() >= self.required_acks
+    }
+
+    // Tells if no relay can be retried anymore
+    pub fn is_exhausted(&self) -> bool {
+        self.deliveries
+            .iter()
+            .all(|delivery| delivery.acked || delivery.attempts >= Self::max_attempts())
+    }
+
+    // Number of relays that must acknowledge an event
+    // for it to leave the outbox. Defaults to 1.
+    pub fn min_acks() -> usize {
+        env::var("OUTBOX_MIN_ACKS")
+            .unwrap_or("1".to_string())
+            .parse::<usize>()
+            .unwrap_or(1)
+    }
+
+    // Number of attempts made on a relay before giving up. Defaults to 10.
+    pub fn max_attempts() -> u32 {
+        env::var("OUTBOX_MAX_ATTEMPTS")
+            .unwrap_or("10".to_string())
+            .parse::<u32>()
+            .unwrap_or(10)
+    }
+
+    // Delay in seconds before the next attempt, doubled after each failed one.
+    // The base delay is provided by `OUTBOX_RETRY_DELAY` and defaults to 30 seconds.
+    // The delay never exceeds a day.
+    pub fn backoff(attempts: u32) -> i64 {
+        let base = env::var("OUTBOX_RETRY_DELAY")
+            .unwrap_or("30".to_string())
+            .parse::<i64>()
+            .unwrap_or(30);
+
+        let factor = 2_i64.saturating_pow(attempts.saturating_sub(1));
+
+        base.saturating_mul(factor).min(86400)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_outbox_event_deliveries() {
+        let targets = ["wss://a", "wss://b"].map(String::from).to_vec();
+
+        // An acknowledged event never enters the outbox
+        assert!(OutboxEvent::new(
+            "f",
+            "e",
+            "p",
+            "id",
+            String::new(),
+            &targets,
+            &targets[..1],
+            0
+        )
+        .is_none());
+
+        let mut event =
+            OutboxEvent::new("f", "e", "p", "id", String::new(), &targets, &[], 0).unwrap();
+
+        assert_eq!(event.required_acks, 1);
+        assert_eq!(event.status, OutboxStatus::Pending);
+        assert!(!event.is_delivered());
+        assert!(!event.deliveries[0].is_due(0));
+        assert!(event.deliveries[0].is_due(OutboxEvent::backoff(1)));
+
+        event.deliveries[0].fail("timeout".to_string(), 100);
+        assert_eq!(event.deliveries[0].attempts, 2);
+        assert_eq!(
+            event.deliveries[0].next_attempt,
+            100 + OutboxEvent::backoff(2)
+        );
+
+        event.deliveries[1].acked = true;
+        assert!(event.is_delivered());
+        assert_eq!(event.get_acked_relays(), ["wss://b".to_string()].to_vec());
+    }
+
+    #[test]
+    fn test_outbox_backoff() {
+        assert_eq!(OutboxEvent::backoff(2), 2 * OutboxEvent::backoff(1));
+        assert_eq!(OutboxEvent::backoff(3), 4 * OutboxEvent::backoff(1));
+        assert_eq!(OutboxEvent::backoff(100), 86400);
+    }
+}
@@ -0,0 +1,63 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// An entry that could not be processed during a tick.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FailedEntry {
+    // The id (guid) of the entry
+    pub entry_id: String,
+    pub reason: String,
+}
+
+/// The outcome of a single processing of a feed.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct TickReport {
+    // Unix timestamp of the processing
+    pub timestamp: i64,
+    // Ids of the entries published
+    #[serde(default)]
+    pub published: Vec<String>,
+    // Ids of the edited entries the `on_update` policy was applied to
+    #[serde(default)]
+    pub updated: Vec<String>,
+    // Ids of the retracted entries whose events were deleted
+    #[serde(default)]
+    pub deleted: Vec<String>,
+    // Number of entries already known and left untouched
+    #[serde(default)]
+    pub skipped: u64,
+    // Entries that failed. They are retried on the next tick
+    #[serde(default)]
+    pub failed: Vec<FailedEntry>,
+    // Error that prevented the whole feed from being processed, e.g: fetch error
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+impl TickReport {
+    pub fn fail(&mut self, entry_id: &str, reason: String) {
+        self.failed.push(FailedEntry {
+            entry_id: entry_id.to_string(),
+            reason,
+        });
+    }
+}
+
+impl fmt::Display for TickReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(error) = &self.error {
+            return write!(f, "failed : {}", error);
+        }
+
+        write!(
+            f,
+            "{} published, {} updated, {} deleted, {} skipped, {} failed",
+            self.published.len(),
+            self.updated.len(),
+            self.deleted.len(),
+            self.skipped,
+            self.failed.len()
+        )
+    }
+}
@@ -0,0 +1,431 @@
+use std::{collections::HashMap, sync::Arc};
+
+use log::{error, info};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+
+use super::{
+    cache::HttpCache,
+    errors::{FeedError, RETAINED_ERRORS},
+    file::{FileSnapshot, FileStore},
+    health::FeedHealth,
+    ledger::Publication,
+    memory::MemoryStore,
+    outbox::{OutboxEvent, OutboxStatus, RETAINED_FAILED_EVENTS},
+    report::TickReport,
+};
+
+/// An event published for an entry by a profile.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PublishedEvent {
+    pub profile_id: String,
+    // Hex representation of the event id
+    pub event_id: String,
+}
+
+/// What is left to send for an entry on behalf of a profile.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum PendingAction {
+    // The entry is published
+    Publish,
+    // The `on_update` policy of the feed is applied to the entry
+    Update,
+}
+
+/// A profile the current version of an entry could not be sent for.
+/// The entry is sent again for the profile on the next ticks.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PendingProfile {
+    pub profile_id: String,
+    pub action: PendingAction,
+    // The relays the entry still has to be sent to, every relay of the profile if empty
+    #[serde(default)]
+    pub relays: Vec<String>,
+}
+
+/// The state retained for a single entry of a feed,
+/// used to detect the entries edited after their publication.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct EntryRecord {
+    // Hash of the content of the entry
+    pub hash: String,
+    // Unix timestamp of the `updated` field of the entry
+    #[serde(default)]
+    pub updated: Option<i64>,
+    // The events published for the entry
+    #[serde(default)]
+    pub events: Vec<PublishedEvent>,
+    // The profiles the entry still has to be sent for
+    #[serde(default)]
+    pub pending: Vec<PendingProfile>,
+}
+
+impl EntryRecord {
+    // Tells if the entry described by `other` is an edited version
+    // of the recorded one. When both provide an `updated` date, the
+    // entry must also be more recent so feeds with volatile content
+    // (e.g: comments count) are not considered edited on each tick.
+    pub fn is_outdated_by(&self, other: &EntryRecord) -> bool {
+        if self.hash == other.hash {
+            return false;
+        }
+
+        match (self.updated, other.updated) {
+            (Some(updated), Some(other_updated)) => other_updated > updated,
+            _ => true,
+        }
+    }
+}
+
+/// The state retained for a single feed.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct FeedRecord {
+    #[serde(default)]
+    pub entries: Vec<String>,
+    // Unix timestamp of the last time the feed has been processed
+    #[serde(default)]
+    pub last_run: Option<i64>,
+    // Records of the entries, indexed by entry id
+    #[serde(default)]
+    pub entry_records: HashMap<String, EntryRecord>,
+    // Ledger of the publications of the feed, newest first
+    #[serde(default)]
+    pub publications: Vec<Publication>,
+    // Events waiting to be acknowledged by enough relays
+    #[serde(default)]
+    pub outbox: Vec<OutboxEvent>,
+    // Number of errors that occured while processing the feed
+    #[serde(default)]
+    pub error_count: u64,
+    // Last errors that occured while processing the feed, newest first
+    #[serde(default)]
+    pub errors: Vec<FeedError>,
+    // Report of the last processing of the feed
+    #[serde(default)]
+    pub last_report: Option<TickReport>,
+    #[serde(default)]
+    pub health: FeedHealth,
+    #[serde(default)]
+    pub http_cache: HttpCache,
+}
+
+impl FeedRecord {
+    // Adds publications on top of the ledger and drops the oldest ones
+    pub fn add_publications(&mut self, publications: Vec<Publication>) {
+        let mut publications: Vec<Publication> = publications.into_iter().rev().collect();
+        publications.append(&mut self.publications);
+        publications.truncate(Publication::ledger_size());
+        self.publications = publications;
+    }
+
+    // Adds errors on top of the retained ones and counts them
+    pub fn add_errors(&mut self, errors: Vec<FeedError>) {
+        self.error_count += errors.len() as u64;
+
+        let mut errors: Vec<FeedError> = errors.into_iter().rev().collect();
+        errors.append(&mut self.errors);
+        errors.truncate(RETAINED_ERRORS);
+        self.errors = errors;
+    }
+
+    // Replaces the publication of the ledger with the same event id.
+    // Returns false if the publication is not found.
+    pub fn update_publication(&mut self, publication: Publication) -> bool {
+        match self
+            .publications
+            .iter_mut()
+            .find(|p| p.event_id == publication.event_id)
+        {
+            Some(p) => {
+                *p = publication;
+                true
+            }
+            None => false,
+        }
+    }
+
+    // Replaces the outbox event with the same event id.
+    // Delivered events leave the outbox and the oldest failed ones are dropped.
+    // Returns false if the event is not found.
+    pub fn update_outbox_event(&mut self, event: OutboxEvent) -> bool {
+        let Some(idx) = self
+            .outbox
+            .iter()
+            .position(|e| e.event_id == event.event_id)
+        else {
+            return false;
+        };
+
+        match event.is_delivered() {
+            true => _ = self.outbox.remove(idx),
+            false => self.outbox[idx] = event,
+        }
+
+        // Events are added to the outbox in chronological order
+        let failed = self
+            .outbox
+            .iter()
+            .filter(|e| e.status == OutboxStatus::Failed)
+            .count();
+        let mut excess = failed.saturating_sub(RETAINED_FAILED_EVENTS);
+
+        self.outbox.retain(|e| {
+            if excess > 0 && e.status == OutboxStatus::Failed {
+                excess -= 1;
+                return false;
+            }
+            true
+        });
+
+        true
+    }
+}
+
+/// The [`SeenStore`] trait describes a backend that retains
+/// the ids of the entries already processed for each feed.
+///
+/// Entries are indexed by feed id so the retained state survives
+/// jobs being rescheduled and, for persistent backends, restarts.
+pub trait SeenStore: Send {
+    // Retrieves the retained entries ids of a feed.
+    // Returns `None` if the feed has never been stored.
+    fn get(&self, feed_id: &str) -> Option<Vec<String>>;
+
+    // Replaces the retained entries ids of a feed
+    fn set(&mut self, feed_id: &str, entries: Vec<String>);
+
+    // Retrieves the unix timestamp of the last processing of a feed
+    fn get_last_run(&self, feed_id: &str) -> Option<i64>;
+
+    // Records the unix timestamp of the last processing of a feed
+    fn set_last_run(&mut self, feed_id: &str, timestamp: i64);
+
+    // Retrieves the records of the entries of a feed
+    fn get_entry_records(&self, feed_id: &str) -> HashMap<String, EntryRecord>;
+
+    // Replaces the records of the entries of a feed
+    fn set_entry_records(&mut self, feed_id: &str, records: HashMap<String, EntryRecord>);
+
+    // Retrieves the ledger of the publications of a feed, newest first
+    fn get_publications(&self, feed_id: &str) -> Vec<Publication>;
+
+    // Retrieves a publication of any feed by its event id
+    fn get_publication(&self, event_id: &str) -> Option<Publication>;
+
+    // Adds publications, in chronological order, to the ledger of a feed
+    fn add_publications(&mut self, feed_id: &str, publications: Vec<Publication>);
+
+    // Replaces a publication of the ledger of a feed
+    fn update_publication(&mut self, publication: Publication);
+
+    // Retrieves the events of the outbox of every feed
+    fn get_outbox(&self) -> Vec<OutboxEvent>;
+
+    // Adds events to the outbox of a feed
+    fn add_outbox_events(&mut self, feed_id: &str, events: Vec<OutboxEvent>);
+
+    // Replaces an event of the outbox. Delivered events are removed.
+    // Events of removed feeds are ignored.
+    fn update_outbox_event(&mut self, event: OutboxEvent);
+
+    // Retrieves the number of errors that occured for a feed
+    fn get_error_count(&self, feed_id: &str) -> u64;
+
+    // Retrieves the last errors of a feed, newest first
+    fn get_errors(&self, feed_id: &str) -> Vec<FeedError>;
+
+    // Records errors, in chronological order, for a feed
+    fn add_errors(&mut self, feed_id: &str, errors: Vec<FeedError>);
+
+    // Retrieves the report of the last processing of a feed
+    fn get_last_report(&self, feed_id: &str) -> Option<TickReport>;
+
+    // Replaces the report of the last processing of a feed
+    fn set_last_report(&mut self, feed_id: &str, report: TickReport);
+
+    // Retrieves the health of a feed
+    fn get_health(&self, feed_id: &str) -> FeedHealth;
+
+    // Replaces the health of a feed
+    fn set_health(&mut self, feed_id: &str, health: FeedHealth);
+
+    // Retrieves the HTTP cache state of a feed
+    fn get_http_cache(&self, feed_id: &str) -> HttpCache;
+
+    // Replaces the HTTP cache state of a feed
+    fn set_http_cache(&mut self, feed_id: &str, cache: HttpCache);
+
+    // Removes a feed from the store
+    fn remove(&mut self, feed_id: &str);
+
+    // Retrieves the state to be persisted if the store changed since the last flush.
+    // Stores kept in memory have nothing to persist.
+    fn take_snapshot(&mut self) -> Option<FileSnapshot> {
+        None
+    }
+}
+
+pub type SharedSeenStore = Arc<Mutex<Box<dyn SeenStore>>>;
+
+// Persists the changes made to a store since the last flush.
+// The store lock is released while the file is written.
+pub async fn flush_store(store: &SharedSeenStore) {
+    let Some(snapshot) = store.lock().await.take_snapshot() else {
+        return;
+    };
+
+    if let Err(e) = tokio::task::spawn_blocking(move || snapshot.write()).await {
+        error!("Error writing store file : {}", e);
+    }
+}
+
+// Builds the store to be used by the application.
+// Takes an optional `path` to a file in which the entries will be persisted.
+// If no path is provided, entries are only kept in memory.
+// Returns an error if the store file can not be read.
+pub fn load_store(path: &Option<String>) -> Result<SharedSeenStore, String> {
+    let store: Box<dyn SeenStore> = match path {
+        Some(path) => {
+            info!(
+                "Found store path argument. Loading seen entries from {}",
+                path
+            );
+            match FileStore::new(path) {
+                Ok(store) => Box::new(store),
+                Err(e) => return Err(format!("Store {} could not be loaded : {}", path, e)),
+            }
+        }
+        None => Box::new(MemoryStore::new()),
+    };
+
+    Ok(Arc::new(Mutex::new(store)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_load_memory_store() {
+        let store = load_store(&None).unwrap();
+        let mut store_lock = store.lock().await;
+
+        assert_eq!(store_lock.get("test"), None);
+
+        store_lock.set("test", ["a".to_string()].to_vec());
+        assert_eq!(store_lock.get("test"), Some(["a".to_string()].to_vec()));
+        assert_eq!(store_lock.get_last_run("test"), None);
+
+        store_lock.set_last_run("test", 1700000000);
+        assert_eq!(store_lock.get_last_run("test"), Some(1700000000));
+
+        assert!(store_lock.get_entry_records("test").is_empty());
+
+        let mut records = HashMap::new();
+        records.insert("a".to_string(), EntryRecord::default());
+        store_lock.set_entry_records("test", records.clone());
+        assert_eq!(store_lock.get_entry_records("test"), records);
+    }
+
+    #[tokio::test]
+    async fn test_flush_file_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("store.json");
+        let path = Some(path.to_str().unwrap().to_string());
+
+        let store = load_store(&path).unwrap();
+        store.lock().await.set("test", ["a".to_string()].to_vec());
+        store.lock().await.set_last_run("test", 1700000000);
+        flush_store(&store).await;
+
+        let store = load_store(&path).unwrap();
+        let store_lock = store.lock().await;
+        assert_eq!(store_lock.get("test"), Some(["a".to_string()].to_vec()));
+        assert_eq!(store_lock.get_last_run("test"), Some(1700000000));
+    }
+
+    #[test]
+    fn test_entry_record_is_outdated_by() {
+        let record = |hash: &str, updated: Option<i64>| EntryRecord {
+            hash: hash.to_string(),
+            updated,
+            events: Vec::new(),
+            pending: Vec::new(),
+        };
+
+        // Same content
+        assert!(!record("a", None).is_outdated_by(&record("a", Some(10))));
+
+        // Edited content without dates
+        assert!(record("a", None).is_outdated_by(&record("b", None)));
+
+        // Edited content with a more recent date
+        assert!(record("a", Some(10)).is_outdated_by(&record("b", Some(20))));
+
+        // Content changed but the entry is not declared updated
+        assert!(!record("a", Some(10)).is_outdated_by(&record("b", Some(10))));
+    }
+
+    #[test]
+    fn test_feed_record_errors() {
+        let error = |timestamp: i64| FeedError {
+            timestamp,
+            kind: "fetch".to_string(),
+            profile_id: None,
+            entry_id: None,
+            message: "timeout".to_string(),
+        };
+
+        let mut record = FeedRecord::default();
+        record.add_errors((0..RETAINED_ERRORS as i64).map(error).collect());
+        record.add_errors([error(100)].to_vec());
+
+        assert_eq!(record.error_count, RETAINED_ERRORS as u64 + 1);
+        assert_eq!(record.errors.len(), RETAINED_ERRORS);
+        assert_eq!(record.errors[0], error(100));
+        assert_eq!(record.errors[1], error(RETAINED_ERRORS as i64 - 1));
+    }
+
+    #[test]
+    fn test_feed_record_failed_outbox_events() {
+        let targets = ["wss://a".to_string()].to_vec();
+        let event = |id: i64| {
+            OutboxEvent::new(
+                "f",
+                "e",
+                "p",
+                &id.to_string(),
+                String::new(),
+                &targets,
+                &[],
+                id,
+            )
+            .unwrap()
+        };
+
+        let mut record = FeedRecord {
+            outbox: (0..RETAINED_FAILED_EVENTS as i64 + 2).map(event).collect(),
+            ..Default::default()
+        };
+
+        for id in 0..RETAINED_FAILED_EVENTS as i64 + 1 {
+            let mut failed = event(id);
+            failed.status = OutboxStatus::Failed;
+            assert!(record.update_outbox_event(failed));
+        }
+
+        // The oldest failed event is dropped, pending ones are kept
+        assert_eq!(record.outbox.len(), RETAINED_FAILED_EVENTS + 1);
+        assert_eq!(record.outbox[0].event_id, "1");
+        assert_eq!(record.outbox.last().unwrap().status, OutboxStatus::Pending);
+        assert_eq!(
+            record
+                .outbox
+                .iter()
+                .filter(|e| e.status == OutboxStatus::Failed)
+                .count(),
+            RETAINED_FAILED_EVENTS
+        );
+    }
+}
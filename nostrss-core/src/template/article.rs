@@ -0,0 +1,160 @@
+use feed_rs::model::Entry;
+use nostr_sdk::{Tag, Timestamp, UncheckedUrl};
+
+use super::markdown::MarkdownConverter;
+
+/// A feed entry prepared to be published
+/// as a long-form content (NIP-23).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Article {
+    // Identifier of the article, used as `d` tag so
+    // the updates of an entry replace the previous version.
+    pub identifier: String,
+    pub title: Option<String>,
+    pub summary: Option<String>,
+    pub image: Option<String>,
+    pub published_at: Option<Timestamp>,
+    // Markdown content of the article
+    pub content: String,
+}
+
+impl From<&Entry> for Article {
+    fn from(entry: &Entry) -> Self {
+        // Full content is preferred. Summary is used for feeds
+        // that only provide an excerpt of their entries.
+        let html = match entry.content.as_ref().and_then(|c| c.body.clone()) {
+            Some(body) => body,
+            None => entry
+                .summary
+                .as_ref()
+                .map(|s| s.content.clone())
+                .unwrap_or_default(),
+        };
+
+        let summary = entry
+            .summary
+            .as_ref()
+            .map(|summary| MarkdownConverter::convert(&summary.content))
+            .filter(|summary| !summary.is_empty());
+
+        let published_at = entry
+            .published
+            .or(entry.updated)
+            .map(|date| Timestamp::from(date.timestamp().max(0) as u64));
+
+        Self {
+            identifier: Self::get_identifier(&entry.id),
+            title: entry.title.as_ref().map(|title| title.content.clone()),
+            summary,
+            image: Self::get_image(entry),
+            published_at,
+            content: MarkdownConverter::convert(&html),
+        }
+    }
+}
+
+impl Article {
+    // Derives the `d` tag of an article from the entry id.
+    // Entry ids are often urls, so they are hashed to keep the tag short.
+    pub fn get_identifier(entry_id: &str) -> String {
+        format!("{:x}", md5::compute(entry_id))
+    }
+
+    // Retrieves the first image attached to the entry
+    fn get_image(entry: &Entry) -> Option<String> {
+        for media in &entry.media {
+            if let Some(thumbnail) = media.thumbnails.first() {
+                return Some(thumbnail.image.uri.clone());
+            }
+
+            for content in &media.content {
+                let is_image = content
+                    .content_type
+                    .as_ref()
+                    .is_some_and(|t| t.ty().as_str() == "image");
+
+                if let (true, Some(url)) = (is_image, &content.url) {
+                    return Some(url.to_string());
+                }
+            }
+        }
+
+        None
+    }
+
+    // Builds the NIP-23 metadata tags of the article
+    pub fn get_tags(&self) -> Vec<Tag> {
+        let mut tags = vec![Tag::Identifier(self.identifier.clone())];
+
+        if let Some(title) = &self.title {
+            tags.push(Tag::Title(title.clone()));
+        }
+
+        if let Some(summary) = &self.summary {
+            tags.push(Tag::Summary(summary.clone()));
+        }
+
+        if let Some(image) = &self.image {
+            tags.push(Tag::Image(UncheckedUrl::from(image.clone()), None));
+        }
+
+        if let Some(published_at) = self.published_at {
+            tags.push(Tag::PublishedAt(published_at));
+        }
+
+        tags
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use feed_rs::parser;
+
+    #[test]
+    fn test_article_from_entry() {
+        let rss = r#"<?xml version="1.0"?>
+<rss version="2.0" xmlns:content="http://purl.org/rss/1.0/modules/content/" xmlns:media="http://search.yahoo.com/mrss/">
+<channel>
+<title>Blog</title>
+<item>
+<guid>https://blog.example.com/first-post</guid>
+<title>First post</title>
+<link>https://blog.example.com/first-post</link>
+<description>A &lt;b&gt;short&lt;/b&gt; excerpt</description>
+<content:encoded><![CDATA[<h1>First post</h1><p>Some <em>content</em>.</p>]]></content:encoded>
+<media:thumbnail url="https://blog.example.com/cover.png" />
+<pubDate>Tue, 14 Nov 2023 22:13:20 GMT</pubDate>
+</item>
+</channel>
+</rss>"#;
+
+        let feed = parser::parse(rss.as_bytes()).unwrap();
+        let entry = &feed.entries[0];
+
+        let article = Article::from(entry);
+
+        assert_eq!(
+            article.identifier,
+            Article::get_identifier("https://blog.example.com/first-post")
+        );
+        assert_eq!(article.title, Some("First post".to_string()));
+        assert_eq!(article.summary, Some("A **short** excerpt".to_string()));
+        assert_eq!(
+            article.image,
+            Some("https://blog.example.com/cover.png".to_string())
+        );
+        assert_eq!(article.published_at, Some(Timestamp::from(1700000000)));
+        assert_eq!(article.content, "# First post\n\nSome *content*.");
+
+        let tags = article.get_tags();
+        assert_eq!(tags.len(), 5);
+        assert_eq!(tags[0], Tag::Identifier(article.identifier.clone()));
+    }
+
+    #[test]
+    fn test_article_identifier_is_stable() {
+        assert_eq!(Article::get_identifier("a"), Article::get_identifier("a"));
+        assert_ne!(Article::get_identifier("a"), Article::get_identifier("b"));
+    }
+}
@@ -0,0 +1,360 @@
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+// Tags whose content is never rendered
+const SKIPPED_TAGS: [&str; 4] = ["script", "style", "head", "noscript"];
+
+// Tags rendered as separated blocks of text
+const BLOCK_TAGS: [&str; 12] = [
+    "p",
+    "div",
+    "section",
+    "article",
+    "header",
+    "footer",
+    "main",
+    "aside",
+    "figure",
+    "figcaption",
+    "table",
+    "tr",
+];
+
+// Comments, opening and closing tags, and the text between them
+static TOKEN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?s)<!--.*?-->|<(/?)([a-zA-Z][a-zA-Z0-9]*)([^>]*)>|[^<]+|<").unwrap()
+});
+
+static ATTRIBUTE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"([a-zA-Z_:][-a-zA-Z0-9_:.]*)\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s>]+))"#).unwrap()
+});
+
+static ENTITY: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"&(#[0-9]+|#[xX][0-9a-fA-F]+|[a-zA-Z]+);").unwrap());
+
+static WHITESPACES: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\s+").unwrap());
+
+static EMPTY_LINES: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\n{3,}").unwrap());
+
+enum ListKind {
+    Ordered(usize),
+    Unordered,
+}
+
+/// Converts the HTML content of feed entries into Markdown.
+///
+/// The converter only handles the subset of HTML commonly found into
+/// feeds (paragraphs, headings, emphasis, links, images, lists, quotes and code).
+/// Unknown tags are dropped and their text content is kept.
+pub struct MarkdownConverter {
+    // Output buffers. A new buffer is pushed for each opened blockquote
+    buffers: Vec<String>,
+    lists: Vec<ListKind>,
+    links: Vec<Option<String>>,
+    pre: bool,
+    skip: Option<String>,
+}
+
+impl MarkdownConverter {
+    pub fn convert(html: &str) -> String {
+        let mut converter = Self {
+            buffers: vec![String::new()],
+            lists: Vec::new(),
+            links: Vec::new(),
+            pre: false,
+            skip: None,
+        };
+
+        for token in TOKEN.captures_iter(html) {
+            let raw = token.get(0).unwrap().as_str();
+
+            match token.get(2) {
+                Some(name) => {
+                    let name = name.as_str().to_lowercase();
+                    let closing = !token.get(1).unwrap().as_str().is_empty();
+                    let attributes = token.get(3).map_or("", |a| a.as_str());
+                    converter.tag(&name, closing, attributes);
+                }
+                None if raw.starts_with("<!--") => {}
+                None => converter.text(raw),
+            }
+        }
+
+        while converter.buffers.len() > 1 {
+            converter.close_blockquote();
+        }
+
+        Self::normalize(&converter.buffers[0])
+    }
+
+    fn output(&mut self) -> &mut String {
+        self.buffers.last_mut().unwrap()
+    }
+
+    fn tag(&mut self, name: &str, closing: bool, attributes: &str) {
+        if let Some(skipped) = &self.skip {
+            if closing && skipped == name {
+                self.skip = None;
+            }
+            return;
+        }
+
+        if SKIPPED_TAGS.contains(&name) {
+            if !closing {
+                self.skip = Some(name.to_string());
+            }
+            return;
+        }
+
+        if BLOCK_TAGS.contains(&name) {
+            self.block();
+            return;
+        }
+
+        match (name, closing) {
+            ("br", _) => self.output().push('\n'),
+            ("hr", _) => {
+                self.block();
+                self.output().push_str("---");
+                self.block();
+            }
+            ("h1" | "h2" | "h3" | "h4" | "h5" | "h6", false) => {
+                let level = name[1..].parse::<usize>().unwrap_or(1);
+                self.block();
+                self.output().push_str(&format!("{} ", "#".repeat(level)));
+            }
+            ("h1" | "h2" | "h3" | "h4" | "h5" | "h6", true) => self.block(),
+            ("strong" | "b", _) => self.output().push_str("**"),
+            ("em" | "i", _) => self.output().push('*'),
+            ("code", _) if !self.pre => self.output().push('`'),
+            ("pre", false) => {
+                self.block();
+                self.output().push_str("```\n");
+                self.pre = true;
+            }
+            ("pre", true) => {
+                self.pre = false;
+                if !self.output().ends_with('\n') {
+                    self.output().push('\n');
+                }
+                self.output().push_str("```");
+                self.block();
+            }
+            ("a", false) => {
+                let href = Self::attribute(attributes, "href");
+                if href.is_some() {
+                    self.output().push('[');
+                }
+                self.links.push(href);
+            }
+            ("a", true) => {
+                if let Some(Some(href)) = self.links.pop() {
+                    self.output().push_str(&format!("]({})", href));
+                }
+            }
+            ("img", _) => {
+                if let Some(src) = Self::attribute(attributes, "src") {
+                    let alt = Self::attribute(attributes, "alt").unwrap_or_default();
+                    self.output().push_str(&format!("![{}]({})", alt, src));
+                }
+            }
+            ("ul", false) => {
+                self.line();
+                self.lists.push(ListKind::Unordered);
+            }
+            ("ol", false) => {
+                self.line();
+                self.lists.push(ListKind::Ordered(0));
+            }
+            ("ul" | "ol", true) => {
+                self.lists.pop();
+                if self.lists.is_empty() {
+                    self.block();
+                }
+            }
+            ("li", false) => {
+                self.line();
+                let indent = "  ".repeat(self.lists.len().saturating_sub(1));
+                let marker = match self.lists.last_mut() {
+                    Some(ListKind::Ordered(index)) => {
+                        *index += 1;
+                        format!("{}. ", index)
+                    }
+                    _ => "- ".to_string(),
+                };
+                self.output().push_str(&format!("{}{}", indent, marker));
+            }
+            ("blockquote", false) => {
+                self.block();
+                self.buffers.push(String::new());
+            }
+            ("blockquote", true) => self.close_blockquote(),
+            _ => {}
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        if self.skip.is_some() {
+            return;
+        }
+
+        let text = Self::decode_entities(text);
+
+        if self.pre {
+            self.output().push_str(&text);
+            return;
+        }
+
+        let collapsed = WHITESPACES.replace_all(&text, " ");
+        let output = self.output();
+
+        // Leading spaces are meaningless at the start of a line
+        let collapsed = match output.is_empty() || output.ends_with('\n') || output.ends_with(' ') {
+            true => collapsed.trim_start(),
+            false => &collapsed,
+        };
+
+        output.push_str(collapsed);
+    }
+
+    // Starts a new line if the current one is not empty
+    fn line(&mut self) {
+        let output = self.output();
+        let trimmed_len = output.trim_end_matches(' ').len();
+        output.truncate(trimmed_len);
+
+        if !output.is_empty() && !output.ends_with('\n') {
+            output.push('\n');
+        }
+    }
+
+    // Separates the next content with an empty line
+    fn block(&mut self) {
+        self.line();
+        let output = self.output();
+
+        if !output.is_empty() && !output.ends_with("\n\n") {
+            output.push('\n');
+        }
+    }
+
+    fn close_blockquote(&mut self) {
+        if self.buffers.len() < 2 {
+            return;
+        }
+
+        let quote = self.buffers.pop().unwrap();
+        let quote = Self::normalize(&quote)
+            .lines()
+            .map(|line| match line.is_empty() {
+                true => ">".to_string(),
+                false => format!("> {}", line),
+            })
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        self.output().push_str(&quote);
+        self.block();
+    }
+
+    fn attribute(attributes: &str, name: &str) -> Option<String> {
+        let captures = ATTRIBUTE
+            .captures_iter(attributes)
+            .find(|captures| captures[1].eq_ignore_ascii_case(name))?;
+
+        let value = captures
+            .get(2)
+            .or(captures.get(3))
+            .or(captures.get(4))?
+            .as_str();
+
+        Some(Self::decode_entities(value))
+    }
+
+    fn decode_entities(text: &str) -> String {
+        ENTITY
+            .replace_all(text, |captures: &regex::Captures| {
+                let entity = &captures[1];
+                let decoded = match entity {
+                    "amp" => Some('&'),
+                    "lt" => Some('<'),
+                    "gt" => Some('>'),
+                    "quot" => Some('"'),
+                    "apos" => Some('\''),
+                    "nbsp" => Some(' '),
+                    _ if entity.starts_with("#x") || entity.starts_with("#X") => {
+                        u32::from_str_radix(&entity[2..], 16)
+                            .ok()
+                            .and_then(char::from_u32)
+                    }
+                    _ if entity.starts_with('#') => {
+                        entity[1..].parse::<u32>().ok().and_then(char::from_u32)
+                    }
+                    _ => None,
+                };
+
+                match decoded {
+                    Some(c) => c.to_string(),
+                    None => captures[0].to_string(),
+                }
+            })
+            .to_string()
+    }
+
+    // Removes trailing spaces and redundant empty lines
+    fn normalize(markdown: &str) -> String {
+        let lines: Vec<&str> = markdown.lines().map(|line| line.trim_end()).collect();
+        let markdown = lines.join("\n");
+
+        EMPTY_LINES
+            .replace_all(&markdown, "\n\n")
+            .trim()
+            .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paragraphs_and_emphasis() {
+        let html = "<p>Hello <strong>nostr</strong>,</p>\n<p>this is <em>an</em> <code>article</code>.</p>";
+
+        assert_eq!(
+            MarkdownConverter::convert(html),
+            "Hello **nostr**,\n\nthis is *an* `article`."
+        );
+    }
+
+    #[test]
+    fn test_headings_links_and_images() {
+        let html = r#"<h2>Title</h2><p>Read <a href="https://example.com?a=1&amp;b=2">the doc</a></p><img src="https://example.com/a.png" alt="A picture">"#;
+
+        assert_eq!(
+            MarkdownConverter::convert(html),
+            "## Title\n\nRead [the doc](https://example.com?a=1&b=2)\n\n![A picture](https://example.com/a.png)"
+        );
+    }
+
+    #[test]
+    fn test_lists() {
+        let html = "<ul><li>one</li><li>two<ol><li>a</li><li>b</li></ol></li></ul><p>end</p>";
+
+        assert_eq!(
+            MarkdownConverter::convert(html),
+            "- one\n- two\n  1. a\n  2. b\n\nend"
+        );
+    }
+
+    #[test]
+    fn test_quotes_code_and_skipped_content() {
+        let html = "<script>alert('x')</script><blockquote><p>quoted</p><p>text</p></blockquote><pre><code>let a = 1;\nlet b = &lt;2&gt;;</code></pre>";
+
+        assert_eq!(
+            MarkdownConverter::convert(html),
+            "> quoted\n>\n> text\n\n```\nlet a = 1;\nlet b = <2>;\n```"
+        );
+    }
+}
@@ -1 +1,3 @@
+pub mod article;
+pub mod markdown;
 pub mod template;
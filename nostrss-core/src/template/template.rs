@@ -3,11 +3,29 @@ use feed_rs::model::Entry;
 use log::error;
 use new_string_template::{error::TemplateError, template::Template};
 use std::env;
-use std::{collections::HashMap, fs};
+use std::{collections::HashMap, fmt, fs};
 
 #[derive(Debug)]
 pub enum TemplateParserError {
     LoadError,
+    // No template provided for the feed and no `DEFAULT_TEMPLATE` in env
+    MissingDefaultTemplate,
+    RenderError(TemplateError),
+}
+
+impl fmt::Display for TemplateParserError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TemplateParserError::LoadError => write!(f, "Template file could not be loaded"),
+            TemplateParserError::MissingDefaultTemplate => {
+                write!(
+                    f,
+                    "No default template found. Declare DEFAULT_TEMPLATE in env file"
+                )
+            }
+            TemplateParserError::RenderError(e) => write!(f, "Template rendering failed : {}", e),
+        }
+    }
 }
 
 /// Provides template rendering to the application
@@ -29,24 +47,24 @@ impl TemplateProcessor {
                     }
                 }
             }
-            None => Ok(Self::get_default_env_template()),
+            None => Self::get_default_env_template(),
         }
     }
 
     // Parses template from environment
-    fn get_default_env_template() -> String {
+    fn get_default_env_template() -> Result<String, TemplateParserError> {
         match env::var("DEFAULT_TEMPLATE") {
-            Ok(val) => val,
+            Ok(val) => Ok(val),
             Err(e) => {
                 error!("{}", e);
-                panic!();
+                Err(TemplateParserError::MissingDefaultTemplate)
             }
         }
     }
 
     // Parses template with data
-    pub fn parse(data: Feed, entry: Entry) -> Result<String, TemplateError> {
-        let template = Self::load_template(data.clone().template).unwrap();
+    pub fn parse(data: Feed, entry: Entry) -> Result<String, TemplateParserError> {
+        let template = Self::load_template(data.clone().template)?;
         let mut map = Self::parse_entry_to_hashmap(entry);
 
         map.insert("name", data.name.clone());
@@ -61,7 +79,7 @@ impl TemplateProcessor {
 
         let templ = Template::new(template);
 
-        templ.render(&map)
+        templ.render(&map).map_err(TemplateParserError::RenderError)
     }
 
     // created a HashMap from the entry data
@@ -75,7 +93,13 @@ impl TemplateProcessor {
         };
 
         map.insert("title", title);
-        map.insert("url", data.links[0].clone().href);
+        // Entries are not required to provide a link
+        let url = match data.links.first() {
+            Some(link) => link.href.clone(),
+            None => "".to_string(),
+        };
+
+        map.insert("url", url);
 
         let summary = match data.summary {
             Some(summary) => summary.content,
@@ -208,6 +232,9 @@ mod tests {
 
         assert_eq!(hashmap["title"], "Test content");
         assert_eq!(hashmap["url"], "https://www.nostr.info");
+
+        let hashmap = TemplateProcessor::parse_entry_to_hashmap(Entry::default());
+        assert_eq!(hashmap["url"], "");
     }
 
     #[test]
@@ -4,6 +4,8 @@
 pub struct StartJobRequest {
     #[prost(string, required, tag = "1")]
     pub feed_id: ::prost::alloc::string::String,
+    #[prost(bool, optional, tag = "2")]
+    pub save: ::core::option::Option<bool>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, Copy, PartialEq, ::prost::Message)]
@@ -13,6 +15,8 @@ pub struct StartJobResponse {}
 pub struct StopJobRequest {
     #[prost(string, required, tag = "1")]
     pub feed_id: ::prost::alloc::string::String,
+    #[prost(bool, optional, tag = "2")]
+    pub save: ::core::option::Option<bool>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, Copy, PartialEq, ::prost::Message)]
@@ -53,6 +57,36 @@ pub struct FeedItem {
     pub cache_size: ::core::option::Option<u64>,
     #[prost(uint64, required, tag = "9")]
     pub pow_level: u64,
+    #[prost(bool, optional, tag = "10")]
+    pub paused: ::core::option::Option<bool>,
+    #[prost(bool, optional, tag = "11")]
+    pub mirror_deletions: ::core::option::Option<bool>,
+    /// The authentication of the feed. Secrets are masked
+    #[prost(string, optional, tag = "12")]
+    pub auth: ::core::option::Option<::prost::alloc::string::String>,
+    /// The additional headers of the feed, as `name: value`. Values are masked
+    #[prost(string, repeated, tag = "13")]
+    pub headers: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// The url of the proxy the feed is fetched through
+    #[prost(string, optional, tag = "14")]
+    pub proxy: ::core::option::Option<::prost::alloc::string::String>,
+    /// The events of a feed in shadow mode are not sent
+    #[prost(bool, optional, tag = "15")]
+    pub shadow: ::core::option::Option<bool>,
+    /// The file the events of the feed are written to while in shadow mode
+    #[prost(string, optional, tag = "16")]
+    pub shadow_file: ::core::option::Option<::prost::alloc::string::String>,
+    /// The kind of event entries are published as, e.g: `note` or `article`
+    #[prost(string, optional, tag = "17")]
+    pub kind: ::core::option::Option<::prost::alloc::string::String>,
+    /// The policy applied to edited entries, e.g: `ignore` or `correction`
+    #[prost(string, optional, tag = "18")]
+    pub on_update: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(message, optional, tag = "19")]
+    pub catch_up: ::core::option::Option<CatchUpItem>,
+    /// Watched feeds are processed when their file changes
+    #[prost(bool, optional, tag = "20")]
+    pub watch: ::core::option::Option<bool>,
 }
 /// === Feeds ===
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -86,6 +120,271 @@ pub struct DeleteFeedRequest {
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, Copy, PartialEq, ::prost::Message)]
 pub struct DeleteFeedResponse {}
+/// Patches a feed. Omitted fields, and empty lists,
+/// keep the current value of the feed.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateFeedRequest {
+    #[prost(string, required, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+    #[prost(string, optional, tag = "2")]
+    pub name: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "3")]
+    pub url: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "4")]
+    pub schedule: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, repeated, tag = "5")]
+    pub profiles: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(string, repeated, tag = "6")]
+    pub tags: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "7")]
+    pub template: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(uint64, optional, tag = "8")]
+    pub cache_size: ::core::option::Option<u64>,
+    #[prost(uint64, optional, tag = "9")]
+    pub pow_level: ::core::option::Option<u64>,
+    #[prost(bool, optional, tag = "10")]
+    pub save: ::core::option::Option<bool>,
+    /// The url of the proxy of the feed. An empty value removes the proxy
+    #[prost(string, optional, tag = "11")]
+    pub proxy: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(bool, optional, tag = "12")]
+    pub shadow: ::core::option::Option<bool>,
+    /// An empty value removes the shadow file
+    #[prost(string, optional, tag = "13")]
+    pub shadow_file: ::core::option::Option<::prost::alloc::string::String>,
+    /// The fields to remove from the feed, among `profiles`, `tags`, `template`,
+    /// `cache_size`, `proxy`, `shadow_file`, `auth` and `headers`.
+    /// A cleared field can not be provided in the same request.
+    /// Secrets can only be set through the feeds file.
+    #[prost(string, repeated, tag = "14")]
+    pub clear: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// The kind of event entries are published as, e.g: `note` or `article`
+    #[prost(string, optional, tag = "15")]
+    pub kind: ::core::option::Option<::prost::alloc::string::String>,
+    /// The policy applied to edited entries, e.g: `ignore` or `correction`
+    #[prost(string, optional, tag = "16")]
+    pub on_update: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(message, optional, tag = "17")]
+    pub catch_up: ::core::option::Option<CatchUpItem>,
+    #[prost(bool, optional, tag = "18")]
+    pub watch: ::core::option::Option<bool>,
+    #[prost(bool, optional, tag = "19")]
+    pub mirror_deletions: ::core::option::Option<bool>,
+}
+/// The catch-up policy of a feed. `count` is required by the `max_entries`
+/// policy and `seconds` by the `max_age` policy.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CatchUpItem {
+    #[prost(string, required, tag = "1")]
+    pub policy: ::prost::alloc::string::String,
+    #[prost(uint64, optional, tag = "2")]
+    pub count: ::core::option::Option<u64>,
+    #[prost(uint64, optional, tag = "3")]
+    pub seconds: ::core::option::Option<u64>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateFeedResponse {
+    #[prost(message, required, tag = "1")]
+    pub feed: FeedItem,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DiscoveredFeedItem {
+    #[prost(string, required, tag = "1")]
+    pub url: ::prost::alloc::string::String,
+    #[prost(string, optional, tag = "2")]
+    pub title: ::core::option::Option<::prost::alloc::string::String>,
+    /// The media type advertised by the web page, e.g: `application/rss+xml`
+    #[prost(string, optional, tag = "3")]
+    pub content_type: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DiscoverFeedsRequest {
+    /// The url of a feed or of a web page referencing feeds
+    #[prost(string, required, tag = "1")]
+    pub url: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DiscoverFeedsResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub feeds: ::prost::alloc::vec::Vec<DiscoveredFeedItem>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FeedProblemItem {
+    /// The setting of the feed the problem relates to, e.g: `schedule`
+    #[prost(string, required, tag = "1")]
+    pub field: ::prost::alloc::string::String,
+    #[prost(string, required, tag = "2")]
+    pub message: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ValidateFeedRequest {
+    /// The id of a feed of the instance
+    #[prost(string, optional, tag = "1")]
+    pub feed_id: ::core::option::Option<::prost::alloc::string::String>,
+    /// The definition of a feed to check before adding it
+    #[prost(message, optional, tag = "2")]
+    pub feed: ::core::option::Option<FeedItem>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ValidateFeedResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub problems: ::prost::alloc::vec::Vec<FeedProblemItem>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PreviewFeedRequest {
+    #[prost(string, required, tag = "1")]
+    pub feed_id: ::prost::alloc::string::String,
+    /// The number of entries to preview, from the top of the feed. Defaults to 1
+    #[prost(uint64, optional, tag = "2")]
+    pub entries: ::core::option::Option<u64>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EventPreviewItem {
+    #[prost(string, required, tag = "1")]
+    pub entry_id: ::prost::alloc::string::String,
+    #[prost(string, required, tag = "2")]
+    pub profile_id: ::prost::alloc::string::String,
+    #[prost(uint64, required, tag = "3")]
+    pub pow_level: u64,
+    /// The unsigned event, as JSON
+    #[prost(string, optional, tag = "4")]
+    pub event: ::core::option::Option<::prost::alloc::string::String>,
+    /// The reason the event could not be built
+    #[prost(string, optional, tag = "5")]
+    pub error: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PreviewFeedResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub events: ::prost::alloc::vec::Vec<EventPreviewItem>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RetractEntryRequest {
+    #[prost(string, required, tag = "1")]
+    pub feed_id: ::prost::alloc::string::String,
+    #[prost(string, required, tag = "2")]
+    pub entry_id: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct RetractEntryResponse {
+    /// Number of deletion events sent
+    #[prost(uint64, required, tag = "1")]
+    pub deletions: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FeedErrorItem {
+    #[prost(int64, required, tag = "1")]
+    pub timestamp: i64,
+    /// The kind of error, e.g: fetch, template, profile_not_found, invalid_keys, no_active_relay or pow
+    #[prost(string, required, tag = "2")]
+    pub kind: ::prost::alloc::string::String,
+    #[prost(string, optional, tag = "3")]
+    pub profile_id: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "4")]
+    pub entry_id: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, required, tag = "5")]
+    pub message: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FeedErrorsRequest {
+    #[prost(string, required, tag = "1")]
+    pub feed_id: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FeedErrorsResponse {
+    /// Number of errors that occured for the feed
+    #[prost(uint64, required, tag = "1")]
+    pub count: u64,
+    /// Last errors of the feed, newest first
+    #[prost(message, repeated, tag = "2")]
+    pub errors: ::prost::alloc::vec::Vec<FeedErrorItem>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FailedEntryItem {
+    #[prost(string, required, tag = "1")]
+    pub entry_id: ::prost::alloc::string::String,
+    #[prost(string, required, tag = "2")]
+    pub reason: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TickReportItem {
+    #[prost(int64, required, tag = "1")]
+    pub timestamp: i64,
+    #[prost(string, repeated, tag = "2")]
+    pub published: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(string, repeated, tag = "3")]
+    pub updated: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(string, repeated, tag = "4")]
+    pub deleted: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(uint64, required, tag = "5")]
+    pub skipped: u64,
+    #[prost(message, repeated, tag = "6")]
+    pub failed: ::prost::alloc::vec::Vec<FailedEntryItem>,
+    /// Set when the feed could not be processed at all, e.g: on fetch error
+    #[prost(string, optional, tag = "7")]
+    pub error: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FeedStatusRequest {
+    #[prost(string, required, tag = "1")]
+    pub feed_id: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FeedHealthItem {
+    /// Unix timestamp of the last fetch, successful or not
+    #[prost(int64, optional, tag = "1")]
+    pub last_fetch: ::core::option::Option<i64>,
+    /// Unix timestamp of the last successful fetch
+    #[prost(int64, optional, tag = "2")]
+    pub last_success: ::core::option::Option<i64>,
+    /// Number of fetches that failed since the last successful one
+    #[prost(uint64, required, tag = "3")]
+    pub consecutive_failures: u64,
+    #[prost(string, optional, tag = "4")]
+    pub last_error: ::core::option::Option<::prost::alloc::string::String>,
+    /// HTTP status of the last response of the remote feed
+    #[prost(uint32, optional, tag = "5")]
+    pub http_status: ::core::option::Option<u32>,
+    /// Number of entries of the feed on the last successful fetch
+    #[prost(uint64, required, tag = "6")]
+    pub entries_seen: u64,
+    /// Number of entries published since the feed is tracked
+    #[prost(uint64, required, tag = "7")]
+    pub entries_published: u64,
+    /// Unix timestamp of the next scheduled run. Unset if the feed is paused
+    #[prost(int64, optional, tag = "8")]
+    pub next_run: ::core::option::Option<i64>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FeedStatusResponse {
+    /// Report of the last processing of the feed. Unset until the feed is processed
+    #[prost(message, optional, tag = "1")]
+    pub last_report: ::core::option::Option<TickReportItem>,
+    #[prost(message, required, tag = "2")]
+    pub health: FeedHealthItem,
+}
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct FeedInfoRequest {
@@ -197,6 +496,169 @@ pub struct ProfileInfoResponse {
     #[prost(message, required, tag = "1")]
     pub profile: ProfileItem,
 }
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RelayItem {
+    #[prost(string, required, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(string, required, tag = "2")]
+    pub target: ::prost::alloc::string::String,
+    #[prost(bool, required, tag = "3")]
+    pub active: bool,
+    #[prost(string, optional, tag = "4")]
+    pub proxy: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(uint64, required, tag = "5")]
+    pub pow_level: u64,
+    /// Connection status of the relay. Ignored when adding a relay.
+    #[prost(string, optional, tag = "6")]
+    pub status: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct RelaysListRequest {}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RelaysListResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub relays: ::prost::alloc::vec::Vec<RelayItem>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RelayInfoRequest {
+    #[prost(string, required, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RelayInfoResponse {
+    #[prost(message, required, tag = "1")]
+    pub relay: RelayItem,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AddRelayRequest {
+    #[prost(message, required, tag = "1")]
+    pub relay: RelayItem,
+    #[prost(bool, optional, tag = "2")]
+    pub save: ::core::option::Option<bool>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct AddRelayResponse {}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DeleteRelayRequest {
+    #[prost(string, required, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(bool, optional, tag = "2")]
+    pub save: ::core::option::Option<bool>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct DeleteRelayResponse {}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PublicationItem {
+    #[prost(string, required, tag = "1")]
+    pub feed_id: ::prost::alloc::string::String,
+    #[prost(string, required, tag = "2")]
+    pub entry_id: ::prost::alloc::string::String,
+    #[prost(string, required, tag = "3")]
+    pub profile_id: ::prost::alloc::string::String,
+    #[prost(string, required, tag = "4")]
+    pub event_id: ::prost::alloc::string::String,
+    #[prost(uint64, required, tag = "5")]
+    pub kind: u64,
+    /// The relays the event was sent to
+    #[prost(string, repeated, tag = "6")]
+    pub targets: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// The relays that acknowledged the event
+    #[prost(string, repeated, tag = "7")]
+    pub relays: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(int64, required, tag = "8")]
+    pub timestamp: i64,
+    /// One of published, partial, failed or dry_run
+    #[prost(string, required, tag = "9")]
+    pub status: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListPublicationsRequest {
+    /// Publications of every feed are listed when no feed is provided
+    #[prost(string, optional, tag = "1")]
+    pub feed_id: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(uint64, optional, tag = "2")]
+    pub limit: ::core::option::Option<u64>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListPublicationsResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub publications: ::prost::alloc::vec::Vec<PublicationItem>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PublicationInfoRequest {
+    #[prost(string, required, tag = "1")]
+    pub event_id: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PublicationInfoResponse {
+    #[prost(message, required, tag = "1")]
+    pub publication: PublicationItem,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OutboxDelivery {
+    #[prost(string, required, tag = "1")]
+    pub relay: ::prost::alloc::string::String,
+    #[prost(bool, required, tag = "2")]
+    pub acked: bool,
+    #[prost(uint32, required, tag = "3")]
+    pub attempts: u32,
+    #[prost(int64, required, tag = "4")]
+    pub next_attempt: i64,
+    #[prost(string, optional, tag = "5")]
+    pub last_error: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OutboxItem {
+    #[prost(string, required, tag = "1")]
+    pub feed_id: ::prost::alloc::string::String,
+    #[prost(string, required, tag = "2")]
+    pub entry_id: ::prost::alloc::string::String,
+    #[prost(string, required, tag = "3")]
+    pub profile_id: ::prost::alloc::string::String,
+    #[prost(string, required, tag = "4")]
+    pub event_id: ::prost::alloc::string::String,
+    /// One of pending or failed
+    #[prost(string, required, tag = "5")]
+    pub status: ::prost::alloc::string::String,
+    #[prost(uint64, required, tag = "6")]
+    pub required_acks: u64,
+    #[prost(message, repeated, tag = "7")]
+    pub deliveries: ::prost::alloc::vec::Vec<OutboxDelivery>,
+    #[prost(int64, required, tag = "8")]
+    pub created_at: i64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListOutboxRequest {
+    /// Events of every feed are listed when no feed is provided
+    #[prost(string, optional, tag = "1")]
+    pub feed_id: ::core::option::Option<::prost::alloc::string::String>,
+    /// Filters the events by status: pending or failed
+    #[prost(string, optional, tag = "2")]
+    pub status: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListOutboxResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub events: ::prost::alloc::vec::Vec<OutboxItem>,
+}
 /// Generated client implementations.
 pub mod nostrss_grpc_client {
     #![allow(
@@ -500,11 +962,11 @@ pub mod nostrss_grpc_client {
                 .insert(GrpcMethod::new("nostrss.NostrssGRPC", "AddFeed"));
             self.inner.unary(req, path, codec).await
         }
-        pub async fn start_job(
+        pub async fn update_feed(
             &mut self,
-            request: impl tonic::IntoRequest<super::StartJobRequest>,
+            request: impl tonic::IntoRequest<super::UpdateFeedRequest>,
         ) -> std::result::Result<
-            tonic::Response<super::StartJobResponse>,
+            tonic::Response<super::UpdateFeedResponse>,
             tonic::Status,
         > {
             self.inner
@@ -517,18 +979,18 @@ pub mod nostrss_grpc_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/nostrss.NostrssGRPC/StartJob",
+                "/nostrss.NostrssGRPC/UpdateFeed",
             );
             let mut req = request.into_request();
             req.extensions_mut()
-                .insert(GrpcMethod::new("nostrss.NostrssGRPC", "StartJob"));
+                .insert(GrpcMethod::new("nostrss.NostrssGRPC", "UpdateFeed"));
             self.inner.unary(req, path, codec).await
         }
-        pub async fn stop_job(
+        pub async fn discover_feeds(
             &mut self,
-            request: impl tonic::IntoRequest<super::StopJobRequest>,
+            request: impl tonic::IntoRequest<super::DiscoverFeedsRequest>,
         ) -> std::result::Result<
-            tonic::Response<super::StopJobResponse>,
+            tonic::Response<super::DiscoverFeedsResponse>,
             tonic::Status,
         > {
             self.inner
@@ -541,146 +1003,580 @@ pub mod nostrss_grpc_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/nostrss.NostrssGRPC/StopJob",
+                "/nostrss.NostrssGRPC/DiscoverFeeds",
             );
             let mut req = request.into_request();
             req.extensions_mut()
-                .insert(GrpcMethod::new("nostrss.NostrssGRPC", "StopJob"));
+                .insert(GrpcMethod::new("nostrss.NostrssGRPC", "DiscoverFeeds"));
             self.inner.unary(req, path, codec).await
         }
-    }
-}
-/// Generated server implementations.
-pub mod nostrss_grpc_server {
-    #![allow(
-        unused_variables,
-        dead_code,
-        missing_docs,
-        clippy::wildcard_imports,
-        clippy::let_unit_value,
-    )]
-    use tonic::codegen::*;
-    /// Generated trait containing gRPC methods that should be implemented for use with NostrssGrpcServer.
-    #[async_trait]
-    pub trait NostrssGrpc: std::marker::Send + std::marker::Sync + 'static {
-        async fn state(
-            &self,
-            request: tonic::Request<super::StateRequest>,
-        ) -> std::result::Result<tonic::Response<super::StateResponse>, tonic::Status>;
-        async fn profiles_list(
-            &self,
-            request: tonic::Request<super::ProfilesListRequest>,
+        pub async fn validate_feed(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ValidateFeedRequest>,
         ) -> std::result::Result<
-            tonic::Response<super::ProfilesListResponse>,
+            tonic::Response<super::ValidateFeedResponse>,
             tonic::Status,
-        >;
-        async fn profile_info(
-            &self,
-            request: tonic::Request<super::ProfileInfoRequest>,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/nostrss.NostrssGRPC/ValidateFeed",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("nostrss.NostrssGRPC", "ValidateFeed"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn preview_feed(
+            &mut self,
+            request: impl tonic::IntoRequest<super::PreviewFeedRequest>,
         ) -> std::result::Result<
-            tonic::Response<super::ProfileInfoResponse>,
+            tonic::Response<super::PreviewFeedResponse>,
             tonic::Status,
-        >;
-        async fn delete_profile(
-            &self,
-            request: tonic::Request<super::DeleteProfileRequest>,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/nostrss.NostrssGRPC/PreviewFeed",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("nostrss.NostrssGRPC", "PreviewFeed"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn relays_list(
+            &mut self,
+            request: impl tonic::IntoRequest<super::RelaysListRequest>,
         ) -> std::result::Result<
-            tonic::Response<super::DeleteProfileResponse>,
+            tonic::Response<super::RelaysListResponse>,
             tonic::Status,
-        >;
-        async fn add_profile(
-            &self,
-            request: tonic::Request<super::AddProfileRequest>,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/nostrss.NostrssGRPC/RelaysList",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("nostrss.NostrssGRPC", "RelaysList"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn relay_info(
+            &mut self,
+            request: impl tonic::IntoRequest<super::RelayInfoRequest>,
         ) -> std::result::Result<
-            tonic::Response<super::AddProfileResponse>,
+            tonic::Response<super::RelayInfoResponse>,
             tonic::Status,
-        >;
-        async fn feeds_list(
-            &self,
-            request: tonic::Request<super::FeedsListRequest>,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/nostrss.NostrssGRPC/RelayInfo",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("nostrss.NostrssGRPC", "RelayInfo"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn add_relay(
+            &mut self,
+            request: impl tonic::IntoRequest<super::AddRelayRequest>,
         ) -> std::result::Result<
-            tonic::Response<super::FeedsListResponse>,
+            tonic::Response<super::AddRelayResponse>,
             tonic::Status,
-        >;
-        async fn feed_info(
-            &self,
-            request: tonic::Request<super::FeedInfoRequest>,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/nostrss.NostrssGRPC/AddRelay",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("nostrss.NostrssGRPC", "AddRelay"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn delete_relay(
+            &mut self,
+            request: impl tonic::IntoRequest<super::DeleteRelayRequest>,
         ) -> std::result::Result<
-            tonic::Response<super::FeedInfoResponse>,
+            tonic::Response<super::DeleteRelayResponse>,
             tonic::Status,
-        >;
-        async fn delete_feed(
-            &self,
-            request: tonic::Request<super::DeleteFeedRequest>,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/nostrss.NostrssGRPC/DeleteRelay",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("nostrss.NostrssGRPC", "DeleteRelay"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn retract_entry(
+            &mut self,
+            request: impl tonic::IntoRequest<super::RetractEntryRequest>,
         ) -> std::result::Result<
-            tonic::Response<super::DeleteFeedResponse>,
+            tonic::Response<super::RetractEntryResponse>,
             tonic::Status,
-        >;
-        async fn add_feed(
-            &self,
-            request: tonic::Request<super::AddFeedRequest>,
-        ) -> std::result::Result<tonic::Response<super::AddFeedResponse>, tonic::Status>;
-        async fn start_job(
-            &self,
-            request: tonic::Request<super::StartJobRequest>,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/nostrss.NostrssGRPC/RetractEntry",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("nostrss.NostrssGRPC", "RetractEntry"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn feed_errors(
+            &mut self,
+            request: impl tonic::IntoRequest<super::FeedErrorsRequest>,
         ) -> std::result::Result<
-            tonic::Response<super::StartJobResponse>,
+            tonic::Response<super::FeedErrorsResponse>,
             tonic::Status,
-        >;
-        async fn stop_job(
-            &self,
-            request: tonic::Request<super::StopJobRequest>,
-        ) -> std::result::Result<tonic::Response<super::StopJobResponse>, tonic::Status>;
-    }
-    #[derive(Debug)]
-    pub struct NostrssGrpcServer<T> {
-        inner: Arc<T>,
-        accept_compression_encodings: EnabledCompressionEncodings,
-        send_compression_encodings: EnabledCompressionEncodings,
-        max_decoding_message_size: Option<usize>,
-        max_encoding_message_size: Option<usize>,
-    }
-    impl<T> NostrssGrpcServer<T> {
-        pub fn new(inner: T) -> Self {
-            Self::from_arc(Arc::new(inner))
-        }
-        pub fn from_arc(inner: Arc<T>) -> Self {
-            Self {
-                inner,
-                accept_compression_encodings: Default::default(),
-                send_compression_encodings: Default::default(),
-                max_decoding_message_size: None,
-                max_encoding_message_size: None,
-            }
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/nostrss.NostrssGRPC/FeedErrors",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("nostrss.NostrssGRPC", "FeedErrors"));
+            self.inner.unary(req, path, codec).await
         }
-        pub fn with_interceptor<F>(
-            inner: T,
-            interceptor: F,
-        ) -> InterceptedService<Self, F>
-        where
-            F: tonic::service::Interceptor,
-        {
-            InterceptedService::new(Self::new(inner), interceptor)
+        pub async fn feed_status(
+            &mut self,
+            request: impl tonic::IntoRequest<super::FeedStatusRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::FeedStatusResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/nostrss.NostrssGRPC/FeedStatus",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("nostrss.NostrssGRPC", "FeedStatus"));
+            self.inner.unary(req, path, codec).await
         }
-        /// Enable decompressing requests with the given encoding.
-        #[must_use]
-        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
-            self.accept_compression_encodings.enable(encoding);
-            self
+        pub async fn list_publications(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListPublicationsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListPublicationsResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/nostrss.NostrssGRPC/ListPublications",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("nostrss.NostrssGRPC", "ListPublications"));
+            self.inner.unary(req, path, codec).await
         }
-        /// Compress responses with the given encoding, if the client supports it.
-        #[must_use]
-        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
-            self.send_compression_encodings.enable(encoding);
-            self
+        pub async fn publication_info(
+            &mut self,
+            request: impl tonic::IntoRequest<super::PublicationInfoRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::PublicationInfoResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/nostrss.NostrssGRPC/PublicationInfo",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("nostrss.NostrssGRPC", "PublicationInfo"));
+            self.inner.unary(req, path, codec).await
         }
-        /// Limits the maximum size of a decoded message.
-        ///
-        /// Default: `4MB`
-        #[must_use]
-        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
-            self.max_decoding_message_size = Some(limit);
-            self
+        pub async fn list_outbox(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListOutboxRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListOutboxResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/nostrss.NostrssGRPC/ListOutbox",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("nostrss.NostrssGRPC", "ListOutbox"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn start_job(
+            &mut self,
+            request: impl tonic::IntoRequest<super::StartJobRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::StartJobResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/nostrss.NostrssGRPC/StartJob",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("nostrss.NostrssGRPC", "StartJob"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn stop_job(
+            &mut self,
+            request: impl tonic::IntoRequest<super::StopJobRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::StopJobResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/nostrss.NostrssGRPC/StopJob",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("nostrss.NostrssGRPC", "StopJob"));
+            self.inner.unary(req, path, codec).await
+        }
+    }
+}
+/// Generated server implementations.
+pub mod nostrss_grpc_server {
+    #![allow(
+        unused_variables,
+        dead_code,
+        missing_docs,
+        clippy::wildcard_imports,
+        clippy::let_unit_value,
+    )]
+    use tonic::codegen::*;
+    /// Generated trait containing gRPC methods that should be implemented for use with NostrssGrpcServer.
+    #[async_trait]
+    pub trait NostrssGrpc: std::marker::Send + std::marker::Sync + 'static {
+        async fn state(
+            &self,
+            request: tonic::Request<super::StateRequest>,
+        ) -> std::result::Result<tonic::Response<super::StateResponse>, tonic::Status>;
+        async fn profiles_list(
+            &self,
+            request: tonic::Request<super::ProfilesListRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ProfilesListResponse>,
+            tonic::Status,
+        >;
+        async fn profile_info(
+            &self,
+            request: tonic::Request<super::ProfileInfoRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ProfileInfoResponse>,
+            tonic::Status,
+        >;
+        async fn delete_profile(
+            &self,
+            request: tonic::Request<super::DeleteProfileRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::DeleteProfileResponse>,
+            tonic::Status,
+        >;
+        async fn add_profile(
+            &self,
+            request: tonic::Request<super::AddProfileRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::AddProfileResponse>,
+            tonic::Status,
+        >;
+        async fn feeds_list(
+            &self,
+            request: tonic::Request<super::FeedsListRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::FeedsListResponse>,
+            tonic::Status,
+        >;
+        async fn feed_info(
+            &self,
+            request: tonic::Request<super::FeedInfoRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::FeedInfoResponse>,
+            tonic::Status,
+        >;
+        async fn delete_feed(
+            &self,
+            request: tonic::Request<super::DeleteFeedRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::DeleteFeedResponse>,
+            tonic::Status,
+        >;
+        async fn add_feed(
+            &self,
+            request: tonic::Request<super::AddFeedRequest>,
+        ) -> std::result::Result<tonic::Response<super::AddFeedResponse>, tonic::Status>;
+        async fn update_feed(
+            &self,
+            request: tonic::Request<super::UpdateFeedRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::UpdateFeedResponse>,
+            tonic::Status,
+        >;
+        async fn discover_feeds(
+            &self,
+            request: tonic::Request<super::DiscoverFeedsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::DiscoverFeedsResponse>,
+            tonic::Status,
+        >;
+        async fn validate_feed(
+            &self,
+            request: tonic::Request<super::ValidateFeedRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ValidateFeedResponse>,
+            tonic::Status,
+        >;
+        async fn preview_feed(
+            &self,
+            request: tonic::Request<super::PreviewFeedRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::PreviewFeedResponse>,
+            tonic::Status,
+        >;
+        async fn relays_list(
+            &self,
+            request: tonic::Request<super::RelaysListRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::RelaysListResponse>,
+            tonic::Status,
+        >;
+        async fn relay_info(
+            &self,
+            request: tonic::Request<super::RelayInfoRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::RelayInfoResponse>,
+            tonic::Status,
+        >;
+        async fn add_relay(
+            &self,
+            request: tonic::Request<super::AddRelayRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::AddRelayResponse>,
+            tonic::Status,
+        >;
+        async fn delete_relay(
+            &self,
+            request: tonic::Request<super::DeleteRelayRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::DeleteRelayResponse>,
+            tonic::Status,
+        >;
+        async fn retract_entry(
+            &self,
+            request: tonic::Request<super::RetractEntryRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::RetractEntryResponse>,
+            tonic::Status,
+        >;
+        async fn feed_errors(
+            &self,
+            request: tonic::Request<super::FeedErrorsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::FeedErrorsResponse>,
+            tonic::Status,
+        >;
+        async fn feed_status(
+            &self,
+            request: tonic::Request<super::FeedStatusRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::FeedStatusResponse>,
+            tonic::Status,
+        >;
+        async fn list_publications(
+            &self,
+            request: tonic::Request<super::ListPublicationsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListPublicationsResponse>,
+            tonic::Status,
+        >;
+        async fn publication_info(
+            &self,
+            request: tonic::Request<super::PublicationInfoRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::PublicationInfoResponse>,
+            tonic::Status,
+        >;
+        async fn list_outbox(
+            &self,
+            request: tonic::Request<super::ListOutboxRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListOutboxResponse>,
+            tonic::Status,
+        >;
+        async fn start_job(
+            &self,
+            request: tonic::Request<super::StartJobRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::StartJobResponse>,
+            tonic::Status,
+        >;
+        async fn stop_job(
+            &self,
+            request: tonic::Request<super::StopJobRequest>,
+        ) -> std::result::Result<tonic::Response<super::StopJobResponse>, tonic::Status>;
+    }
+    #[derive(Debug)]
+    pub struct NostrssGrpcServer<T> {
+        inner: Arc<T>,
+        accept_compression_encodings: EnabledCompressionEncodings,
+        send_compression_encodings: EnabledCompressionEncodings,
+        max_decoding_message_size: Option<usize>,
+        max_encoding_message_size: Option<usize>,
+    }
+    impl<T> NostrssGrpcServer<T> {
+        pub fn new(inner: T) -> Self {
+            Self::from_arc(Arc::new(inner))
+        }
+        pub fn from_arc(inner: Arc<T>) -> Self {
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+                max_decoding_message_size: None,
+                max_encoding_message_size: None,
+            }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+        /// Enable decompressing requests with the given encoding.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.accept_compression_encodings.enable(encoding);
+            self
+        }
+        /// Compress responses with the given encoding, if the client supports it.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.send_compression_encodings.enable(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.max_decoding_message_size = Some(limit);
+            self
         }
         /// Limits the maximum size of an encoded message.
         ///
@@ -710,21 +1606,471 @@ pub mod nostrss_grpc_server {
             match req.uri().path() {
                 "/nostrss.NostrssGRPC/State" => {
                     #[allow(non_camel_case_types)]
-                    struct StateSvc<T: NostrssGrpc>(pub Arc<T>);
-                    impl<T: NostrssGrpc> tonic::server::UnaryService<super::StateRequest>
-                    for StateSvc<T> {
-                        type Response = super::StateResponse;
+                    struct StateSvc<T: NostrssGrpc>(pub Arc<T>);
+                    impl<T: NostrssGrpc> tonic::server::UnaryService<super::StateRequest>
+                    for StateSvc<T> {
+                        type Response = super::StateResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::StateRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as NostrssGrpc>::state(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = StateSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/nostrss.NostrssGRPC/ProfilesList" => {
+                    #[allow(non_camel_case_types)]
+                    struct ProfilesListSvc<T: NostrssGrpc>(pub Arc<T>);
+                    impl<
+                        T: NostrssGrpc,
+                    > tonic::server::UnaryService<super::ProfilesListRequest>
+                    for ProfilesListSvc<T> {
+                        type Response = super::ProfilesListResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ProfilesListRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as NostrssGrpc>::profiles_list(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ProfilesListSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/nostrss.NostrssGRPC/ProfileInfo" => {
+                    #[allow(non_camel_case_types)]
+                    struct ProfileInfoSvc<T: NostrssGrpc>(pub Arc<T>);
+                    impl<
+                        T: NostrssGrpc,
+                    > tonic::server::UnaryService<super::ProfileInfoRequest>
+                    for ProfileInfoSvc<T> {
+                        type Response = super::ProfileInfoResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ProfileInfoRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as NostrssGrpc>::profile_info(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ProfileInfoSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/nostrss.NostrssGRPC/DeleteProfile" => {
+                    #[allow(non_camel_case_types)]
+                    struct DeleteProfileSvc<T: NostrssGrpc>(pub Arc<T>);
+                    impl<
+                        T: NostrssGrpc,
+                    > tonic::server::UnaryService<super::DeleteProfileRequest>
+                    for DeleteProfileSvc<T> {
+                        type Response = super::DeleteProfileResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::DeleteProfileRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as NostrssGrpc>::delete_profile(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = DeleteProfileSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/nostrss.NostrssGRPC/AddProfile" => {
+                    #[allow(non_camel_case_types)]
+                    struct AddProfileSvc<T: NostrssGrpc>(pub Arc<T>);
+                    impl<
+                        T: NostrssGrpc,
+                    > tonic::server::UnaryService<super::AddProfileRequest>
+                    for AddProfileSvc<T> {
+                        type Response = super::AddProfileResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::AddProfileRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as NostrssGrpc>::add_profile(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = AddProfileSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/nostrss.NostrssGRPC/FeedsList" => {
+                    #[allow(non_camel_case_types)]
+                    struct FeedsListSvc<T: NostrssGrpc>(pub Arc<T>);
+                    impl<
+                        T: NostrssGrpc,
+                    > tonic::server::UnaryService<super::FeedsListRequest>
+                    for FeedsListSvc<T> {
+                        type Response = super::FeedsListResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::FeedsListRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as NostrssGrpc>::feeds_list(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = FeedsListSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/nostrss.NostrssGRPC/FeedInfo" => {
+                    #[allow(non_camel_case_types)]
+                    struct FeedInfoSvc<T: NostrssGrpc>(pub Arc<T>);
+                    impl<
+                        T: NostrssGrpc,
+                    > tonic::server::UnaryService<super::FeedInfoRequest>
+                    for FeedInfoSvc<T> {
+                        type Response = super::FeedInfoResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::FeedInfoRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as NostrssGrpc>::feed_info(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = FeedInfoSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/nostrss.NostrssGRPC/DeleteFeed" => {
+                    #[allow(non_camel_case_types)]
+                    struct DeleteFeedSvc<T: NostrssGrpc>(pub Arc<T>);
+                    impl<
+                        T: NostrssGrpc,
+                    > tonic::server::UnaryService<super::DeleteFeedRequest>
+                    for DeleteFeedSvc<T> {
+                        type Response = super::DeleteFeedResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::DeleteFeedRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as NostrssGrpc>::delete_feed(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = DeleteFeedSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/nostrss.NostrssGRPC/AddFeed" => {
+                    #[allow(non_camel_case_types)]
+                    struct AddFeedSvc<T: NostrssGrpc>(pub Arc<T>);
+                    impl<
+                        T: NostrssGrpc,
+                    > tonic::server::UnaryService<super::AddFeedRequest>
+                    for AddFeedSvc<T> {
+                        type Response = super::AddFeedResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::AddFeedRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as NostrssGrpc>::add_feed(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = AddFeedSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/nostrss.NostrssGRPC/UpdateFeed" => {
+                    #[allow(non_camel_case_types)]
+                    struct UpdateFeedSvc<T: NostrssGrpc>(pub Arc<T>);
+                    impl<
+                        T: NostrssGrpc,
+                    > tonic::server::UnaryService<super::UpdateFeedRequest>
+                    for UpdateFeedSvc<T> {
+                        type Response = super::UpdateFeedResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::UpdateFeedRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as NostrssGrpc>::update_feed(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = UpdateFeedSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/nostrss.NostrssGRPC/DiscoverFeeds" => {
+                    #[allow(non_camel_case_types)]
+                    struct DiscoverFeedsSvc<T: NostrssGrpc>(pub Arc<T>);
+                    impl<
+                        T: NostrssGrpc,
+                    > tonic::server::UnaryService<super::DiscoverFeedsRequest>
+                    for DiscoverFeedsSvc<T> {
+                        type Response = super::DiscoverFeedsResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::StateRequest>,
+                            request: tonic::Request<super::DiscoverFeedsRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as NostrssGrpc>::state(&inner, request).await
+                                <T as NostrssGrpc>::discover_feeds(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -735,7 +2081,7 @@ pub mod nostrss_grpc_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = StateSvc(inner);
+                        let method = DiscoverFeedsSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -751,25 +2097,25 @@ pub mod nostrss_grpc_server {
                     };
                     Box::pin(fut)
                 }
-                "/nostrss.NostrssGRPC/ProfilesList" => {
+                "/nostrss.NostrssGRPC/ValidateFeed" => {
                     #[allow(non_camel_case_types)]
-                    struct ProfilesListSvc<T: NostrssGrpc>(pub Arc<T>);
+                    struct ValidateFeedSvc<T: NostrssGrpc>(pub Arc<T>);
                     impl<
                         T: NostrssGrpc,
-                    > tonic::server::UnaryService<super::ProfilesListRequest>
-                    for ProfilesListSvc<T> {
-                        type Response = super::ProfilesListResponse;
+                    > tonic::server::UnaryService<super::ValidateFeedRequest>
+                    for ValidateFeedSvc<T> {
+                        type Response = super::ValidateFeedResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::ProfilesListRequest>,
+                            request: tonic::Request<super::ValidateFeedRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as NostrssGrpc>::profiles_list(&inner, request).await
+                                <T as NostrssGrpc>::validate_feed(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -780,7 +2126,7 @@ pub mod nostrss_grpc_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = ProfilesListSvc(inner);
+                        let method = ValidateFeedSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -796,25 +2142,25 @@ pub mod nostrss_grpc_server {
                     };
                     Box::pin(fut)
                 }
-                "/nostrss.NostrssGRPC/ProfileInfo" => {
+                "/nostrss.NostrssGRPC/PreviewFeed" => {
                     #[allow(non_camel_case_types)]
-                    struct ProfileInfoSvc<T: NostrssGrpc>(pub Arc<T>);
+                    struct PreviewFeedSvc<T: NostrssGrpc>(pub Arc<T>);
                     impl<
                         T: NostrssGrpc,
-                    > tonic::server::UnaryService<super::ProfileInfoRequest>
-                    for ProfileInfoSvc<T> {
-                        type Response = super::ProfileInfoResponse;
+                    > tonic::server::UnaryService<super::PreviewFeedRequest>
+                    for PreviewFeedSvc<T> {
+                        type Response = super::PreviewFeedResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::ProfileInfoRequest>,
+                            request: tonic::Request<super::PreviewFeedRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as NostrssGrpc>::profile_info(&inner, request).await
+                                <T as NostrssGrpc>::preview_feed(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -825,7 +2171,7 @@ pub mod nostrss_grpc_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = ProfileInfoSvc(inner);
+                        let method = PreviewFeedSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -841,25 +2187,25 @@ pub mod nostrss_grpc_server {
                     };
                     Box::pin(fut)
                 }
-                "/nostrss.NostrssGRPC/DeleteProfile" => {
+                "/nostrss.NostrssGRPC/RelaysList" => {
                     #[allow(non_camel_case_types)]
-                    struct DeleteProfileSvc<T: NostrssGrpc>(pub Arc<T>);
+                    struct RelaysListSvc<T: NostrssGrpc>(pub Arc<T>);
                     impl<
                         T: NostrssGrpc,
-                    > tonic::server::UnaryService<super::DeleteProfileRequest>
-                    for DeleteProfileSvc<T> {
-                        type Response = super::DeleteProfileResponse;
+                    > tonic::server::UnaryService<super::RelaysListRequest>
+                    for RelaysListSvc<T> {
+                        type Response = super::RelaysListResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::DeleteProfileRequest>,
+                            request: tonic::Request<super::RelaysListRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as NostrssGrpc>::delete_profile(&inner, request).await
+                                <T as NostrssGrpc>::relays_list(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -870,7 +2216,7 @@ pub mod nostrss_grpc_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = DeleteProfileSvc(inner);
+                        let method = RelaysListSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -886,25 +2232,25 @@ pub mod nostrss_grpc_server {
                     };
                     Box::pin(fut)
                 }
-                "/nostrss.NostrssGRPC/AddProfile" => {
+                "/nostrss.NostrssGRPC/RelayInfo" => {
                     #[allow(non_camel_case_types)]
-                    struct AddProfileSvc<T: NostrssGrpc>(pub Arc<T>);
+                    struct RelayInfoSvc<T: NostrssGrpc>(pub Arc<T>);
                     impl<
                         T: NostrssGrpc,
-                    > tonic::server::UnaryService<super::AddProfileRequest>
-                    for AddProfileSvc<T> {
-                        type Response = super::AddProfileResponse;
+                    > tonic::server::UnaryService<super::RelayInfoRequest>
+                    for RelayInfoSvc<T> {
+                        type Response = super::RelayInfoResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::AddProfileRequest>,
+                            request: tonic::Request<super::RelayInfoRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as NostrssGrpc>::add_profile(&inner, request).await
+                                <T as NostrssGrpc>::relay_info(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -915,7 +2261,7 @@ pub mod nostrss_grpc_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = AddProfileSvc(inner);
+                        let method = RelayInfoSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -931,25 +2277,25 @@ pub mod nostrss_grpc_server {
                     };
                     Box::pin(fut)
                 }
-                "/nostrss.NostrssGRPC/FeedsList" => {
+                "/nostrss.NostrssGRPC/AddRelay" => {
                     #[allow(non_camel_case_types)]
-                    struct FeedsListSvc<T: NostrssGrpc>(pub Arc<T>);
+                    struct AddRelaySvc<T: NostrssGrpc>(pub Arc<T>);
                     impl<
                         T: NostrssGrpc,
-                    > tonic::server::UnaryService<super::FeedsListRequest>
-                    for FeedsListSvc<T> {
-                        type Response = super::FeedsListResponse;
+                    > tonic::server::UnaryService<super::AddRelayRequest>
+                    for AddRelaySvc<T> {
+                        type Response = super::AddRelayResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::FeedsListRequest>,
+                            request: tonic::Request<super::AddRelayRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as NostrssGrpc>::feeds_list(&inner, request).await
+                                <T as NostrssGrpc>::add_relay(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -960,7 +2306,7 @@ pub mod nostrss_grpc_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = FeedsListSvc(inner);
+                        let method = AddRelaySvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -976,25 +2322,25 @@ pub mod nostrss_grpc_server {
                     };
                     Box::pin(fut)
                 }
-                "/nostrss.NostrssGRPC/FeedInfo" => {
+                "/nostrss.NostrssGRPC/DeleteRelay" => {
                     #[allow(non_camel_case_types)]
-                    struct FeedInfoSvc<T: NostrssGrpc>(pub Arc<T>);
+                    struct DeleteRelaySvc<T: NostrssGrpc>(pub Arc<T>);
                     impl<
                         T: NostrssGrpc,
-                    > tonic::server::UnaryService<super::FeedInfoRequest>
-                    for FeedInfoSvc<T> {
-                        type Response = super::FeedInfoResponse;
+                    > tonic::server::UnaryService<super::DeleteRelayRequest>
+                    for DeleteRelaySvc<T> {
+                        type Response = super::DeleteRelayResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::FeedInfoRequest>,
+                            request: tonic::Request<super::DeleteRelayRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as NostrssGrpc>::feed_info(&inner, request).await
+                                <T as NostrssGrpc>::delete_relay(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -1005,7 +2351,7 @@ pub mod nostrss_grpc_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = FeedInfoSvc(inner);
+                        let method = DeleteRelaySvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -1021,25 +2367,25 @@ pub mod nostrss_grpc_server {
                     };
                     Box::pin(fut)
                 }
-                "/nostrss.NostrssGRPC/DeleteFeed" => {
+                "/nostrss.NostrssGRPC/RetractEntry" => {
                     #[allow(non_camel_case_types)]
-                    struct DeleteFeedSvc<T: NostrssGrpc>(pub Arc<T>);
+                    struct RetractEntrySvc<T: NostrssGrpc>(pub Arc<T>);
                     impl<
                         T: NostrssGrpc,
-                    > tonic::server::UnaryService<super::DeleteFeedRequest>
-                    for DeleteFeedSvc<T> {
-                        type Response = super::DeleteFeedResponse;
+                    > tonic::server::UnaryService<super::RetractEntryRequest>
+                    for RetractEntrySvc<T> {
+                        type Response = super::RetractEntryResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::DeleteFeedRequest>,
+                            request: tonic::Request<super::RetractEntryRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as NostrssGrpc>::delete_feed(&inner, request).await
+                                <T as NostrssGrpc>::retract_entry(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -1050,7 +2396,7 @@ pub mod nostrss_grpc_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = DeleteFeedSvc(inner);
+                        let method = RetractEntrySvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -1066,25 +2412,25 @@ pub mod nostrss_grpc_server {
                     };
                     Box::pin(fut)
                 }
-                "/nostrss.NostrssGRPC/AddFeed" => {
+                "/nostrss.NostrssGRPC/FeedErrors" => {
                     #[allow(non_camel_case_types)]
-                    struct AddFeedSvc<T: NostrssGrpc>(pub Arc<T>);
+                    struct FeedErrorsSvc<T: NostrssGrpc>(pub Arc<T>);
                     impl<
                         T: NostrssGrpc,
-                    > tonic::server::UnaryService<super::AddFeedRequest>
-                    for AddFeedSvc<T> {
-                        type Response = super::AddFeedResponse;
+                    > tonic::server::UnaryService<super::FeedErrorsRequest>
+                    for FeedErrorsSvc<T> {
+                        type Response = super::FeedErrorsResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::AddFeedRequest>,
+                            request: tonic::Request<super::FeedErrorsRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as NostrssGrpc>::add_feed(&inner, request).await
+                                <T as NostrssGrpc>::feed_errors(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -1095,7 +2441,187 @@ pub mod nostrss_grpc_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = AddFeedSvc(inner);
+                        let method = FeedErrorsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/nostrss.NostrssGRPC/FeedStatus" => {
+                    #[allow(non_camel_case_types)]
+                    struct FeedStatusSvc<T: NostrssGrpc>(pub Arc<T>);
+                    impl<
+                        T: NostrssGrpc,
+                    > tonic::server::UnaryService<super::FeedStatusRequest>
+                    for FeedStatusSvc<T> {
+                        type Response = super::FeedStatusResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::FeedStatusRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as NostrssGrpc>::feed_status(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = FeedStatusSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/nostrss.NostrssGRPC/ListPublications" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListPublicationsSvc<T: NostrssGrpc>(pub Arc<T>);
+                    impl<
+                        T: NostrssGrpc,
+                    > tonic::server::UnaryService<super::ListPublicationsRequest>
+                    for ListPublicationsSvc<T> {
+                        type Response = super::ListPublicationsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListPublicationsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as NostrssGrpc>::list_publications(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ListPublicationsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/nostrss.NostrssGRPC/PublicationInfo" => {
+                    #[allow(non_camel_case_types)]
+                    struct PublicationInfoSvc<T: NostrssGrpc>(pub Arc<T>);
+                    impl<
+                        T: NostrssGrpc,
+                    > tonic::server::UnaryService<super::PublicationInfoRequest>
+                    for PublicationInfoSvc<T> {
+                        type Response = super::PublicationInfoResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::PublicationInfoRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as NostrssGrpc>::publication_info(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = PublicationInfoSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/nostrss.NostrssGRPC/ListOutbox" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListOutboxSvc<T: NostrssGrpc>(pub Arc<T>);
+                    impl<
+                        T: NostrssGrpc,
+                    > tonic::server::UnaryService<super::ListOutboxRequest>
+                    for ListOutboxSvc<T> {
+                        type Response = super::ListOutboxResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListOutboxRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as NostrssGrpc>::list_outbox(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ListOutboxSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(